@@ -36,6 +36,8 @@
 //! - Enabling and Disabling Open (Non Admin) Contributions to the Locked Escrow Funds
 //! - Freezing and Thawing the Escrow.
 //! - Closing the Escrow, and proportionally disbursing the remaining funds back to contributors.
+//! - Holding per-asset balances of fungible assets alongside the native escrow funds.
+//! - Reserving escrow funds for other pallets through the [`EscrowInterface`], so they can only be paid out as agreed.
 //! 
 //! ## Interface
 //!
@@ -51,11 +53,16 @@
 //! - `thaw_escrow` - Removes a freeze from an Escrow, enabling distributions, contributions, and changes in configuration.
 //! - `add_admin` - Adds an admin to the Escrow's admins.
 //! - `remove_admin` - Removes an admin from the Escrow's admins.
+//! - `fund_escrow_asset` - Transfers a fungible asset into the Escrow's asset account.
+//! - `payout_escrow_asset` - Distributes a fungible asset from the Escrow's asset account.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
 
+mod traits;
+pub use traits::EscrowInterface;
+
 #[cfg(test)]
 mod mock;
 
@@ -76,11 +83,14 @@ pub mod pallet {
 			LockIdentifier,
 			LockableCurrency,
 			WithdrawReasons, 
-			ExistenceRequirement::AllowDeath
+			ExistenceRequirement::AllowDeath,
+			tokens::fungibles,
 		}, 
-		sp_runtime::{traits::{Zero, CheckedSub}}
+		sp_runtime::{traits::{Zero, CheckedAdd, CheckedSub, Saturating, AccountIdConversion}},
+		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
+	use crate::EscrowInterface;
 
 	pub const VEC_LIMIT: u32 = u32::MAX;
 	pub const ESCROW_LOCK: LockIdentifier = *b"Escrowed";
@@ -89,6 +99,15 @@ pub mod pallet {
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type EscrowCurrency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber> + Clone + Eq;
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen;
+		type EscrowAssets: fungibles::Transfer<
+			Self::AccountId,
+			AssetId = Self::AssetId,
+			Balance = <Self::EscrowCurrency as Currency<Self::AccountId>>::Balance,
+		>;
+		/// Used to derive the accounts holding each escrow's fungible assets
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
 	}
 
 	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -123,10 +142,41 @@ pub mod pallet {
 	#[pallet::getter(fn administrator)]
 	pub(super) type Administrator<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, T::BlockNumber, OptionQuery>;
 
+	// Fungible assets can't be locked the way the native currency is,
+	// so they are held in an account derived from the pallet id and escrow id
+	#[pallet::storage]
+	#[pallet::getter(fn escrow_asset_balance)]
+	pub type EscrowAssetBalance<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AssetId, AssetBalanceOf<T>, ValueQuery>;
+
+	// Funds reserved by other pallets through the EscrowInterface, per escrow, asset
+	// (None being the native funds) and reservation, so that each agreement can only
+	// draw on its own reservation. Reserved funds can't be paid out by admins directly.
+	#[pallet::storage]
+	#[pallet::getter(fn reserved_funds)]
+	pub type ReservedFunds<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // escrow_id
+			NMapKey<Blake2_128Concat, Option<T::AssetId>>, // asset_id
+			NMapKey<Blake2_128Concat, T::Hash>, // reservation_id
+		),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	// The sum of all reservations, per escrow and asset
+	#[pallet::storage]
+	#[pallet::getter(fn total_reserved_funds)]
+	pub type TotalReservedFunds<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, Option<T::AssetId>, BalanceOf<T>, ValueQuery>;
+
 	pub type BalanceOf<T> = <<T as Config>::EscrowCurrency as Currency<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
 
+	pub type AssetBalanceOf<T> = <<T as Config>::EscrowAssets as fungibles::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -159,7 +209,22 @@ pub mod pallet {
 		AddAdministrator(T::AccountId, T::AccountId, T::AccountId),
 		/// Remove Administrator
 		/// [escrow, who, admin_to_remove]
-		RemoveAdministrator(T::AccountId, T::AccountId, T::AccountId)
+		RemoveAdministrator(T::AccountId, T::AccountId, T::AccountId),
+		/// Adds a fungible asset to the Escrow
+		/// [escrow, who, asset, amount]
+		FundEscrowAsset(T::AccountId, T::AccountId, T::AssetId, AssetBalanceOf<T>),
+		/// Pays out a fungible asset from the Escrow
+		/// [escrow, who, payee, asset, amount]
+		PayoutEscrowAsset(T::AccountId, T::AccountId, T::AccountId, T::AssetId, AssetBalanceOf<T>),
+		/// Reserves Escrow funds for an agreement, on the authority of an admin
+		/// [escrow, who, asset, amount]
+		ReserveEscrowFunds(T::AccountId, T::AccountId, Option<T::AssetId>, BalanceOf<T>),
+		/// Pays out reserved Escrow funds
+		/// [escrow, payee, asset, amount]
+		CommitEscrowFunds(T::AccountId, T::AccountId, Option<T::AssetId>, BalanceOf<T>),
+		/// Returns reserved Escrow funds to the uncommitted balance
+		/// [escrow, asset, amount]
+		ReleaseEscrowFunds(T::AccountId, Option<T::AssetId>, BalanceOf<T>),
 	}
 
 	// Errors inform users that escrow went wrong.
@@ -197,6 +262,12 @@ pub mod pallet {
 		InsufficientEscrowFunds,
 		/// Funder has insufficient balance for the intended transfer into the escrow
 		InsufficientBalance,
+		/// The Escrow still holds fungible assets, which must be paid out before closing
+		AssetFundsRemaining,
+		/// The Escrow has reserved funds, which must be committed or released before closing
+		ReservedFundsRemaining,
+		/// The Escrow has insufficient reserved funds to commit or release the intended amount
+		InsufficientReservedFunds,
 	}
 
 	#[pallet::call]
@@ -348,9 +419,9 @@ pub mod pallet {
 				Error::<T>::SelfDistributionAttempt
 			);
 
-			// Confirm distribution is smaller than escrow amount
+			// Confirm distribution is smaller than the escrow amount not reserved
 			ensure!(
-				(escrow_details.amount >= amount),
+				(Self::uncommitted_balance(&escrow_id, None) >= amount),
 				Error::<T>::InsufficientEscrowFunds
 			);
 			
@@ -410,6 +481,18 @@ pub mod pallet {
 				Error::<T>::Unauthorized
 			);
 
+			// Reserved funds are owed to agreements, so they can't be disbursed to contributors
+			ensure!(
+				<TotalReservedFunds<T>>::iter_prefix_values(&escrow_id).all(|amount| amount.is_zero()),
+				Error::<T>::ReservedFundsRemaining
+			);
+
+			// Asset funds have no contribution record, so they must be paid out first
+			ensure!(
+				<EscrowAssetBalance<T>>::iter_prefix_values(&escrow_id).all(|amount| amount.is_zero()),
+				Error::<T>::AssetFundsRemaining
+			);
+
 			// Cast the Total Contributed and Current Balance from Escrow to u128s 
 				// for use in calculating the distribution of the remaining balance
 			let escrow_total_at_closing: u128 = 
@@ -706,5 +789,314 @@ pub mod pallet {
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
+
+		/// A dispatchable to fund an escrow with a fungible asset
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3).ref_time())]
+		pub fn fund_escrow_asset(origin: OriginFor<T>, escrow_id: T::AccountId, asset_id: T::AssetId, amount: AssetBalanceOf<T>) -> DispatchResult {
+			// Check that our caller has signed the transaction
+			let funder = ensure_signed(origin)?;
+			
+			// Check that the passed in escrow exists
+			let escrow_details = <Escrow<T>>::get(&escrow_id).ok_or(<Error<T>>::NoSuchEscrow)?;
+			
+			// Check escrow isn't frozen
+			ensure!(
+				!escrow_details.is_frozen,
+				Error::<T>::Frozen
+			);
+			
+			// If escrow isn't open, confirm that origin is an admin
+			if !escrow_details.is_open {
+			ensure!(
+				escrow_details.admins.iter().any(|x| *x == funder.clone()),
+				Error::<T>::Unauthorized
+			);
+			}
+
+			// Move the asset into the escrow's asset account
+			<T::EscrowAssets as fungibles::Transfer<T::AccountId>>::transfer(
+				asset_id,
+				&funder,
+				&Self::escrow_asset_account(&escrow_id),
+				amount,
+				false,
+			)?;
+			<EscrowAssetBalance<T>>::try_mutate(
+				&escrow_id,
+				&asset_id,
+				| balance | -> DispatchResult {
+					*balance = balance.checked_add(&amount).ok_or(<Error<T>>::StorageOverflow)?;
+					Ok(())
+				}
+			)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::FundEscrowAsset(escrow_id, funder, asset_id, amount));
+			Ok(())
+		}
+
+		/// A dispatchable to payout a fungible asset from an escrow
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3).ref_time())]
+		pub fn payout_escrow_asset(origin: OriginFor<T>, payee: T::AccountId, escrow_id: T::AccountId, asset_id: T::AssetId, amount: AssetBalanceOf<T>) -> DispatchResult {
+			// Check that our caller has signed the transaction
+			let who = ensure_signed(origin)?;
+
+			Self::do_payout_escrow_asset(&escrow_id, &who, &payee, asset_id, amount)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::PayoutEscrowAsset(escrow_id, who, payee, asset_id, amount));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding an escrow's fungible assets
+		pub fn escrow_asset_account(escrow_id: &T::AccountId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(escrow_id)
+		}
+
+		/// Pays out a fungible asset held by an escrow, on behalf of one of its admins
+		pub fn do_payout_escrow_asset(
+			escrow_id: &T::AccountId,
+			admin: &T::AccountId,
+			payee: &T::AccountId,
+			asset_id: T::AssetId,
+			amount: AssetBalanceOf<T>,
+		) -> DispatchResult {
+			// Check that the passed in escrow exists
+			let escrow_details = <Escrow<T>>::get(escrow_id).ok_or(<Error<T>>::NoSuchEscrow)?;
+			
+			// Check escrow isn't frozen
+			ensure!(
+				!escrow_details.is_frozen,
+				Error::<T>::Frozen
+			);
+			
+			// Confirm that the caller is an admin
+			ensure!(
+				escrow_details.admins.iter().any(|x| x == admin),
+				Error::<T>::Unauthorized
+			);
+
+			// Confirm that payee is not an admin
+			ensure!(
+				!escrow_details.admins.iter().any(|x| x == payee),
+				Error::<T>::SelfDistributionAttempt
+			);
+
+			// Confirm distribution is smaller than the escrow's asset balance not reserved
+			ensure!(
+				Self::uncommitted_balance(escrow_id, Some(asset_id)) >= amount,
+				Error::<T>::InsufficientEscrowFunds
+			);
+			<EscrowAssetBalance<T>>::try_mutate(
+				escrow_id,
+				&asset_id,
+				| balance | -> DispatchResult {
+					*balance = balance.checked_sub(&amount).ok_or(<Error<T>>::InsufficientEscrowFunds)?;
+					Ok(())
+				}
+			)?;
+
+			// Send funds to payee
+			<T::EscrowAssets as fungibles::Transfer<T::AccountId>>::transfer(
+				asset_id,
+				&Self::escrow_asset_account(escrow_id),
+				payee,
+				amount,
+				false,
+			)?;
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Takes funds out of a reservation, and out of the escrow's reserved total
+		fn unreserve_funds(
+			escrow_id: &T::AccountId,
+			asset_id: Option<T::AssetId>,
+			reservation_id: &T::Hash,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			<ReservedFunds<T>>::try_mutate(
+				(escrow_id, asset_id, reservation_id),
+				| reserved | -> DispatchResult {
+					*reserved = reserved.checked_sub(&amount).ok_or(<Error<T>>::InsufficientReservedFunds)?;
+					Ok(())
+				}
+			)?;
+			<TotalReservedFunds<T>>::try_mutate(
+				escrow_id,
+				asset_id,
+				| reserved | -> DispatchResult {
+					*reserved = reserved.checked_sub(&amount).ok_or(<Error<T>>::InsufficientReservedFunds)?;
+					Ok(())
+				}
+			)
+		}
+	}
+
+	impl<T: Config> EscrowInterface<T::AccountId, T::AssetId, BalanceOf<T>, T::Hash> for Pallet<T> {
+		fn reserve_funds(
+			escrow_id: &T::AccountId,
+			admin: &T::AccountId,
+			asset_id: Option<T::AssetId>,
+			reservation_id: &T::Hash,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			// Check that the passed in escrow exists
+			let escrow_details = <Escrow<T>>::get(escrow_id).ok_or(<Error<T>>::NoSuchEscrow)?;
+
+			// Check escrow isn't frozen
+			ensure!(
+				!escrow_details.is_frozen,
+				Error::<T>::Frozen
+			);
+
+			// Confirm that the reservation is authorized by an admin
+			ensure!(
+				escrow_details.admins.iter().any(|x| x == admin),
+				Error::<T>::Unauthorized
+			);
+
+			// Confirm the reservation is covered by funds not already reserved
+			ensure!(
+				Self::uncommitted_balance(escrow_id, asset_id) >= amount,
+				Error::<T>::InsufficientEscrowFunds
+			);
+			<ReservedFunds<T>>::try_mutate(
+				(escrow_id, asset_id, reservation_id),
+				| reserved | -> DispatchResult {
+					*reserved = reserved.checked_add(&amount).ok_or(<Error<T>>::StorageOverflow)?;
+					Ok(())
+				}
+			)?;
+			<TotalReservedFunds<T>>::try_mutate(
+				escrow_id,
+				asset_id,
+				| reserved | -> DispatchResult {
+					*reserved = reserved.checked_add(&amount).ok_or(<Error<T>>::StorageOverflow)?;
+					Ok(())
+				}
+			)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::ReserveEscrowFunds(escrow_id.clone(), admin.clone(), asset_id, amount));
+			Ok(())
+		}
+
+		fn commit_funds(
+			escrow_id: &T::AccountId,
+			payee: &T::AccountId,
+			asset_id: Option<T::AssetId>,
+			reservation_id: &T::Hash,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			// Check that the passed in escrow exists
+			let escrow_details = <Escrow<T>>::get(escrow_id).ok_or(<Error<T>>::NoSuchEscrow)?;
+
+			// Check escrow isn't frozen
+			ensure!(
+				!escrow_details.is_frozen,
+				Error::<T>::Frozen
+			);
+
+			// Confirm that payee is not an admin
+			ensure!(
+				!escrow_details.admins.iter().any(|x| x == payee),
+				Error::<T>::SelfDistributionAttempt
+			);
+
+			// Confirm the payout is covered by the funds reserved for it
+			Self::unreserve_funds(escrow_id, asset_id, reservation_id, amount)?;
+
+			match asset_id {
+				Some(asset_id) => {
+					<EscrowAssetBalance<T>>::try_mutate(
+						escrow_id,
+						&asset_id,
+						| balance | -> DispatchResult {
+							*balance = balance.checked_sub(&amount).ok_or(<Error<T>>::InsufficientEscrowFunds)?;
+							Ok(())
+						}
+					)?;
+
+					// Send funds to payee
+					<T::EscrowAssets as fungibles::Transfer<T::AccountId>>::transfer(
+						asset_id,
+						&Self::escrow_asset_account(escrow_id),
+						payee,
+						amount,
+						false,
+					)?;
+				},
+				None => {
+					T::EscrowCurrency::remove_lock(ESCROW_LOCK, escrow_id);
+
+					// Send funds to payee
+					T::EscrowCurrency::transfer(
+						escrow_id,
+						payee,
+						amount,
+						AllowDeath,
+					)?;
+
+					// Update Escrow storage
+					<Escrow<T>>::try_mutate(
+						escrow_id, 
+						| maybe_escrow_details | -> DispatchResult {
+							let escrow_details =
+								maybe_escrow_details.as_mut().ok_or(<Error<T>>::NoSuchEscrow)?;
+							
+							escrow_details.amount = 
+								escrow_details.amount.checked_sub(&amount).ok_or(<Error<T>>::InsufficientEscrowFunds)?;
+							
+							T::EscrowCurrency::set_lock(
+								ESCROW_LOCK,
+								escrow_id,
+								escrow_details.amount,
+								WithdrawReasons::all(),
+							);
+							Ok(())
+						}
+					)?;
+				},
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::CommitEscrowFunds(escrow_id.clone(), payee.clone(), asset_id, amount));
+			Ok(())
+		}
+
+		fn release_funds(
+			escrow_id: &T::AccountId,
+			asset_id: Option<T::AssetId>,
+			reservation_id: &T::Hash,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			Self::unreserve_funds(escrow_id, asset_id, reservation_id, amount)?;
+
+			// Emit an event.
+			Self::deposit_event(Event::ReleaseEscrowFunds(escrow_id.clone(), asset_id, amount));
+			Ok(())
+		}
+
+		fn reserved_balance(
+			escrow_id: &T::AccountId,
+			asset_id: Option<T::AssetId>,
+			reservation_id: &T::Hash,
+		) -> BalanceOf<T> {
+			<ReservedFunds<T>>::get((escrow_id, asset_id, reservation_id))
+		}
+
+		fn uncommitted_balance(escrow_id: &T::AccountId, asset_id: Option<T::AssetId>) -> BalanceOf<T> {
+			let total = match asset_id {
+				Some(asset_id) => <EscrowAssetBalance<T>>::get(escrow_id, asset_id),
+				None => <Escrow<T>>::get(escrow_id)
+					.map(|escrow_details| escrow_details.amount)
+					.unwrap_or_else(Zero::zero),
+			};
+			total.saturating_sub(<TotalReservedFunds<T>>::get(escrow_id, asset_id))
+		}
 	}
 }
@@ -19,6 +19,7 @@ pub use frame_support::{
 		IdentityFee, Weight,
 	},
 	StorageValue,
+	PalletId,
 };
 
 // Configure a mock runtime to test the pallet.
@@ -30,6 +31,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances,
+		Assets: pallet_assets,
 		EscrowModule: pallet_escrow::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -77,9 +79,33 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
 }
 
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const EscrowPalletId: PalletId = PalletId(*b"py/escrw");
+}
+
 impl pallet_escrow::Config for Test {
 	type Event = Event;
 	type EscrowCurrency = Balances;
+	type AssetId = u32;
+	type EscrowAssets = Assets;
+	type PalletId = EscrowPalletId;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,5 +1,6 @@
-use crate::{mock::*, Error, /* Escrow, */ EscrowDetails};
+use crate::{mock::*, Error, /* Escrow, */ EscrowDetails, EscrowInterface};
 use frame_support::{assert_noop,  assert_ok, /* BoundedVec */};
+use sp_core::H256;
 
 const ACCOUNT_ID: u64 = 1;
 const OTHER_ACCOUNT_ID: u64 = 2;
@@ -396,3 +397,84 @@ fn correct_error_for_remove_admin_on_non_present_admin() {
 		assert_noop!(EscrowModule::remove_admin(Origin::signed(ACCOUNT_ID), YET_ANOTHER_ACCOUNT_ID, ACCOUNT_ID), Error::<Test>::AdminNotPresent);
 	});
 }
+
+
+/// Escrow Asset Tests
+const ASSET_ID: u32 = 7;
+
+#[test]
+fn fund_and_payout_escrow_asset_successfully_executes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), ASSET_ID, ACCOUNT_ID, true, 1));
+		assert_ok!(Assets::mint(Origin::signed(ACCOUNT_ID), ASSET_ID, ACCOUNT_ID, AMOUNT));
+		assert_ok!(EscrowModule::create_escrow(Origin::signed(ACCOUNT_ID)));
+		assert_ok!(EscrowModule::fund_escrow_asset(Origin::signed(ACCOUNT_ID), ACCOUNT_ID, ASSET_ID, AMOUNT));
+		assert_eq!(EscrowModule::escrow_asset_balance(ACCOUNT_ID, ASSET_ID), AMOUNT);
+		assert_eq!(Assets::balance(ASSET_ID, EscrowModule::escrow_asset_account(&ACCOUNT_ID)), AMOUNT);
+		assert_noop!(EscrowModule::payout_escrow_asset(Origin::signed(ACCOUNT_ID), OTHER_ACCOUNT_ID, ACCOUNT_ID, ASSET_ID, GREATER_AMOUNT), Error::<Test>::InsufficientEscrowFunds);
+		assert_ok!(EscrowModule::payout_escrow_asset(Origin::signed(ACCOUNT_ID), OTHER_ACCOUNT_ID, ACCOUNT_ID, ASSET_ID, AMOUNT));
+		assert_eq!(EscrowModule::escrow_asset_balance(ACCOUNT_ID, ASSET_ID), 0);
+		assert_eq!(Assets::balance(ASSET_ID, OTHER_ACCOUNT_ID), AMOUNT);
+	});
+}
+
+#[test]
+fn correct_error_for_close_escrow_with_asset_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), ASSET_ID, ACCOUNT_ID, true, 1));
+		assert_ok!(Assets::mint(Origin::signed(ACCOUNT_ID), ASSET_ID, ACCOUNT_ID, AMOUNT));
+		assert_ok!(EscrowModule::create_escrow(Origin::signed(ACCOUNT_ID)));
+		assert_ok!(EscrowModule::fund_escrow_asset(Origin::signed(ACCOUNT_ID), ACCOUNT_ID, ASSET_ID, AMOUNT));
+		assert_noop!(EscrowModule::close_escrow(Origin::signed(ACCOUNT_ID), ACCOUNT_ID), Error::<Test>::AssetFundsRemaining);
+	});
+}
+
+/// Escrow Interface Tests
+const RESERVATION_ID: H256 = H256([1; 32]);
+const OTHER_RESERVATION_ID: H256 = H256([2; 32]);
+
+#[test]
+fn reserve_commit_and_release_funds_successfully_executes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EscrowModule::create_escrow(Origin::signed(ACCOUNT_ID)));
+		assert_ok!(EscrowModule::fund_escrow(Origin::signed(ACCOUNT_ID), ACCOUNT_ID, AMOUNT));
+		assert_noop!(EscrowModule::reserve_funds(&ACCOUNT_ID, &OTHER_ACCOUNT_ID, None, &RESERVATION_ID, AMOUNT), Error::<Test>::Unauthorized);
+		assert_noop!(EscrowModule::reserve_funds(&ACCOUNT_ID, &ACCOUNT_ID, None, &RESERVATION_ID, GREATER_AMOUNT), Error::<Test>::InsufficientEscrowFunds);
+		assert_ok!(EscrowModule::reserve_funds(&ACCOUNT_ID, &ACCOUNT_ID, None, &RESERVATION_ID, AMOUNT));
+		assert_eq!(EscrowModule::reserved_balance(&ACCOUNT_ID, None, &RESERVATION_ID), AMOUNT);
+		assert_eq!(EscrowModule::uncommitted_balance(&ACCOUNT_ID, None), 0);
+		assert_ok!(EscrowModule::commit_funds(&ACCOUNT_ID, &OTHER_ACCOUNT_ID, None, &RESERVATION_ID, AMOUNT / 2));
+		assert_eq!(Balances::free_balance(OTHER_ACCOUNT_ID), AMOUNT / 2);
+		assert_ok!(EscrowModule::release_funds(&ACCOUNT_ID, None, &RESERVATION_ID, AMOUNT / 2));
+		assert_noop!(EscrowModule::commit_funds(&ACCOUNT_ID, &OTHER_ACCOUNT_ID, None, &RESERVATION_ID, 1), Error::<Test>::InsufficientReservedFunds);
+		assert_eq!(EscrowModule::uncommitted_balance(&ACCOUNT_ID, None), AMOUNT / 2);
+	});
+}
+
+#[test]
+fn reservations_can_only_be_drawn_on_by_their_own_agreement() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EscrowModule::create_escrow(Origin::signed(ACCOUNT_ID)));
+		assert_ok!(EscrowModule::fund_escrow(Origin::signed(ACCOUNT_ID), ACCOUNT_ID, AMOUNT));
+		assert_ok!(EscrowModule::reserve_funds(&ACCOUNT_ID, &ACCOUNT_ID, None, &RESERVATION_ID, AMOUNT / 2));
+		assert_ok!(EscrowModule::reserve_funds(&ACCOUNT_ID, &ACCOUNT_ID, None, &OTHER_RESERVATION_ID, AMOUNT / 4));
+		assert_eq!(EscrowModule::reserved_funds((ACCOUNT_ID, None::<u32>, RESERVATION_ID)), AMOUNT / 2);
+		assert_eq!(EscrowModule::total_reserved_funds(ACCOUNT_ID, None::<u32>), AMOUNT * 3 / 4);
+		assert_noop!(EscrowModule::commit_funds(&ACCOUNT_ID, &OTHER_ACCOUNT_ID, None, &OTHER_RESERVATION_ID, AMOUNT / 2), Error::<Test>::InsufficientReservedFunds);
+		assert_noop!(EscrowModule::release_funds(&ACCOUNT_ID, None, &OTHER_RESERVATION_ID, AMOUNT / 2), Error::<Test>::InsufficientReservedFunds);
+		assert_ok!(EscrowModule::commit_funds(&ACCOUNT_ID, &OTHER_ACCOUNT_ID, None, &OTHER_RESERVATION_ID, AMOUNT / 4));
+		assert_eq!(EscrowModule::reserved_funds((ACCOUNT_ID, None::<u32>, RESERVATION_ID)), AMOUNT / 2);
+		assert_eq!(EscrowModule::total_reserved_funds(ACCOUNT_ID, None::<u32>), AMOUNT / 2);
+	});
+}
+
+#[test]
+fn correct_error_for_payout_and_close_escrow_with_reserved_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EscrowModule::create_escrow(Origin::signed(ACCOUNT_ID)));
+		assert_ok!(EscrowModule::fund_escrow(Origin::signed(ACCOUNT_ID), ACCOUNT_ID, AMOUNT));
+		assert_ok!(EscrowModule::reserve_funds(&ACCOUNT_ID, &ACCOUNT_ID, None, &RESERVATION_ID, AMOUNT));
+		assert_noop!(EscrowModule::payout_escrow(Origin::signed(ACCOUNT_ID), OTHER_ACCOUNT_ID, ACCOUNT_ID, AMOUNT), Error::<Test>::InsufficientEscrowFunds);
+		assert_noop!(EscrowModule::close_escrow(Origin::signed(ACCOUNT_ID), ACCOUNT_ID), Error::<Test>::ReservedFundsRemaining);
+	});
+}
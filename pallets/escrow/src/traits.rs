@@ -0,0 +1,63 @@
+// This file is part of Ventur, it defines the interface other
+// pallets use to draw on escrow funds.
+
+// Copyright (C) 2022 Popular Coding LLC.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use frame_support::dispatch::DispatchResult;
+
+/// Lets other pallets earmark escrow funds for an agreement, and later pay
+/// them out or hand them back, without touching escrow storage directly.
+/// An `asset_id` of `None` refers to the escrow's native funds. Funds are
+/// reserved under a `reservation_id` identifying the agreement, and can
+/// only be paid out or handed back from that reservation.
+pub trait EscrowInterface<AccountId, AssetId, Balance, ReservationId> {
+	/// Reserves uncommitted escrow funds, on the authority of one of the escrow's admins
+	fn reserve_funds(
+		escrow_id: &AccountId,
+		admin: &AccountId,
+		asset_id: Option<AssetId>,
+		reservation_id: &ReservationId,
+		amount: Balance,
+	) -> DispatchResult;
+
+	/// Pays reserved escrow funds out to the payee
+	fn commit_funds(
+		escrow_id: &AccountId,
+		payee: &AccountId,
+		asset_id: Option<AssetId>,
+		reservation_id: &ReservationId,
+		amount: Balance,
+	) -> DispatchResult;
+
+	/// Returns reserved escrow funds to the escrow's uncommitted balance
+	fn release_funds(
+		escrow_id: &AccountId,
+		asset_id: Option<AssetId>,
+		reservation_id: &ReservationId,
+		amount: Balance,
+	) -> DispatchResult;
+
+	/// The escrow funds currently held in a reservation
+	fn reserved_balance(
+		escrow_id: &AccountId,
+		asset_id: Option<AssetId>,
+		reservation_id: &ReservationId,
+	) -> Balance;
+
+	/// The escrow funds that are neither reserved nor paid out
+	fn uncommitted_balance(escrow_id: &AccountId, asset_id: Option<AssetId>) -> Balance;
+}
@@ -59,12 +59,15 @@
 //! - Assigning NT-NFTs.
 //! - Burning NT-NFTs.
 //! - Discarding NT-NFTs.
+//! - Letting other pallets check the NT-NFTs an account holds as credentials, through the [`CredentialInspect`] trait.
+//! - Letting other pallets mint and assign NT-NFTs with item metadata, through the [`CredentialIssue`] trait.
 //! 
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
 //! - `create_collection` - Creates a collection administered by the calling AccountId.
+//! - `create_collection_with_auto_id` - Creates a collection under the next free collection id, reported in the `CreateCollection` event.
 //! - `freeze_collection` - Freeze a collection, preventing any further actions from being performed on the collection until it is thawed.
 //! - `thaw_collection` - Thaw a frozen collection, allowing actions to be performed on the collection.
 //! - `destroy_collection` - Destroy a collection. <This extrinsic is not yet implemented.>
@@ -72,26 +75,41 @@
 //! - `accept_assignment` - Accept an NT-NFT assignment.
 //! - `cancel_assignment` - Cancel an NT-NFT assignment.
 //! - `mint_ntnft` - Mint a new NT-NFT in a collection.
+//! - `mint_ntnft_with_auto_id` - Mint a new NT-NFT under the next free item id of the collection, reported in the `MintNTNFT` event.
 //! - `update_ntnft` - Update the metadata of a NT-NFT. <This extrinsic is not yet implemented.>
 //! - `burn_ntnft` - Burn a minted unassigned NT-NFT from a collection.
 //! - `discard_ntnft` - Discard an assigned NT-NFT.
+//!
+//! ### Hooks
+//!
+//! - `on_runtime_upgrade` - Runs the pallet's storage migrations, see [`migrations`]
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
 
+mod traits;
+pub use traits::{CredentialInspect, CredentialIssue};
+
 #[cfg(test)]
 mod mock;
 
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use frame_support::{pallet_prelude::*, storage::with_storage_layer};
 	use frame_system::pallet_prelude::*;
+	use crate::{CredentialInspect, CredentialIssue};
 
 	pub const VEC_LIMIT: u32 = u32::MAX;
+
+	/// How many ids in a row an allocation skips over because
+	/// they are already in use, before it gives up
+	pub const MAX_ID_PROBES: u32 = 64;
 	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
 	pub struct CollectionDetails<AccountId> {
@@ -111,15 +129,19 @@ pub mod pallet {
 		pub(super) is_accepted: bool,
 	}
 
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-        type CollectionId: Member + Parameter + MaxEncodedLen + Copy;
-		type ItemId: Member + Parameter + MaxEncodedLen + Copy;
+        type CollectionId: Member + Parameter + MaxEncodedLen + Copy + From<u32>;
+		type ItemId: Member + Parameter + MaxEncodedLen + Copy + From<u32>;
     }
 
 	#[pallet::storage]
@@ -131,50 +153,81 @@ pub mod pallet {
 			CollectionDetails<T::AccountId>, 
 			OptionQuery>;
 	
+	// Assignments are keyed by item as well as account,
+	// so an account can hold several NT-NFTs of a collection
 	#[pallet::storage]
 	#[pallet::getter(fn assignment)]
 	pub(super) type Assignment<T: Config> = 
-		StorageDoubleMap<_, 
-			Blake2_128Concat, 
-			T::CollectionId, 
-			Blake2_128Concat, 
-			T::AccountId, 
-			T::ItemId, 
+		StorageNMap<_, 
+			(
+				NMapKey<Blake2_128Concat, T::CollectionId>, 
+				NMapKey<Blake2_128Concat, T::AccountId>, 
+				NMapKey<Blake2_128Concat, T::ItemId>,
+			), 
+			(), 
 			OptionQuery>;
 			
 	#[pallet::storage]
 	#[pallet::getter(fn proposed_assignment)]
 	pub(super) type ProposedAssignment<T: Config> = 
-		StorageDoubleMap<_, 
-			Blake2_128Concat, 
-			T::CollectionId, 
-			Blake2_128Concat, 
-			T::AccountId, 
-			T::ItemId, 
+		StorageNMap<_, 
+			(
+				NMapKey<Blake2_128Concat, T::CollectionId>, 
+				NMapKey<Blake2_128Concat, T::AccountId>, 
+				NMapKey<Blake2_128Concat, T::ItemId>,
+			), 
+			(), 
 			OptionQuery>;
 	
 	// ToDo: Evaluate whether keeping cancelled assignments is worth the storage cost.
 	#[pallet::storage]
 	#[pallet::getter(fn canceled_assignment)]
 	pub(super) type CanceledAssignment<T: Config> = 
+		StorageNMap<_, 
+			(
+				NMapKey<Blake2_128Concat, T::CollectionId>, 
+				NMapKey<Blake2_128Concat, T::AccountId>, 
+				NMapKey<Blake2_128Concat, T::ItemId>,
+			), 
+			(), 
+			OptionQuery>;
+	
+	#[pallet::storage]
+	#[pallet::getter(fn item)]
+	pub(super) type Item<T: Config> = 
 		StorageDoubleMap<_, 
 			Blake2_128Concat, 
 			T::CollectionId, 
 			Blake2_128Concat, 
-			T::AccountId, 
 			T::ItemId, 
+			ItemDetails<T::AccountId>, 
 			OptionQuery>;
-	
+
+	// Metadata of NT-NFTs issued by other pallets, such as the RFP and
+	// payment agreement a completion credential was issued for
 	#[pallet::storage]
-	#[pallet::getter(fn item)]
-	pub(super) type Item<T: Config> = 
+	#[pallet::getter(fn item_metadata)]
+	pub(super) type ItemMetadata<T: Config> = 
 		StorageDoubleMap<_, 
 			Blake2_128Concat, 
 			T::CollectionId, 
 			Blake2_128Concat, 
 			T::ItemId, 
-			ItemDetails<T::AccountId>, 
+			BoundedVec<u8, ConstU32<{VEC_LIMIT}>>, 
 			OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn collection_id_counter)]
+	pub(super) type NextCollectionId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn item_id_counter)]
+	pub(super) type NextItemId<T: Config> = 
+		StorageMap<_, 
+			Blake2_128Concat, 
+			T::CollectionId, 
+			u32, 
+			ValueQuery>;
 	
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -238,6 +291,15 @@ pub mod pallet {
 		Unauthorized,
 		/// Caller is attempting to accept an ntnft they do not have an assignment for
 		NoAssignmentForThisAccount,
+		/// Every id the counter can allocate is taken, or the next [`MAX_ID_PROBES`] ids are
+		IdsExhausted,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>()
+		}
 	}
 
 	#[pallet::call]
@@ -251,19 +313,20 @@ pub mod pallet {
 			metadata_ipfs_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(!<Collection<T>>::contains_key(&collection_id), <Error<T>>::CollectionIdAlreadyExists);
-			<Collection<T>>::insert(
-				collection_id, 
-				CollectionDetails {
-					owner: who.clone(),
-					amount: 0,
-					is_frozen: false,
-					image_ipfs_cid: image_ipfs_cid,
-					metadata_ipfs_cid: metadata_ipfs_cid,
-				});
+			Self::do_create_collection(who, collection_id, image_ipfs_cid, metadata_ipfs_cid)
+		}
 
-			Self::deposit_event(Event::CreateCollection(collection_id, who));
-			Ok(())
+		/// A dispatchable to create an NT-NFT Collection under the
+		/// next collection id allocated by the chain
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1 + MAX_ID_PROBES as u64, 3).ref_time())]
+		pub fn create_collection_with_auto_id(
+			origin: OriginFor<T>, 
+			image_ipfs_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>>,
+			metadata_ipfs_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let collection_id = Self::allocate_collection_id()?;
+			Self::do_create_collection(who, collection_id, image_ipfs_cid, metadata_ipfs_cid)
 		}
 
 		/// A dispatchable to freeze an NT-NFT Collection
@@ -330,41 +393,17 @@ pub mod pallet {
 		pub fn mint_ntnft(origin: OriginFor<T>, collection_id: T::CollectionId, ntnft_id: T::ItemId) -> DispatchResult {
 			// Ensure transaction signed, collection exists, and caller is authorized
 			let who = ensure_signed(origin)?;
+			Self::do_mint_ntnft(who, collection_id, ntnft_id)
+		}
 
-			// Check that collection exists
-			let collection_details = <Collection<T>>::get(&collection_id).ok_or(<Error<T>>::CollectionIdDoesNotExist)?;
-
-			// Check that collection is not frozen
-			ensure!(!collection_details.is_frozen, <Error<T>>::CollectionFrozen);
-
-			// Ensure that the caller is the owner
-			ensure!(who == collection_details.owner, <Error<T>>::Unauthorized);
-
-			// Check that item does not already exist
-			ensure!(!<Item<T>>::contains_key(&collection_id, &ntnft_id), <Error<T>>::ItemIdAlreadyExists);
-
-			// Insert Item and Update Collection
-			<Collection<T>>::try_mutate(
-				&collection_id, 
-				| maybe_collection_details | -> DispatchResult {
-					let collection_details =
-						maybe_collection_details.as_mut().ok_or(<Error<T>>::NoneValue)?;
-					let new_amount = 
-						collection_details.amount.checked_add(1).ok_or(<Error<T>>::StorageOverflow)?;
-					collection_details.amount = new_amount;
-					let item = ItemDetails{
-						owner: who.clone(),
-						is_assigned: false,
-						is_accepted: false,
-					};
-					<Item::<T>>::insert(&collection_id, &ntnft_id, item);
-					Ok(())
-				}
-			)?;
-			
-			// Deposit Event
-			Self::deposit_event(Event::MintNTNFT(collection_id, ntnft_id, who));
-			Ok(())
+		/// A dispatchable to mint an NT-NFT under the next
+		/// item id allocated by the chain for the collection
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2 + MAX_ID_PROBES as u64, 3).ref_time())]
+		pub fn mint_ntnft_with_auto_id(origin: OriginFor<T>, collection_id: T::CollectionId) -> DispatchResult {
+			// Ensure transaction signed, collection exists, and caller is authorized
+			let who = ensure_signed(origin)?;
+			let ntnft_id = Self::allocate_item_id(&collection_id)?;
+			Self::do_mint_ntnft(who, collection_id, ntnft_id)
 		}
 
 		/// A dispatchable to burn an NT-NFT
@@ -400,11 +439,12 @@ pub mod pallet {
 
 			// Remove Item
 			if item.is_accepted {
-				<Assignment<T>>::remove(&collection_id, &item.owner);
+				<Assignment<T>>::remove((&collection_id, &item.owner, &ntnft_id));
 			} else if item.is_assigned {
-				<ProposedAssignment<T>>::remove(&collection_id, &item.owner);
+				<ProposedAssignment<T>>::remove((&collection_id, &item.owner, &ntnft_id));
 			}
 			<Item<T>>::remove(&collection_id, &ntnft_id);
+			<ItemMetadata<T>>::remove(&collection_id, &ntnft_id);
 			
 			// Deposit Event
 			Self::deposit_event(Event::BurnNTNFT(collection_id, ntnft_id, who));
@@ -416,35 +456,7 @@ pub mod pallet {
 		pub fn assign_ntnft(origin: OriginFor<T>, collection_id: T::CollectionId, ntnft_id: T::ItemId, target_address: T::AccountId) -> DispatchResult {
 			// Ensure transaction is signed
 			let who = ensure_signed(origin)?;
-
-			// Check that collection exists
-			let collection_details = <Collection<T>>::get(&collection_id).ok_or(<Error<T>>::CollectionIdDoesNotExist)?;
-			
-			// Check that collection is not frozen
-			ensure!(!collection_details.is_frozen, <Error<T>>::CollectionFrozen);
-
-			// Ensure that the caller is the owner
-			ensure!(who == collection_details.owner, <Error<T>>::Unauthorized);
-
-			// Ensure the Item exists
-			ensure!(<Item<T>>::contains_key(&collection_id, &ntnft_id), <Error<T>>::ItemIdDoesNotExist);
-
-			<Item<T>>::try_mutate(
-				&collection_id, 
-				&ntnft_id, 
-				| maybe_item_details | -> DispatchResult {
-					let item_details =
-						maybe_item_details.as_mut().ok_or(<Error<T>>::ItemIdDoesNotExist)?;
-					ensure!(!item_details.is_accepted && !item_details.is_assigned, <Error<T>>::ItemIsAlreadyAssigned);
-					item_details.is_assigned = true;
-					<ProposedAssignment<T>>::insert(&collection_id, &target_address, ntnft_id);
-					Ok(())
-				}
-			)?;
-
-			// Deposit Event
-			Self::deposit_event(Event::AssignNTNFT(who, collection_id, ntnft_id, target_address));
-			Ok(())
+			Self::do_assign_ntnft(who, collection_id, ntnft_id, target_address)
 		}
 
 		/// A dispatchable to accept an NT-NFT assignment
@@ -454,7 +466,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 
 			// Check that target has a proposed assignment
-			ensure!(<ProposedAssignment<T>>::contains_key(&collection_id, &who), <Error<T>>::NoAssignmentForThisAccount);
+			ensure!(<ProposedAssignment<T>>::contains_key((&collection_id, &who, &ntnft_id)), <Error<T>>::NoAssignmentForThisAccount);
 			
 			// Update item assignment
 			<Item<T>>::try_mutate(
@@ -469,8 +481,8 @@ pub mod pallet {
 						<Error<T>>::ItemIsNotAssigned
 					);
 					item_details.is_accepted = true;
-					<ProposedAssignment<T>>::remove(&collection_id, &who);
-					<Assignment<T>>::insert(&collection_id, &who, ntnft_id);
+					<ProposedAssignment<T>>::remove((&collection_id, &who, &ntnft_id));
+					<Assignment<T>>::insert((&collection_id, &who, &ntnft_id), ());
 					Ok(())
 				}
 			)?;
@@ -487,7 +499,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 
 			// Check that target has a proposed assignment
-			ensure!(<ProposedAssignment<T>>::contains_key(&collection_id, &target_address), <Error<T>>::NoAssignmentForThisAccount);
+			ensure!(<ProposedAssignment<T>>::contains_key((&collection_id, &target_address, &ntnft_id)), <Error<T>>::NoAssignmentForThisAccount);
 
 			// Check that collection exists
 			let collection_details = <Collection<T>>::get(&collection_id).ok_or(<Error<T>>::CollectionIdDoesNotExist)?;
@@ -496,7 +508,7 @@ pub mod pallet {
 			ensure!(!collection_details.is_frozen, <Error<T>>::CollectionFrozen);
 
 			// Check that caller is authorized to call cancel (either collection owner, or asignee)
-			ensure!(<ProposedAssignment<T>>::contains_key(&collection_id, &who)||who == collection_details.owner, <Error<T>>::Unauthorized);
+			ensure!(<ProposedAssignment<T>>::contains_key((&collection_id, &who, &ntnft_id))||who == collection_details.owner, <Error<T>>::Unauthorized);
 
 			// Update item and cancel assignment
 			<Item<T>>::try_mutate(
@@ -512,8 +524,8 @@ pub mod pallet {
 					);
 					item_details.is_accepted = false;
 					item_details.is_assigned = false;
-					<ProposedAssignment<T>>::remove(&collection_id, &target_address);
-					<CanceledAssignment<T>>::insert(&collection_id, &target_address, ntnft_id);
+					<ProposedAssignment<T>>::remove((&collection_id, &target_address, &ntnft_id));
+					<CanceledAssignment<T>>::insert((&collection_id, &target_address, &ntnft_id), ());
 					Ok(())
 				}
 			)?;
@@ -530,7 +542,7 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 
 			// Check that the caller has the ntnft
-			ensure!(<Assignment<T>>::contains_key(&collection_id, &who), <Error<T>>::NoAssignmentForThisAccount);
+			ensure!(<Assignment<T>>::contains_key((&collection_id, &who, &ntnft_id)), <Error<T>>::NoAssignmentForThisAccount);
 
 			// Update item to unassign ntnft from the caller
 			<Item<T>>::try_mutate(
@@ -541,7 +553,7 @@ pub mod pallet {
 						maybe_item_details.as_mut().ok_or(<Error<T>>::ItemIdDoesNotExist)?;
 					item_details.is_accepted = false;
 					item_details.is_assigned = false;
-					<Assignment<T>>::remove(&collection_id, &who);
+					<Assignment<T>>::remove((&collection_id, &who, &ntnft_id));
 					Ok(())
 				}
 			)?;
@@ -551,4 +563,168 @@ pub mod pallet {
 			Ok(())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		/// Allocates the next collection id from the on-chain counter,
+		/// skipping up to [`MAX_ID_PROBES`] ids that are already in use
+		pub fn allocate_collection_id() -> Result<T::CollectionId, DispatchError> {
+			<NextCollectionId<T>>::try_mutate(
+				| next_id | -> Result<T::CollectionId, DispatchError> {
+					for _ in 0..MAX_ID_PROBES {
+						let collection_id: T::CollectionId = (*next_id).into();
+						*next_id = next_id.checked_add(1).ok_or(<Error<T>>::IdsExhausted)?;
+						if !<Collection<T>>::contains_key(&collection_id) {
+							return Ok(collection_id)
+						}
+					}
+					Err(<Error<T>>::IdsExhausted.into())
+				}
+			)
+		}
+
+		/// Allocates the next item id of a collection from its on-chain
+		/// counter, skipping up to [`MAX_ID_PROBES`] ids that are already in use
+		pub fn allocate_item_id(collection_id: &T::CollectionId) -> Result<T::ItemId, DispatchError> {
+			<NextItemId<T>>::try_mutate(
+				collection_id,
+				| next_id | -> Result<T::ItemId, DispatchError> {
+					for _ in 0..MAX_ID_PROBES {
+						let ntnft_id: T::ItemId = (*next_id).into();
+						*next_id = next_id.checked_add(1).ok_or(<Error<T>>::IdsExhausted)?;
+						if !<Item<T>>::contains_key(collection_id, &ntnft_id) {
+							return Ok(ntnft_id)
+						}
+					}
+					Err(<Error<T>>::IdsExhausted.into())
+				}
+			)
+		}
+
+		/// Creates a collection under the given id, owned by `who`
+		pub fn do_create_collection(
+			who: T::AccountId,
+			collection_id: T::CollectionId,
+			image_ipfs_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>>,
+			metadata_ipfs_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>>,
+		) -> DispatchResult {
+			ensure!(!<Collection<T>>::contains_key(&collection_id), <Error<T>>::CollectionIdAlreadyExists);
+			<Collection<T>>::insert(
+				collection_id, 
+				CollectionDetails {
+					owner: who.clone(),
+					amount: 0,
+					is_frozen: false,
+					image_ipfs_cid: image_ipfs_cid,
+					metadata_ipfs_cid: metadata_ipfs_cid,
+				});
+
+			Self::deposit_event(Event::CreateCollection(collection_id, who));
+			Ok(())
+		}
+
+		/// Mints an NT-NFT under the given id, if `who` owns the collection
+		pub fn do_mint_ntnft(who: T::AccountId, collection_id: T::CollectionId, ntnft_id: T::ItemId) -> DispatchResult {
+			// Check that collection exists
+			let collection_details = <Collection<T>>::get(&collection_id).ok_or(<Error<T>>::CollectionIdDoesNotExist)?;
+
+			// Check that collection is not frozen
+			ensure!(!collection_details.is_frozen, <Error<T>>::CollectionFrozen);
+
+			// Ensure that the caller is the owner
+			ensure!(who == collection_details.owner, <Error<T>>::Unauthorized);
+
+			// Check that item does not already exist
+			ensure!(!<Item<T>>::contains_key(&collection_id, &ntnft_id), <Error<T>>::ItemIdAlreadyExists);
+
+			// Insert Item and Update Collection
+			<Collection<T>>::try_mutate(
+				&collection_id, 
+				| maybe_collection_details | -> DispatchResult {
+					let collection_details =
+						maybe_collection_details.as_mut().ok_or(<Error<T>>::NoneValue)?;
+					let new_amount = 
+						collection_details.amount.checked_add(1).ok_or(<Error<T>>::StorageOverflow)?;
+					collection_details.amount = new_amount;
+					let item = ItemDetails{
+						owner: who.clone(),
+						is_assigned: false,
+						is_accepted: false,
+					};
+					<Item::<T>>::insert(&collection_id, &ntnft_id, item);
+					Ok(())
+				}
+			)?;
+			
+			// Deposit Event
+			Self::deposit_event(Event::MintNTNFT(collection_id, ntnft_id, who));
+			Ok(())
+		}
+
+		/// Assigns an NT-NFT to the target address, if `who` owns the collection
+		pub fn do_assign_ntnft(
+			who: T::AccountId,
+			collection_id: T::CollectionId,
+			ntnft_id: T::ItemId,
+			target_address: T::AccountId,
+		) -> DispatchResult {
+			// Check that collection exists
+			let collection_details = <Collection<T>>::get(&collection_id).ok_or(<Error<T>>::CollectionIdDoesNotExist)?;
+			
+			// Check that collection is not frozen
+			ensure!(!collection_details.is_frozen, <Error<T>>::CollectionFrozen);
+
+			// Ensure that the caller is the owner
+			ensure!(who == collection_details.owner, <Error<T>>::Unauthorized);
+
+			// Ensure the Item exists
+			ensure!(<Item<T>>::contains_key(&collection_id, &ntnft_id), <Error<T>>::ItemIdDoesNotExist);
+
+			<Item<T>>::try_mutate(
+				&collection_id, 
+				&ntnft_id, 
+				| maybe_item_details | -> DispatchResult {
+					let item_details =
+						maybe_item_details.as_mut().ok_or(<Error<T>>::ItemIdDoesNotExist)?;
+					ensure!(!item_details.is_accepted && !item_details.is_assigned, <Error<T>>::ItemIsAlreadyAssigned);
+					item_details.is_assigned = true;
+					<ProposedAssignment<T>>::insert((&collection_id, &target_address, &ntnft_id), ());
+					Ok(())
+				}
+			)?;
+
+			// Deposit Event
+			Self::deposit_event(Event::AssignNTNFT(who, collection_id, ntnft_id, target_address));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> CredentialInspect<T::AccountId, T::CollectionId> for Pallet<T> {
+		fn has_credential(who: &T::AccountId, collection_id: &T::CollectionId) -> bool {
+			<Assignment<T>>::iter_key_prefix((*collection_id, who.clone())).next().is_some()
+		}
+	}
+
+	impl<T: Config> CredentialIssue<T::AccountId, T::CollectionId> for Pallet<T> {
+		fn can_issue(issuer: &T::AccountId, collection_id: &T::CollectionId) -> bool {
+			<Collection<T>>::get(collection_id).map_or(false, |collection_details| {
+				!collection_details.is_frozen && collection_details.owner == *issuer
+			})
+		}
+
+		fn issue_credential(
+			issuer: &T::AccountId,
+			collection_id: &T::CollectionId,
+			who: &T::AccountId,
+			metadata: BoundedVec<u8, ConstU32<{VEC_LIMIT}>>,
+		) -> DispatchResult {
+			// Called outside of a dispatchable, so roll back the
+			// allocated id and minted item if anything fails
+			with_storage_layer(|| {
+				let ntnft_id = Self::allocate_item_id(collection_id)?;
+				Self::do_mint_ntnft(issuer.clone(), *collection_id, ntnft_id)?;
+				<ItemMetadata<T>>::insert(collection_id, &ntnft_id, metadata);
+				Self::do_assign_ntnft(issuer.clone(), *collection_id, ntnft_id, who.clone())
+			})
+		}
+	}
 }
\ No newline at end of file
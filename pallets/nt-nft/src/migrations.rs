@@ -0,0 +1,99 @@
+// This file is part of Ventur, it implements an NT-NFT,
+// Non-Transferable NFT, Substrate Pallet.
+
+// Copyright (C) 2022 Popular Coding LLC.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations for the NT-NFT pallet.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	sp_std::prelude::*,
+	storage_alias,
+	traits::StorageVersion,
+};
+
+/// Keys assignments by item as well as account, so an
+/// account can hold several NT-NFTs of the same collection.
+pub mod v1 {
+	use super::*;
+
+	/// The storage layout from before assignments were keyed by item,
+	/// when an account could only hold one NT-NFT of a collection
+	pub mod v0 {
+		use super::*;
+
+		#[storage_alias]
+		pub type Assignment<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as Config>::CollectionId,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			<T as Config>::ItemId,
+		>;
+
+		#[storage_alias]
+		pub type ProposedAssignment<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as Config>::CollectionId,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			<T as Config>::ItemId,
+		>;
+
+		#[storage_alias]
+		pub type CanceledAssignment<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as Config>::CollectionId,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			<T as Config>::ItemId,
+		>;
+	}
+
+	/// The old and new maps share their storage prefixes, so every old
+	/// entry is drained before any of them is stored under its new key
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let assignments = v0::Assignment::<T>::drain().collect::<Vec<_>>();
+		let proposed_assignments = v0::ProposedAssignment::<T>::drain().collect::<Vec<_>>();
+		let canceled_assignments = v0::CanceledAssignment::<T>::drain().collect::<Vec<_>>();
+		let translated = (assignments.len() + proposed_assignments.len() + canceled_assignments.len()) as u64;
+
+		for (collection_id, who, ntnft_id) in assignments {
+			<Assignment<T>>::insert((collection_id, who, ntnft_id), ());
+		}
+		for (collection_id, who, ntnft_id) in proposed_assignments {
+			<ProposedAssignment<T>>::insert((collection_id, who, ntnft_id), ());
+		}
+		for (collection_id, who, ntnft_id) in canceled_assignments {
+			<CanceledAssignment<T>>::insert((collection_id, who, ntnft_id), ());
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(
+			translated.saturating_add(1),
+			translated.saturating_mul(2).saturating_add(1)
+		)
+	}
+}
@@ -1,5 +1,5 @@
-use crate::{mock::*, Error, CollectionDetails};
-use frame_support::{assert_noop,  assert_ok};
+use crate::{mock::*, migrations, Error, CollectionDetails, CredentialInspect, CredentialIssue};
+use frame_support::{assert_noop,  assert_ok, traits::StorageVersion};
 use hex_literal::hex;
 use sp_runtime::BoundedVec;
 
@@ -380,6 +380,162 @@ fn discard_ntnft_fails_on_wrong_item() {
 		assert_ok!(NTNFTModule::mint_ntnft(Origin::signed(ACCOUNT_ID), COLLECTION_ID, NTNFT_ID));
 		assert_ok!(NTNFTModule::assign_ntnft(Origin::signed(ACCOUNT_ID), COLLECTION_ID, NTNFT_ID, OTHER_ACCOUNT_ID));
 		assert_ok!(NTNFTModule::accept_assignment(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, NTNFT_ID));
-		assert_noop!(NTNFTModule::discard_ntnft(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, OTHER_NTNFT_ID), Error::<Test>::ItemIdDoesNotExist);
+		assert_noop!(NTNFTModule::discard_ntnft(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, OTHER_NTNFT_ID), Error::<Test>::NoAssignmentForThisAccount);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn create_collection_and_mint_with_auto_id_skip_taken_ids() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let image_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"QmaG1CtUr74GPQwZeAnFhpiSgwtwGyR3zK2BRYh4DPDw3c".to_vec().try_into().unwrap();
+		let meta_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"Qmb232AquR57EMUGgU92TxeZ8QyAJF5nERjdPZRNNJoh6z".to_vec().try_into().unwrap();
+		assert_ok!(NTNFTModule::create_collection(
+			Origin::signed(ACCOUNT_ID), 
+			0,
+			image_cid.clone(),
+			meta_cid.clone(),
+		));
+		assert_ok!(NTNFTModule::create_collection_with_auto_id(
+			Origin::signed(ACCOUNT_ID), 
+			image_cid,
+			meta_cid,
+		));
+		System::assert_last_event(Event::NTNFTModule(crate::Event::CreateCollection(1, ACCOUNT_ID)));
+		assert!(NTNFTModule::collection(1).is_some());
+
+		assert_ok!(NTNFTModule::mint_ntnft(Origin::signed(ACCOUNT_ID), 1, 0));
+		assert_ok!(NTNFTModule::mint_ntnft_with_auto_id(Origin::signed(ACCOUNT_ID), 1));
+		System::assert_last_event(Event::NTNFTModule(crate::Event::MintNTNFT(1, 1, ACCOUNT_ID)));
+		assert!(NTNFTModule::item(1, 1).is_some());
+		assert_noop!(NTNFTModule::mint_ntnft_with_auto_id(Origin::signed(OTHER_ACCOUNT_ID), 1), Error::<Test>::Unauthorized);
+	});
+}
+
+#[test]
+fn create_collection_with_auto_id_gives_up_after_max_id_probes() {
+	new_test_ext().execute_with(|| {
+		let image_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"QmaG1CtUr74GPQwZeAnFhpiSgwtwGyR3zK2BRYh4DPDw3c".to_vec().try_into().unwrap();
+		let meta_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"Qmb232AquR57EMUGgU92TxeZ8QyAJF5nERjdPZRNNJoh6z".to_vec().try_into().unwrap();
+		for collection_id in 0..crate::MAX_ID_PROBES as u128 {
+			assert_ok!(NTNFTModule::create_collection(
+				Origin::signed(ACCOUNT_ID), 
+				collection_id,
+				image_cid.clone(),
+				meta_cid.clone(),
+			));
+		}
+		assert_noop!(
+			NTNFTModule::create_collection_with_auto_id(Origin::signed(ACCOUNT_ID), image_cid, meta_cid),
+			Error::<Test>::IdsExhausted
+		);
+	});
+}
+
+#[test]
+fn accepted_assignments_are_credentials() {
+	new_test_ext().execute_with(|| {
+		let image_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"QmaG1CtUr74GPQwZeAnFhpiSgwtwGyR3zK2BRYh4DPDw3c".to_vec().try_into().unwrap();
+		let meta_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"Qmb232AquR57EMUGgU92TxeZ8QyAJF5nERjdPZRNNJoh6z".to_vec().try_into().unwrap();
+		assert_ok!(NTNFTModule::create_collection(
+			Origin::signed(ACCOUNT_ID), 
+			COLLECTION_ID,
+			image_cid,
+			meta_cid,
+		));
+		assert_ok!(NTNFTModule::mint_ntnft(Origin::signed(ACCOUNT_ID), COLLECTION_ID, NTNFT_ID));
+		assert_ok!(NTNFTModule::assign_ntnft(Origin::signed(ACCOUNT_ID), COLLECTION_ID, NTNFT_ID, OTHER_ACCOUNT_ID));
+		// An assignment only counts once it is accepted
+		assert!(!NTNFTModule::has_credential(&OTHER_ACCOUNT_ID, &COLLECTION_ID));
+		assert_ok!(NTNFTModule::accept_assignment(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, NTNFT_ID));
+		assert!(NTNFTModule::has_credential(&OTHER_ACCOUNT_ID, &COLLECTION_ID));
+		assert!(!NTNFTModule::has_credential(&OTHER_ACCOUNT_ID, &OTHER_COLLECTION_ID));
+		assert_ok!(NTNFTModule::discard_ntnft(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, NTNFT_ID));
+		assert!(!NTNFTModule::has_credential(&OTHER_ACCOUNT_ID, &COLLECTION_ID));
+	});
+}
+
+#[test]
+fn issued_credentials_are_assigned_with_metadata() {
+	new_test_ext().execute_with(|| {
+		let image_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"QmaG1CtUr74GPQwZeAnFhpiSgwtwGyR3zK2BRYh4DPDw3c".to_vec().try_into().unwrap();
+		let meta_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"Qmb232AquR57EMUGgU92TxeZ8QyAJF5nERjdPZRNNJoh6z".to_vec().try_into().unwrap();
+		let metadata: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"rfp-1410".to_vec().try_into().unwrap();
+		assert_ok!(NTNFTModule::create_collection(
+			Origin::signed(ACCOUNT_ID), 
+			COLLECTION_ID,
+			image_cid,
+			meta_cid,
+		));
+		// Only the collection owner can issue its NT-NFTs
+		assert!(NTNFTModule::can_issue(&ACCOUNT_ID, &COLLECTION_ID));
+		assert!(!NTNFTModule::can_issue(&OTHER_ACCOUNT_ID, &COLLECTION_ID));
+		assert_noop!(
+			NTNFTModule::issue_credential(&OTHER_ACCOUNT_ID, &COLLECTION_ID, &OTHER_ACCOUNT_ID, metadata.clone()),
+			Error::<Test>::Unauthorized
+		);
+		assert_ok!(NTNFTModule::issue_credential(&ACCOUNT_ID, &COLLECTION_ID, &OTHER_ACCOUNT_ID, metadata.clone()));
+		let ntnft_id: u128 = 0;
+		assert_eq!(NTNFTModule::item_metadata(COLLECTION_ID, ntnft_id), Some(metadata));
+		assert!(NTNFTModule::proposed_assignment((COLLECTION_ID, OTHER_ACCOUNT_ID, ntnft_id)).is_some());
+		assert_ok!(NTNFTModule::accept_assignment(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, ntnft_id));
+		assert!(NTNFTModule::has_credential(&OTHER_ACCOUNT_ID, &COLLECTION_ID));
+	});
+}
+#[test]
+fn an_account_can_be_issued_several_credentials_from_a_collection() {
+	new_test_ext().execute_with(|| {
+		let image_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"QmaG1CtUr74GPQwZeAnFhpiSgwtwGyR3zK2BRYh4DPDw3c".to_vec().try_into().unwrap();
+		let meta_cid: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"Qmb232AquR57EMUGgU92TxeZ8QyAJF5nERjdPZRNNJoh6z".to_vec().try_into().unwrap();
+		let metadata: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"rfp-1410".to_vec().try_into().unwrap();
+		let other_metadata: BoundedVec<u8, ConstU32<{VEC_LIMIT}>> = b"rfp-1411".to_vec().try_into().unwrap();
+		assert_ok!(NTNFTModule::create_collection(
+			Origin::signed(ACCOUNT_ID), 
+			COLLECTION_ID,
+			image_cid,
+			meta_cid,
+		));
+		assert_ok!(NTNFTModule::issue_credential(&ACCOUNT_ID, &COLLECTION_ID, &OTHER_ACCOUNT_ID, metadata.clone()));
+		assert_ok!(NTNFTModule::issue_credential(&ACCOUNT_ID, &COLLECTION_ID, &OTHER_ACCOUNT_ID, other_metadata.clone()));
+
+		// The second credential doesn't replace the first
+		let (ntnft_id, other_ntnft_id): (u128, u128) = (0, 1);
+		assert!(NTNFTModule::proposed_assignment((COLLECTION_ID, OTHER_ACCOUNT_ID, ntnft_id)).is_some());
+		assert!(NTNFTModule::proposed_assignment((COLLECTION_ID, OTHER_ACCOUNT_ID, other_ntnft_id)).is_some());
+		assert_eq!(NTNFTModule::item_metadata(COLLECTION_ID, ntnft_id), Some(metadata));
+		assert_eq!(NTNFTModule::item_metadata(COLLECTION_ID, other_ntnft_id), Some(other_metadata));
+		assert_ok!(NTNFTModule::accept_assignment(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, ntnft_id));
+		assert_ok!(NTNFTModule::accept_assignment(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, other_ntnft_id));
+		assert!(NTNFTModule::assignment((COLLECTION_ID, OTHER_ACCOUNT_ID, ntnft_id)).is_some());
+		assert!(NTNFTModule::assignment((COLLECTION_ID, OTHER_ACCOUNT_ID, other_ntnft_id)).is_some());
+
+		// The account keeps the credential until it discards every NT-NFT of the collection
+		assert_ok!(NTNFTModule::discard_ntnft(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, ntnft_id));
+		assert!(NTNFTModule::has_credential(&OTHER_ACCOUNT_ID, &COLLECTION_ID));
+		assert_noop!(
+			NTNFTModule::discard_ntnft(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, ntnft_id),
+			Error::<Test>::NoAssignmentForThisAccount
+		);
+		assert_ok!(NTNFTModule::discard_ntnft(Origin::signed(OTHER_ACCOUNT_ID), COLLECTION_ID, other_ntnft_id));
+		assert!(!NTNFTModule::has_credential(&OTHER_ACCOUNT_ID, &COLLECTION_ID));
+	});
+}
+
+#[test]
+fn migrate_assignments_to_item_keys() {
+	new_test_ext().execute_with(|| {
+		migrations::v1::v0::Assignment::<Test>::insert(COLLECTION_ID, ACCOUNT_ID, NTNFT_ID);
+		migrations::v1::v0::ProposedAssignment::<Test>::insert(COLLECTION_ID, OTHER_ACCOUNT_ID, OTHER_NTNFT_ID);
+		migrations::v1::v0::CanceledAssignment::<Test>::insert(OTHER_COLLECTION_ID, OTHER_ACCOUNT_ID, NTNFT_ID);
+		StorageVersion::new(0).put::<NTNFTModule>();
+
+		migrations::v1::migrate::<Test>();
+		assert_eq!(StorageVersion::get::<NTNFTModule>(), StorageVersion::new(1));
+		assert!(NTNFTModule::assignment((COLLECTION_ID, ACCOUNT_ID, NTNFT_ID)).is_some());
+		assert!(NTNFTModule::has_credential(&ACCOUNT_ID, &COLLECTION_ID));
+		assert!(NTNFTModule::proposed_assignment((COLLECTION_ID, OTHER_ACCOUNT_ID, OTHER_NTNFT_ID)).is_some());
+		assert!(NTNFTModule::canceled_assignment((OTHER_COLLECTION_ID, OTHER_ACCOUNT_ID, NTNFT_ID)).is_some());
+		assert_eq!(migrations::v1::v0::Assignment::<Test>::iter().count(), 0);
+		assert_eq!(migrations::v1::v0::ProposedAssignment::<Test>::iter().count(), 0);
+	});
+}
@@ -0,0 +1,47 @@
+// This file is part of Ventur, it defines the interfaces other
+// pallets use to inspect and issue NT-NFT credentials.
+
+// Copyright (C) 2022 Popular Coding LLC.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use frame_support::{dispatch::DispatchResult, traits::ConstU32, BoundedVec};
+use crate::VEC_LIMIT;
+
+/// Lets other pallets check the credentials an account holds, such as a KYC
+/// badge or a certification, without touching NT-NFT storage directly.
+/// A credential is an NT-NFT from a collection, assigned to and accepted by the account.
+pub trait CredentialInspect<AccountId, CollectionId> {
+	/// Whether the account has accepted an NT-NFT from the collection
+	fn has_credential(who: &AccountId, collection_id: &CollectionId) -> bool;
+}
+
+/// Lets other pallets issue credentials, such as a record of completed work,
+/// on behalf of the owner of a collection.
+pub trait CredentialIssue<AccountId, CollectionId> {
+	/// Whether the issuer owns the collection, and it isn't frozen
+	fn can_issue(issuer: &AccountId, collection_id: &CollectionId) -> bool;
+
+	/// Mints an NT-NFT in the issuer's collection under the next free item id,
+	/// with the given metadata, and assigns it to the account, which still has
+	/// to accept it. Each credential is a separate item, so issuing another one
+	/// to the same account adds to the ones it already holds
+	fn issue_credential(
+		issuer: &AccountId,
+		collection_id: &CollectionId,
+		who: &AccountId,
+		metadata: BoundedVec<u8, ConstU32<{VEC_LIMIT}>>,
+	) -> DispatchResult;
+}
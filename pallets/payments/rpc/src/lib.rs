@@ -0,0 +1,94 @@
+// This file is part of Ventur, it exposes the payments
+// pallet's runtime API over RPC
+
+// Copyright (C) 2022 Popular Coding LLC.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the Payments pallet
+//!
+//! - `payments_paymentReceipts` - Returns the receipts of all completed
+//!   payment agreements paid out to a payee
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_payments_runtime_api::PaymentsApi as PaymentsRuntimeApi;
+
+#[rpc(client, server)]
+pub trait PaymentsApi<BlockHash, AccountId, PaymentReceipt> {
+	/// Returns the receipts of all completed payment agreements
+	/// paid out to the payee
+	#[method(name = "payments_paymentReceipts")]
+	fn payment_receipts(
+		&self,
+		payee: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<PaymentReceipt>>;
+}
+
+/// Provides RPC methods to query the payments pallet
+pub struct Payments<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Payments<C, Block> {
+	/// Creates a new instance of the Payments RPC handler
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error code returned when the runtime API call fails
+const RUNTIME_ERROR: i32 = 1;
+
+#[async_trait]
+impl<C, Block, AccountId, PaymentReceipt>
+	PaymentsApiServer<<Block as BlockT>::Hash, AccountId, PaymentReceipt> for Payments<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PaymentsRuntimeApi<Block, AccountId, PaymentReceipt>,
+	AccountId: Codec + Send + Sync + DeserializeOwned + 'static,
+	PaymentReceipt: Codec + Send + Sync + Serialize + 'static,
+{
+	fn payment_receipts(
+		&self,
+		payee: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<PaymentReceipt>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.payment_receipts(&at, payee).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				RUNTIME_ERROR,
+				"Unable to query payment receipts.",
+				Some(e.to_string()),
+			))
+			.into()
+		})
+	}
+}
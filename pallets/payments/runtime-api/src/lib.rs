@@ -0,0 +1,36 @@
+// This file is part of Ventur, it defines the runtime API
+// used to query the payments pallet
+
+// Copyright (C) 2022 Popular Coding LLC.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the Payments pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait PaymentsApi<AccountId, PaymentReceipt> where
+		AccountId: Codec,
+		PaymentReceipt: Codec,
+	{
+		/// Returns the receipts of all completed payment
+		/// agreements paid out to the payee
+		fn payment_receipts(payee: AccountId) -> Vec<PaymentReceipt>;
+	}
+}
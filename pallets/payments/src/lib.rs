@@ -24,6 +24,9 @@
 //! Payments can come out of an individual's account, or out of
 //! an escrow account set up by the payer of the payment agreement
 // 
+//! Payment agreements are proposed by the payer, and only become
+//! active once the payee accepts them before the proposal expires
+// 
 //! Payments must be claimed by individuals
 //! In the case of scheduled, iterative payments, payments can only
 //! be claimed if the claim comes after the scheduled payment date
@@ -45,17 +48,57 @@
 //! The Payments pallet provides functions for:
 //!
 //! - Setting up payments
-//! - Claiming payments
+//! - Claiming payments, in full or in part
 //! - Blocking/Releasing payments from being claimed
+//! - Adjusting the amount of unclaimed payments by mutual agreement
+//! - Streaming payments, which accrue every block at a fixed rate
+//! - Late-payment penalties on instalments that were blocked or underfunded once due
+//! - Payments in the native currency or in any fungible asset
+//! - Keeping compact receipts of completed payment agreements
+//! - Issuing completion credentials, NT-NFTs that record the work paid for,
+//!   to payees of completed payment agreements
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
-//! - `initialize_payment` - Creates the payment details and commits them to storage
+//! - `initialize_payment` - Proposes a payment agreement to the payee
+//! - `initialize_payment_with_auto_id` - Proposes a payment agreement under the next free
+//!   payment id, which is reported in the `PaymentProposed` event
+//! - `initialize_payment_batch` - Proposes many payment agreements funded by one escrow,
+//!   after checking the escrow can cover all of them
+//! - `accept_payment` - Payee accepts a proposed payment agreement, making it claimable
+//! - `reject_payment` - Payee rejects a proposed payment agreement
+//! - `withdraw_payment_proposal` - Payer withdraws a payment agreement the payee hasn't accepted
 //! - `claim` - Transfers the next available funds to the payee's account
+//! - `claim_available` - Transfers as much of the next payment as the payment source can cover,
+//!   keeping track of the remainder
 //! - `block_next_payment` - Prevent the claiming of the next and all subsequent payments
 //! - `release_next_payment` - Free up the next available and all subsequent payments for claiming
+//! - `report_late_payment` - Payee marks a due instalment that can't be claimed as late
+//! - `claim_late_penalty` - Transfers a late penalty the payment source couldn't cover
+//!   when its instalment was claimed to the payee
+//! - `propose_payment_adjustment` - Payer proposes a new amount for an unclaimed scheduled payment
+//! - `accept_payment_adjustment` - Payee accepts a proposed amount, updating the payment schedule
+//! - `initialize_stream` - Creates a payment stream that accrues a fixed amount every block
+//! - `withdraw_from_stream` - Transfers everything accrued so far in a stream to the payee
+//! - `pause_stream` - Stops a stream from accruing, settling what has accrued so far
+//! - `resume_stream` - Lets a paused stream accrue again from the current block
+//! - `cancel_stream` - Pays out what has accrued and closes the stream, returning the rest
+//! - `prune_receipt` - Payee removes the receipt of a completed payment agreement
+//! - `set_completion_credential` - Payer opts the agreements of an RFP into completion
+//!   credentials from one of their NT-NFT collections, or opts them out again
+//!
+//! Once the last scheduled payment of an agreement is claimed, the
+//! payment details are removed from storage and replaced by a
+//! [`PaymentReceipt`], which can be queried through the payments RPC.
+//! If the payer set a completion credential collection for the RFP, an
+//! NT-NFT is minted in it and assigned to the payee, with the SCALE encoded
+//! `(payer, rfp_reference_id, payment_id)` of the agreement as its metadata
+//!
+//! ### Hooks
+//!
+//! - `on_runtime_upgrade` - Runs the pallet's storage migrations, see [`migrations`]
 
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -70,6 +113,8 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
@@ -78,17 +123,36 @@ pub mod pallet {
 		traits::{
 			Currency, 
 			ExistenceRequirement::AllowDeath, 
-			WithdrawReasons, 
 			UnixTime,
 			LockableCurrency,
+			ReservableCurrency,
+			tokens::fungibles,
+		},
+		storage::{
+			bounded_vec::BoundedVec,
+			with_transaction,
+			TransactionOutcome,
 		},
-		storage::bounded_vec::BoundedVec,
+		sp_runtime::{
+			traits::{Hash, Saturating, Zero},
+			PerThing,
+			Permill,
+			SaturatedConversion,
+		},
+		sp_std::vec::Vec,
 	};
 	use frame_system::pallet_prelude::*;
-	use pallet_escrow;
+	use pallet_escrow::{self, EscrowInterface};
+	use pallet_ntnft::CredentialIssue;
+	#[cfg(feature = "std")]
+	use serde::{Deserialize, Serialize};
 
 	pub const VEC_LIMIT: u32 = u32::MAX;
 
+	/// How many ids in a row an allocation skips over because
+	/// they are already in use, before it gives up
+	pub const MAX_ID_PROBES: u32 = 64;
+
 	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
 	/// The struct that stores info about the payment agreement
@@ -131,6 +195,32 @@ pub mod pallet {
 		/// Admins will have special privileges w.r.t.
 		/// modifying payments
 		pub administrator_id: T::AccountId,
+
+		/// The fungible asset the payments are made in.
+		/// If None, payments are made in the PaymentCurrency,
+		/// otherwise escrow-sourced payments draw on the
+		/// escrow's balance of this asset
+		pub asset_id: Option<AssetIdOf<T>>,
+
+		/// Penalties owed to the payee for instalments paid late.
+		/// If None, no penalties accrue
+		pub late_payment_terms: Option<LatePaymentTerms>,
+	}
+
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo, Copy, MaxEncodedLen)]
+	/// The penalty a payer owes on top of an instalment that
+	/// could not be claimed once it was due
+	pub struct LatePaymentTerms {
+		/// How long, in seconds, an instalment can be late
+		/// before penalties start accruing
+		pub grace_period: u64,
+
+		/// The share of the instalment added as a penalty
+		/// for every full penalty period it is late
+		pub penalty_rate: Permill,
+
+		/// The length of a penalty period, in seconds
+		pub penalty_period: u64,
 	}
 
 	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
@@ -146,6 +236,14 @@ pub mod pallet {
 
 		/// If false, this instance is not eligible for claim
 		pub released: bool,
+
+		/// When the payment became late, because it was blocked
+		/// or underfunded after its payment date
+		pub delinquent_since: Option<u64>,
+
+		/// Late penalties already settled for this payment,
+		/// which are paid out along with it
+		pub late_penalty: BalanceOf<T>,
 	}
 
 	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, Eq, TypeInfo, Copy, MaxEncodedLen)]
@@ -166,22 +264,157 @@ pub mod pallet {
 		pub account_id: T::AccountId,
 	}
 
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// A late penalty the payment source couldn't cover when
+	/// the instalment it was owed on was claimed
+	pub struct OutstandingPenalty<T: Config> {
+		/// Where the penalty will be drawn from
+		pub payment_method: PaymentMethod<T>,
+
+		/// The fungible asset the penalty is paid in, if any
+		pub asset_id: Option<AssetIdOf<T>>,
+
+		/// The penalty still owed to the payee
+		pub amount: BalanceOf<T>,
+	}
+
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// A payment that accrues continuously, at a fixed rate
+	/// per block, until its total amount has been streamed
+	pub struct PaymentStream<T: Config> {
+		/// The amount that accrues to the payee every block
+		pub rate_per_block: BalanceOf<T>,
+
+		/// The most that will ever be streamed to the payee
+		pub total_amount: BalanceOf<T>,
+
+		/// Accrued funds that have not been withdrawn yet
+		pub accrued: BalanceOf<T>,
+
+		/// Funds that have already been withdrawn by the payee
+		pub withdrawn: BalanceOf<T>,
+
+		/// The block the stream started accruing at
+		pub start_block: T::BlockNumber,
+
+		/// The block up to which accruals have been settled
+		pub last_settled_block: T::BlockNumber,
+
+		/// If true, the stream is not accruing
+		pub is_paused: bool,
+
+		/// A struct describing where the streamed funds
+		/// will be coming from. Personal or escrow funds are
+		/// reserved up front for the whole stream
+		pub payment_method: PaymentMethod<T>,
+	}
+
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// A payment agreement proposed by the payer, awaiting
+	/// the payee's acceptance
+	pub struct PaymentProposal<T: Config> {
+		/// The payment agreement that becomes active on acceptance
+		pub payment_details: PaymentDetails<T>,
+
+		/// After this time the payee can no longer accept the proposal
+		pub expires_at: u64,
+	}
+
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// Keeps track of an active payment agreement's progress,
+	/// so that a receipt can be issued once it is completed
+	pub struct PaymentProgress<T: Config> {
+		/// When the payment agreement was initialized
+		pub started_at: u64,
+
+		/// How much has been paid out to the payee so far
+		pub amount_paid: BalanceOf<T>,
+	}
+
+	#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	/// A compact record of a completed payment agreement,
+	/// which payees can use as proof of income
+	pub struct PaymentReceipt<AccountId, PaymentId, RFPReferenceId, Balance, AssetId> {
+		/// The paying party of the payment agreement
+		pub payer: AccountId,
+
+		/// The party that was paid
+		pub payee: AccountId,
+
+		/// The id of the completed payment agreement
+		pub payment_id: PaymentId,
+
+		/// The id of the RFP associated with the payment agreement
+		pub rfp_reference_id: RFPReferenceId,
+
+		/// The total amount paid out to the payee
+		pub total_paid: Balance,
+
+		/// The fungible asset the payments were made in, if any
+		pub asset_id: Option<AssetId>,
+
+		/// When the payment agreement was initialized
+		pub started_at: u64,
+
+		/// When the last scheduled payment was claimed
+		pub completed_at: u64,
+	}
+
+	pub type PaymentReceiptOf<T> = PaymentReceipt<
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::PaymentId,
+		<T as Config>::RFPReferenceId,
+		BalanceOf<T>,
+		AssetIdOf<T>,
+	>;
+
 	pub type BalanceOf<T> = <<T as Config>::PaymentCurrency as Currency<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
 
+	pub type AssetIdOf<T> = <T as pallet_escrow::Config>::AssetId;
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_escrow::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type PaymentId: Member + Parameter + From<u32> + Clone + Eq + Copy + MaxEncodedLen;
 		type RFPReferenceId: Member + Parameter + MaxEncodedLen + From<u32> + Copy + Clone + Eq + TypeInfo;
-		type PaymentCurrency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber> + Clone + Eq;
+		type PaymentCurrency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>
+			+ ReservableCurrency<Self::AccountId>
+			+ Clone
+			+ Eq;
 		type TimeProvider: UnixTime;
+		type Assets: fungibles::Transfer<
+			Self::AccountId,
+			AssetId = AssetIdOf<Self>,
+			Balance = BalanceOf<Self>,
+		>;
+		/// The escrow that funds escrow-sourced payments and streams
+		type Escrow: EscrowInterface<Self::AccountId, AssetIdOf<Self>, BalanceOf<Self>, Self::Hash>;
+		/// How long, in seconds, a payee has to accept a proposed payment agreement
+		#[pallet::constant]
+		type PaymentProposalExpiry: Get<u64>;
+		/// The maximum number of payment agreements proposed in a single batch
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// Identifies the NT-NFT collection completion credentials are issued from
+		type CredentialCollectionId: Member + Parameter + MaxEncodedLen + Copy;
+		/// Issues completion credentials to the payees of completed payment agreements
+		type Credentials: CredentialIssue<Self::AccountId, Self::CredentialCollectionId>;
 	}
 
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 
@@ -201,20 +434,234 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn proposed_payment_agreements)]
+	/// Here we store payment agreements awaiting the payee's acceptance
+	/// Key: (payer, payee, payment_id)
+	/// Value: Payment Proposal
+	pub type ProposedPaymentAgreements<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // payer_account
+			NMapKey<Blake2_128Concat, T::AccountId>, // payee_account
+			NMapKey<Blake2_128Concat, T::PaymentId>, // paymentId
+		),
+		PaymentProposal<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn payment_progress)]
+	/// Here we track the progress of active payment agreements
+	/// Key: (payer, payee, payment_id)
+	/// Value: Payment Progress
+	pub type PaymentAgreementProgress<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // payer_account
+			NMapKey<Blake2_128Concat, T::AccountId>, // payee_account
+			NMapKey<Blake2_128Concat, T::PaymentId>, // paymentId
+		),
+		PaymentProgress<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn payment_receipts)]
+	/// Here we store the receipts of completed payment agreements
+	/// Key: (payee, payer, payment_id)
+	/// Value: Payment Receipt
+	pub type PaymentReceipts<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // payee_account
+			NMapKey<Blake2_128Concat, T::AccountId>, // payer_account
+			NMapKey<Blake2_128Concat, T::PaymentId>, // paymentId
+		),
+		PaymentReceiptOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn payment_adjustments)]
+	/// Amounts proposed by the payer for unclaimed scheduled payments,
+	/// awaiting the payee's acceptance
+	/// Key: (payer, payee, payment_id, payment_date)
+	/// Value: Proposed amount for the scheduled payment
+	pub type PaymentAdjustments<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // payer_account
+			NMapKey<Blake2_128Concat, T::AccountId>, // payee_account
+			NMapKey<Blake2_128Concat, T::PaymentId>, // paymentId
+			NMapKey<Blake2_128Concat, u64>, // payment_date
+		),
+		BalanceOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn payment_streams)]
+	/// Here we store all payment streams
+	/// Key: (payer, payee, payment_id)
+	/// Value: Payment Stream
+	pub type PaymentStreams<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // payer_account
+			NMapKey<Blake2_128Concat, T::AccountId>, // payee_account
+			NMapKey<Blake2_128Concat, T::PaymentId>, // paymentId
+		),
+		PaymentStream<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn outstanding_penalties)]
+	/// Late penalties owed to payees, which the payment source
+	/// couldn't cover when their instalments were claimed
+	/// Key: (payer, payee, payment_id)
+	/// Value: Outstanding Penalty
+	pub type OutstandingPenalties<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // payer_account
+			NMapKey<Blake2_128Concat, T::AccountId>, // payee_account
+			NMapKey<Blake2_128Concat, T::PaymentId>, // paymentId
+		),
+		OutstandingPenalty<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn payment_id_counter)]
+	/// The counter payment ids are allocated from, when the
+	/// payer leaves choosing the payment id to the chain
+	pub type NextPaymentId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn completion_credentials)]
+	/// The NT-NFT collections payers issue completion credentials from,
+	/// for the payment agreements of an RFP
+	/// Key: (payer, rfp_reference_id)
+	/// Value: Collection id
+	pub type CompletionCredentials<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::RFPReferenceId,
+		T::CredentialCollectionId,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Payment has successfully been initialized
+		/// Payment has been accepted by the payee and is now active
 		/// [payer, payee, total_payment_amount]
 		PaymentInitialized(T::AccountId, T::AccountId, BalanceOf<T>),
 
+		/// A payment agreement has been proposed to the payee
+		/// [payer, payee, payment_id, expires_at]
+		PaymentProposed(T::AccountId, T::AccountId, T::PaymentId, u64),
+
+		/// A batch of payment agreements funded by one escrow has been proposed
+		/// [payer, escrow_id, number_of_payments]
+		PaymentBatchProposed(T::AccountId, T::AccountId, u32),
+
+		/// The payee has rejected a proposed payment agreement
+		/// [payer, payee, payment_id]
+		PaymentProposalRejected(T::AccountId, T::AccountId, T::PaymentId),
+
+		/// The payer has withdrawn a proposed payment agreement
+		/// [payer, payee, payment_id]
+		PaymentProposalWithdrawn(T::AccountId, T::AccountId, T::PaymentId),
+
 		/// The next available payment has been claimed
-		/// [payee, amount_claimed]
-		PartOfPaymentClaimed(T::AccountId, BalanceOf<T>),
+		/// [payee, amount_claimed, late_penalty_included]
+		PartOfPaymentClaimed(T::AccountId, BalanceOf<T>, BalanceOf<T>),
+
+		/// The payee has reported that a due payment can't be claimed
+		/// [payee, payment_id, delinquent_since]
+		LatePaymentReported(T::AccountId, T::PaymentId, u64),
+
+		/// The payment source couldn't cover the late penalty on a claimed
+		/// payment, which stays owed to the payee
+		/// [payee, payment_id, late_penalty_owed]
+		LatePenaltyDeferred(T::AccountId, T::PaymentId, BalanceOf<T>),
+
+		/// A late penalty owed to the payee has been claimed
+		/// [payee, payment_id, late_penalty_paid]
+		LatePenaltyClaimed(T::AccountId, T::PaymentId, BalanceOf<T>),
 
 		/// The next available payment has been released or frozen
 		/// [payer, payment_id, payment_made_avialble_for_claim]
 		NextPaymentReleaseStatusChanged(T::AccountId, T::PaymentId, bool),
+
+		/// Part of the next available payment has been claimed,
+		/// the remainder stays scheduled
+		/// [payee, payment_id, amount_claimed, amount_remaining]
+		PartialPaymentClaimed(T::AccountId, T::PaymentId, BalanceOf<T>, BalanceOf<T>),
+
+		/// The payer has proposed a new amount for a scheduled payment
+		/// [payer, payee, payment_id, payment_date, proposed_amount]
+		PaymentAdjustmentProposed(T::AccountId, T::AccountId, T::PaymentId, u64, BalanceOf<T>),
+
+		/// The payee has accepted a new amount for a scheduled payment
+		/// [payer, payee, payment_id, payment_date, previous_amount, new_amount]
+		PaymentAdjustmentAccepted(
+			T::AccountId,
+			T::AccountId,
+			T::PaymentId,
+			u64,
+			BalanceOf<T>,
+			BalanceOf<T>,
+		),
+
+		/// A payment stream has been initialized
+		/// [payer, payee, payment_id, rate_per_block, total_amount]
+		StreamInitialized(T::AccountId, T::AccountId, T::PaymentId, BalanceOf<T>, BalanceOf<T>),
+
+		/// Accrued funds have been withdrawn from a payment stream
+		/// [payee, payment_id, amount_withdrawn]
+		StreamWithdrawn(T::AccountId, T::PaymentId, BalanceOf<T>),
+
+		/// A payment stream has been paused
+		/// [payer, payment_id]
+		StreamPaused(T::AccountId, T::PaymentId),
+
+		/// A payment stream has been resumed
+		/// [payer, payment_id]
+		StreamResumed(T::AccountId, T::PaymentId),
+
+		/// A payment stream has been cancelled
+		/// [payer, payment_id, amount_paid_to_payee, amount_returned_to_payer]
+		StreamCancelled(T::AccountId, T::PaymentId, BalanceOf<T>, BalanceOf<T>),
+
+		/// The last scheduled payment has been claimed and the
+		/// payment agreement has been replaced by a receipt
+		/// [payer, payee, payment_id, total_paid]
+		PaymentCompleted(T::AccountId, T::AccountId, T::PaymentId, BalanceOf<T>),
+
+		/// The receipt of a completed payment agreement has been removed
+		/// [payee, payer, payment_id]
+		ReceiptPruned(T::AccountId, T::AccountId, T::PaymentId),
+
+		/// The payer has set, or removed, the collection completion
+		/// credentials for the agreements of an RFP are issued from
+		/// [payer, rfp_reference_id, collection_id]
+		CompletionCredentialSet(T::AccountId, T::RFPReferenceId, Option<T::CredentialCollectionId>),
+
+		/// A completion credential has been assigned to the payee,
+		/// who can accept it through the NT-NFT pallet
+		/// [payer, payee, payment_id, collection_id]
+		CompletionCredentialIssued(T::AccountId, T::AccountId, T::PaymentId, T::CredentialCollectionId),
+
+		/// A completion credential couldn't be issued, because the payer
+		/// no longer owns the collection or it has been frozen
+		/// [payer, payee, payment_id, collection_id]
+		CompletionCredentialNotIssued(T::AccountId, T::AccountId, T::PaymentId, T::CredentialCollectionId),
 	}
 
 	#[pallet::error]
@@ -236,21 +683,64 @@ pub mod pallet {
 		/// meaning the payment cannot be claimed
 		PaymentNotAvailable,
 
-		/// A payment is specified for Escrow, but no associated
-		/// escrow account was found
-		NoEscrowAccountFound,
+		/// Trying to claim more funds than exist in an escrow
+		InsufficientEscrowFunds,
+
+		/// The payment source has no funds available to claim
+		NoFundsAvailable,
 
-		/// The Escrow account has been frozen
-		Frozen,
+		/// No scheduled payment exists for the specified payment date
+		ScheduledPaymentNotFound,
 
-		/// The payer accessing the escrow is not an admin
-		Unauthorized,
+		/// There is no proposed adjustment for the specified scheduled payment
+		NoAdjustmentProposed,
 
-		/// Attempting to claim payment from escrow for oneself
-		SelfDistributionAttempt,
+		/// A payment stream with the specified key already exists
+		StreamAlreadyInitialized,
 
-		/// Trying to claim more funds than exist in an escrow
-		InsufficientEscrowFunds,
+		/// Payment stream doesn't exist in storage with the specified key
+		StreamNonExistent,
+
+		/// The payment stream is already paused
+		StreamAlreadyPaused,
+
+		/// The payment stream is not paused
+		StreamNotPaused,
+
+		/// Personal payments must be drawn from the payer's own account
+		InvalidPaymentAccount,
+
+		/// Payment receipt doesn't exist in storage with the specified key
+		ReceiptNonExistent,
+
+		/// Payment proposal doesn't exist in storage with the specified key
+		ProposalNonExistent,
+
+		/// The next payment is not due, or can be claimed
+		PaymentNotLate,
+
+		/// No late penalty is owed for the payment agreement
+		NoPenaltyOutstanding,
+
+		/// The payment proposal has expired and can no longer be accepted
+		ProposalExpired,
+
+		/// A payment in a batch is not drawn from the batch's escrow
+		PaymentNotFromBatchEscrow,
+
+		/// Every payment id the counter can allocate is taken,
+		/// or the next [`MAX_ID_PROBES`] ids are
+		PaymentIdsExhausted,
+
+		/// The payer doesn't own the collection, or it is frozen
+		CannotIssueCredentials,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>()
+		}
 	}
 
 	#[pallet::call]
@@ -271,14 +761,13 @@ pub mod pallet {
 						maybe_payment_agreements
 						.as_mut()
 						.ok_or(<Error<T>>::PaymentDetailsNonExistent)?;
-					let payment_schedule = &mut payment_details.payment_schedule;
 					ensure!(
-						!payment_schedule.is_empty(), 
+						!payment_details.payment_schedule.is_empty(), 
 						<Error<T>>::NoScheduledPaymentRecorded
 					);
 
 					// Try to claim the next payment
-					let next_payment = payment_schedule.first().ok_or(
+					let next_payment = payment_details.payment_schedule.first().ok_or(
 						<Error<T>>::NoScheduledPaymentRecorded
 					)?;
 
@@ -288,29 +777,35 @@ pub mod pallet {
 						time >= next_payment.payment_date, 
 						<Error<T>>::PaymentNotAvailable
 					);
-					let payment_amount = next_payment.amount_per_claim;
-					let payment_method = &payment_details.payment_method;
-					let payment_account_id = &payment_method.account_id;
 					ensure!(next_payment.released, <Error<T>>::PaymentNotReleased);
-					if payment_method.payment_source == PaymentSource::PersonalAccount {
-						Pallet::<T>::transfer_funds_from_personal_account(
-							payment_account_id,
-							&payee,
-							payment_amount,
-						)?;
-					} else {
-						Pallet::<T>::transfer_funds_from_escrow_account(
-							payment_account_id,
-							&payer_id,
-							&payee,
-							payment_amount
-						)?
-					}
+
+					let late_penalty = Pallet::<T>::late_penalty(
+						&payment_details.late_payment_terms,
+						next_payment,
+						time,
+					);
+					let amount_per_claim = next_payment.amount_per_claim;
+					Pallet::<T>::transfer_funds(
+						&payment_details.payment_method,
+						payment_details.asset_id,
+						&Pallet::<T>::agreement_reservation(&payer_id, &payee, &payment_id),
+						&payee,
+						amount_per_claim,
+					)?;
 					
-					// If successfully claimed, get rid of the first payment
-					payment_schedule.remove(0);
+					// If successfully claimed, get rid of the first payment.
+					// Late penalties are paid on top of the scheduled amount
+					payment_details.payment_schedule.remove(0);
+					let late_penalty_paid = 
+						Pallet::<T>::settle_late_penalty(&payer_id, payment_details, late_penalty);
+					let payment_amount = amount_per_claim.saturating_add(late_penalty_paid);
+					Pallet::<T>::record_payment(&payer_id, payment_details, payment_amount);
+					if payment_details.payment_schedule.is_empty() {
+						Pallet::<T>::issue_receipt(&payer_id, payment_details);
+						*maybe_payment_agreements = None;
+					}
 					Self::deposit_event(
-						Event::PartOfPaymentClaimed(payee, payment_amount)
+						Event::PartOfPaymentClaimed(payee, payment_amount, late_penalty_paid)
 					);
 					Ok(())
 				}
@@ -318,29 +813,119 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
-		/// An extrinsic that initializes a payment and commits
-		/// it to storage
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1).ref_time())]
+		/// An extrinsic that proposes a payment agreement to the payee.
+		/// The agreement only becomes active, and claimable, once
+		/// the payee accepts it
 		pub fn initialize_payment (
 			origin: OriginFor<T>, 
 			payment_details: PaymentDetails<T>,
 		) -> DispatchResult {
 			let payer = ensure_signed(origin)?;
-			let payee = payment_details.payee.clone();
-			let payment_id = payment_details.payment_id;
-			let payment_details_exists = <PaymentAgreements<T>>::get(
-				(&payer, &payee, &payment_id)
+			Pallet::<T>::propose_payment(payer, payment_details)
+		}
+
+		#[pallet::weight(
+			10_000 + T::DbWeight::get()
+				.reads_writes(3 + 3 * MAX_ID_PROBES as u64, 2)
+				.ref_time()
+		)]
+		/// An extrinsic that proposes a payment agreement to the payee
+		/// under the next payment id allocated by the chain. The
+		/// payment id set in the payment details is ignored
+		pub fn initialize_payment_with_auto_id (
+			origin: OriginFor<T>, 
+			mut payment_details: PaymentDetails<T>,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			payment_details.payment_id = Pallet::<T>::allocate_payment_id(
+				&payer,
+				&payment_details.payee
+			)?;
+			Pallet::<T>::propose_payment(payer, payment_details)
+		}
+
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(4, 2).ref_time()
+				.saturating_mul(payments.len() as u64)
+		)]
+		/// An extrinsic that proposes a batch of payment agreements,
+		/// all funded by the same escrow. The combined payment schedules
+		/// must not exceed what the escrow has left uncommitted, and either
+		/// every agreement in the batch is proposed, or none of them are
+		pub fn initialize_payment_batch (
+			origin: OriginFor<T>, 
+			escrow_id: T::AccountId,
+			payments: BoundedVec<PaymentDetails<T>, T::MaxBatchSize>,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+
+			// Sum the obligations per asset before anything is reserved
+			let mut obligations: Vec<(Option<AssetIdOf<T>>, BalanceOf<T>)> = Vec::new();
+			for payment_details in payments.iter() {
+				ensure!(
+					payment_details.payment_method.payment_source == PaymentSource::EscrowAccount &&
+						payment_details.payment_method.account_id == escrow_id,
+					Error::<T>::PaymentNotFromBatchEscrow
+				);
+				let amount = Pallet::<T>::unclaimed_amount(payment_details);
+				match obligations.iter_mut().find(|(asset_id, _)| *asset_id == payment_details.asset_id) {
+					Some((_, total)) => *total = total.saturating_add(amount),
+					None => obligations.push((payment_details.asset_id, amount)),
+				}
+			}
+			for (asset_id, total) in obligations {
+				ensure!(
+					total <= T::Escrow::uncommitted_balance(&escrow_id, asset_id),
+					Error::<T>::InsufficientEscrowFunds
+				);
+			}
+
+			let number_of_payments = payments.len() as u32;
+			for payment_details in payments.into_iter() {
+				Pallet::<T>::propose_payment(payer.clone(), payment_details)?;
+			}
+			Self::deposit_event(
+				Event::PaymentBatchProposed(
+					payer,
+					escrow_id,
+					number_of_payments
+				)
 			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 3).ref_time())]
+		/// An extrinsic that lets the payee accept a proposed
+		/// payment agreement, making its payments claimable
+		pub fn accept_payment (
+			origin: OriginFor<T>, 
+			payer_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payee = ensure_signed(origin)?;
+			let payment_proposal = <ProposedPaymentAgreements<T>>::take(
+				(&payer_id, &payee, &payment_id)
+			).ok_or(<Error<T>>::ProposalNonExistent)?;
+			let time: u64 = T::TimeProvider::now().as_secs();
 			ensure!(
-				payment_details_exists.is_none(),
-				Error::<T>::PaymentAlreadyInitialized
+				time <= payment_proposal.expires_at,
+				<Error<T>>::ProposalExpired
 			);
+			let payment_details = payment_proposal.payment_details;
 			let total_payment_amount = payment_details.total_payment_amount;
 			let paying_account = payment_details.payment_method.account_id.clone();
 			<PaymentAgreements<T>>::insert(
-				(&payer, &payee, payment_id), 
+				(&payer_id, &payee, payment_id), 
 				payment_details
 			);
+			<PaymentAgreementProgress<T>>::insert(
+				(&payer_id, &payee, payment_id),
+				PaymentProgress {
+					started_at: time,
+					amount_paid: Zero::zero(),
+				}
+			);
 			Self::deposit_event(
 				Event::PaymentInitialized(
 					paying_account, 
@@ -351,6 +936,45 @@ pub mod pallet {
 			Ok(())
 		}
 
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
+		/// An extrinsic that lets the payee reject a proposed
+		/// payment agreement
+		pub fn reject_payment (
+			origin: OriginFor<T>, 
+			payer_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payee = ensure_signed(origin)?;
+			let payment_proposal = <ProposedPaymentAgreements<T>>::take(
+				(&payer_id, &payee, &payment_id)
+			).ok_or(<Error<T>>::ProposalNonExistent)?;
+			Pallet::<T>::release_unclaimed_funds(&payer_id, &payment_proposal.payment_details)?;
+			Self::deposit_event(
+				Event::PaymentProposalRejected(payer_id, payee, payment_id)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
+		/// An extrinsic that lets the payer withdraw a payment
+		/// agreement the payee hasn't accepted, including
+		/// proposals that have expired
+		pub fn withdraw_payment_proposal (
+			origin: OriginFor<T>, 
+			payee_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let payment_proposal = <ProposedPaymentAgreements<T>>::take(
+				(&payer, &payee_id, &payment_id)
+			).ok_or(<Error<T>>::ProposalNonExistent)?;
+			Pallet::<T>::release_unclaimed_funds(&payer, &payment_proposal.payment_details)?;
+			Self::deposit_event(
+				Event::PaymentProposalWithdrawn(payer, payee_id, payment_id)
+			);
+			Ok(())
+		}
+
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
 		// An extrinsic that blocks the next payment from release
 		pub fn block_next_payment (
@@ -383,31 +1007,648 @@ pub mod pallet {
 				true
 			)
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		pub fn change_next_payment_release_status(
-			payer: &T::AccountId,
-			payee_id: &T::AccountId,
-			payment_id: &T::PaymentId,
-			released: bool 
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1).ref_time())]
+		/// An extrinsic that transfers as much of the next scheduled
+		/// payment as the payment source can currently cover.
+		/// Any unpaid remainder stays scheduled for a later claim
+		pub fn claim_available (
+			origin: OriginFor<T>, 
+			payer_id: T::AccountId,
+			payment_id: T::PaymentId,
 		) -> DispatchResult {
+			let payee = ensure_signed(origin)?;
 			<PaymentAgreements<T>>::try_mutate(
-				(&payer.clone(), &payee_id, &payment_id), 
+				(&payer_id, &payee.clone(), &payment_id), 
 				| maybe_payment_agreements | -> DispatchResult {
 					let payment_details = 
-						&mut maybe_payment_agreements
+						maybe_payment_agreements
 						.as_mut()
 						.ok_or(<Error<T>>::PaymentDetailsNonExistent)?;
+					let payment_method = &payment_details.payment_method;
+					let asset_id = payment_details.asset_id;
+					let late_payment_terms = payment_details.late_payment_terms;
 					let payment_schedule = &mut payment_details.payment_schedule;
-					ensure!(
-						!payment_schedule.is_empty(), 
+					let next_payment = payment_schedule.get_mut(0).ok_or(
 						<Error<T>>::NoScheduledPaymentRecorded
-					);
-					let next_payment = &mut payment_schedule.get_mut(0)
-						.ok_or(
+					)?;
+
+					// Deny the payment if it is before the due date
+					let time: u64 = T::TimeProvider::now().as_secs();
+					ensure!(
+						time >= next_payment.payment_date, 
+						<Error<T>>::PaymentNotAvailable
+					);
+					ensure!(next_payment.released, <Error<T>>::PaymentNotReleased);
+
+					// Claim whatever part of the payment can be covered
+					let reservation_id = 
+						Pallet::<T>::agreement_reservation(&payer_id, &payee, &payment_id);
+					let amount_claimed = 
+						Pallet::<T>::available_funds(payment_method, asset_id, &reservation_id)
+						.min(next_payment.amount_per_claim);
+					ensure!(!amount_claimed.is_zero(), <Error<T>>::NoFundsAvailable);
+					Pallet::<T>::transfer_funds(
+						payment_method,
+						asset_id,
+						&reservation_id,
+						&payee,
+						amount_claimed,
+					)?;
+
+					let amount_remaining = 
+						next_payment.amount_per_claim.saturating_sub(amount_claimed);
+					let late_penalty = 
+						Pallet::<T>::late_penalty(&late_payment_terms, next_payment, time);
+					if amount_remaining.is_zero() {
+						// Late penalties are paid on top of the scheduled amount
+						payment_schedule.remove(0);
+						let late_penalty_paid = 
+							Pallet::<T>::settle_late_penalty(&payer_id, payment_details, late_penalty);
+						let amount_paid = amount_claimed.saturating_add(late_penalty_paid);
+						Pallet::<T>::record_payment(&payer_id, payment_details, amount_paid);
+						if payment_details.payment_schedule.is_empty() {
+							Pallet::<T>::issue_receipt(&payer_id, payment_details);
+							*maybe_payment_agreements = None;
+						}
+						Self::deposit_event(
+							Event::PartOfPaymentClaimed(payee, amount_paid, late_penalty_paid)
+						);
+					} else {
+						// Keep track of the part of the payment still owed,
+						// which is late from now on. The penalty accrued on
+						// the whole payment so far is carried over to it
+						next_payment.amount_per_claim = amount_remaining;
+						next_payment.late_penalty = late_penalty;
+						next_payment.delinquent_since = Some(time);
+						Pallet::<T>::record_payment(&payer_id, payment_details, amount_claimed);
+						Self::deposit_event(
+							Event::PartialPaymentClaimed(
+								payee, 
+								payment_id, 
+								amount_claimed, 
+								amount_remaining
+							)
+						);
+					}
+					Ok(())
+				}
+			)?;
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1).ref_time())]
+		/// An extrinsic that lets the payee report that the next
+		/// payment is due but can't be claimed, because it is blocked
+		/// or the payment source can't cover it. Late penalties
+		/// accrue from the time it is reported
+		pub fn report_late_payment (
+			origin: OriginFor<T>, 
+			payer_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payee = ensure_signed(origin)?;
+			let delinquent_since = <PaymentAgreements<T>>::try_mutate(
+				(&payer_id, &payee, &payment_id), 
+				| maybe_payment_agreements | -> Result<u64, DispatchError> {
+					let payment_details = 
+						maybe_payment_agreements
+						.as_mut()
+						.ok_or(<Error<T>>::PaymentDetailsNonExistent)?;
+					let available_funds = Pallet::<T>::available_funds(
+						&payment_details.payment_method,
+						payment_details.asset_id,
+						&Pallet::<T>::agreement_reservation(&payer_id, &payee, &payment_id),
+					);
+					let next_payment = payment_details.payment_schedule.get_mut(0).ok_or(
 						<Error<T>>::NoScheduledPaymentRecorded
 					)?;
+					let time: u64 = T::TimeProvider::now().as_secs();
+					ensure!(
+						time >= next_payment.payment_date &&
+						(!next_payment.released || available_funds < next_payment.amount_per_claim),
+						<Error<T>>::PaymentNotLate
+					);
+					Ok(*next_payment.delinquent_since.get_or_insert(time))
+				}
+			)?;
+			Self::deposit_event(
+				Event::LatePaymentReported(payee, payment_id, delinquent_since)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3).ref_time())]
+		/// An extrinsic that pays the payee a late penalty the payment
+		/// source couldn't cover when its instalment was claimed
+		pub fn claim_late_penalty (
+			origin: OriginFor<T>, 
+			payer_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payee = ensure_signed(origin)?;
+			let outstanding_penalty = <OutstandingPenalties<T>>::take(
+				(&payer_id, &payee, &payment_id)
+			).ok_or(<Error<T>>::NoPenaltyOutstanding)?;
+			Pallet::<T>::transfer_late_penalty(
+				&payer_id,
+				&payee,
+				&payment_id,
+				&outstanding_penalty.payment_method,
+				outstanding_penalty.asset_id,
+				outstanding_penalty.amount,
+			)?;
+			Self::deposit_event(
+				Event::LatePenaltyClaimed(payee, payment_id, outstanding_penalty.amount)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1).ref_time())]
+		/// An extrinsic that lets the payer propose a new amount
+		/// for an unclaimed scheduled payment. The new amount only
+		/// takes effect once the payee accepts it
+		pub fn propose_payment_adjustment (
+			origin: OriginFor<T>, 
+			payee_id: T::AccountId,
+			payment_id: T::PaymentId,
+			payment_date: u64,
+			new_amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let payment_details = <PaymentAgreements<T>>::get(
+				(&payer, &payee_id, &payment_id)
+			).ok_or(<Error<T>>::PaymentDetailsNonExistent)?;
+			ensure!(
+				payment_details.payment_schedule
+					.iter()
+					.any(|scheduled_payment| scheduled_payment.payment_date == payment_date),
+				<Error<T>>::ScheduledPaymentNotFound
+			);
+			<PaymentAdjustments<T>>::insert(
+				(&payer, &payee_id, &payment_id, payment_date),
+				new_amount
+			);
+			Self::deposit_event(
+				Event::PaymentAdjustmentProposed(
+					payer,
+					payee_id,
+					payment_id,
+					payment_date,
+					new_amount
+				)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2).ref_time())]
+		/// An extrinsic that lets the payee accept an amount proposed
+		/// by the payer for an unclaimed scheduled payment
+		pub fn accept_payment_adjustment (
+			origin: OriginFor<T>, 
+			payer_id: T::AccountId,
+			payment_id: T::PaymentId,
+			payment_date: u64,
+		) -> DispatchResult {
+			let payee = ensure_signed(origin)?;
+			let new_amount = <PaymentAdjustments<T>>::take(
+				(&payer_id, &payee, &payment_id, payment_date)
+			).ok_or(<Error<T>>::NoAdjustmentProposed)?;
+			let previous_amount = <PaymentAgreements<T>>::try_mutate(
+				(&payer_id, &payee, &payment_id), 
+				| maybe_payment_agreements | -> Result<BalanceOf<T>, DispatchError> {
+					let payment_details = 
+						maybe_payment_agreements
+						.as_mut()
+						.ok_or(<Error<T>>::PaymentDetailsNonExistent)?;
+					let index = payment_details.payment_schedule
+						.iter()
+						.position(|scheduled_payment| scheduled_payment.payment_date == payment_date)
+						.ok_or(<Error<T>>::ScheduledPaymentNotFound)?;
+					let scheduled_payment = payment_details.payment_schedule
+						.get_mut(index)
+						.ok_or(<Error<T>>::ScheduledPaymentNotFound)?;
+					let previous_amount = scheduled_payment.amount_per_claim;
+					scheduled_payment.amount_per_claim = new_amount;
+
+					// Keep the escrow's reservation in line with the schedule
+					if payment_details.payment_method.payment_source == PaymentSource::EscrowAccount {
+						let escrow_id = &payment_details.payment_method.account_id;
+						let reservation_id = 
+							Pallet::<T>::agreement_reservation(&payer_id, &payee, &payment_id);
+						if new_amount > previous_amount {
+							T::Escrow::reserve_funds(
+								escrow_id,
+								&payer_id,
+								payment_details.asset_id,
+								&reservation_id,
+								new_amount.saturating_sub(previous_amount),
+							)?;
+						} else {
+							T::Escrow::release_funds(
+								escrow_id,
+								payment_details.asset_id,
+								&reservation_id,
+								previous_amount.saturating_sub(new_amount),
+							)?;
+						}
+					}
+
+					// Keep the agreement's total in line with its schedule
+					payment_details.total_payment_amount = payment_details.total_payment_amount
+						.saturating_sub(previous_amount)
+						.saturating_add(new_amount);
+					Ok(previous_amount)
+				}
+			)?;
+			Self::deposit_event(
+				Event::PaymentAdjustmentAccepted(
+					payer_id,
+					payee,
+					payment_id,
+					payment_date,
+					previous_amount,
+					new_amount
+				)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2).ref_time())]
+		/// An extrinsic that initializes a payment stream and commits
+		/// it to storage. Funds streamed from a personal account are
+		/// reserved for the whole stream up front
+		pub fn initialize_stream (
+			origin: OriginFor<T>, 
+			payee_id: T::AccountId,
+			payment_id: T::PaymentId,
+			rate_per_block: BalanceOf<T>,
+			total_amount: BalanceOf<T>,
+			payment_method: PaymentMethod<T>,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			ensure!(
+				!<PaymentStreams<T>>::contains_key((&payer, &payee_id, &payment_id)),
+				Error::<T>::StreamAlreadyInitialized
+			);
+			match payment_method.payment_source {
+				PaymentSource::PersonalAccount => {
+					ensure!(
+						payment_method.account_id == payer,
+						Error::<T>::InvalidPaymentAccount
+					);
+					T::PaymentCurrency::reserve(&payer, total_amount)?;
+				},
+				PaymentSource::EscrowAccount => {
+					T::Escrow::reserve_funds(
+						&payment_method.account_id,
+						&payer,
+						None,
+						&Pallet::<T>::stream_reservation(&payer, &payee_id, &payment_id),
+						total_amount,
+					)?;
+				},
+			}
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			<PaymentStreams<T>>::insert(
+				(&payer, &payee_id, &payment_id),
+				PaymentStream {
+					rate_per_block,
+					total_amount,
+					accrued: Zero::zero(),
+					withdrawn: Zero::zero(),
+					start_block: current_block,
+					last_settled_block: current_block,
+					is_paused: false,
+					payment_method,
+				}
+			);
+			Self::deposit_event(
+				Event::StreamInitialized(
+					payer,
+					payee_id,
+					payment_id,
+					rate_per_block,
+					total_amount
+				)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2).ref_time())]
+		/// An extrinsic that transfers everything accrued so far
+		/// in a payment stream to the payee
+		pub fn withdraw_from_stream (
+			origin: OriginFor<T>, 
+			payer_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payee = ensure_signed(origin)?;
+			let amount_withdrawn = <PaymentStreams<T>>::try_mutate(
+				(&payer_id, &payee, &payment_id),
+				| maybe_payment_stream | -> Result<BalanceOf<T>, DispatchError> {
+					let payment_stream = 
+						maybe_payment_stream
+						.as_mut()
+						.ok_or(<Error<T>>::StreamNonExistent)?;
+					Pallet::<T>::settle_stream(payment_stream);
+					let amount_withdrawn = 
+						Pallet::<T>::pay_out_stream(&payer_id, &payee, &payment_id, payment_stream)?;
+					ensure!(!amount_withdrawn.is_zero(), <Error<T>>::NoFundsAvailable);
+					Ok(amount_withdrawn)
+				}
+			)?;
+			Self::deposit_event(
+				Event::StreamWithdrawn(payee, payment_id, amount_withdrawn)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1).ref_time())]
+		/// An extrinsic that stops a payment stream from accruing
+		pub fn pause_stream (
+			origin: OriginFor<T>, 
+			payee_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			<PaymentStreams<T>>::try_mutate(
+				(&payer, &payee_id, &payment_id),
+				| maybe_payment_stream | -> DispatchResult {
+					let payment_stream = 
+						maybe_payment_stream
+						.as_mut()
+						.ok_or(<Error<T>>::StreamNonExistent)?;
+					ensure!(!payment_stream.is_paused, <Error<T>>::StreamAlreadyPaused);
+
+					// Settle what has accrued up to this block
+					Pallet::<T>::settle_stream(payment_stream);
+					payment_stream.is_paused = true;
+					Ok(())
+				}
+			)?;
+			Self::deposit_event(Event::StreamPaused(payer, payment_id));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1).ref_time())]
+		/// An extrinsic that lets a paused payment stream accrue again
+		pub fn resume_stream (
+			origin: OriginFor<T>, 
+			payee_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			<PaymentStreams<T>>::try_mutate(
+				(&payer, &payee_id, &payment_id),
+				| maybe_payment_stream | -> DispatchResult {
+					let payment_stream = 
+						maybe_payment_stream
+						.as_mut()
+						.ok_or(<Error<T>>::StreamNonExistent)?;
+					ensure!(payment_stream.is_paused, <Error<T>>::StreamNotPaused);
+
+					// Nothing accrues for the blocks spent paused
+					Pallet::<T>::settle_stream(payment_stream);
+					payment_stream.is_paused = false;
+					Ok(())
+				}
+			)?;
+			Self::deposit_event(Event::StreamResumed(payer, payment_id));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2).ref_time())]
+		/// An extrinsic that pays out what a stream has accrued up
+		/// to the current block, closes the stream, and returns any
+		/// reserved funds that were not streamed to the payer
+		pub fn cancel_stream (
+			origin: OriginFor<T>, 
+			payee_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			let mut payment_stream = <PaymentStreams<T>>::take(
+				(&payer, &payee_id, &payment_id)
+			).ok_or(<Error<T>>::StreamNonExistent)?;
+			Pallet::<T>::settle_stream(&mut payment_stream);
+			let amount_paid = 
+				Pallet::<T>::pay_out_stream(&payer, &payee_id, &payment_id, &mut payment_stream)?;
+
+			// The payee must receive everything accrued before the stream closes
+			ensure!(payment_stream.accrued.is_zero(), <Error<T>>::InsufficientEscrowFunds);
+			let amount_returned = payment_stream.total_amount
+				.saturating_sub(payment_stream.withdrawn);
+			match payment_stream.payment_method.payment_source {
+				PaymentSource::PersonalAccount => {
+					T::PaymentCurrency::unreserve(
+						&payment_stream.payment_method.account_id,
+						amount_returned
+					);
+				},
+				PaymentSource::EscrowAccount => {
+					T::Escrow::release_funds(
+						&payment_stream.payment_method.account_id,
+						None,
+						&Pallet::<T>::stream_reservation(&payer, &payee_id, &payment_id),
+						amount_returned
+					)?;
+				},
+			}
+			Self::deposit_event(
+				Event::StreamCancelled(payer, payment_id, amount_paid, amount_returned)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		/// An extrinsic that lets the payee remove the receipt
+		/// of a completed payment agreement from storage
+		pub fn prune_receipt (
+			origin: OriginFor<T>, 
+			payer_id: T::AccountId,
+			payment_id: T::PaymentId,
+		) -> DispatchResult {
+			let payee = ensure_signed(origin)?;
+			<PaymentReceipts<T>>::take(
+				(&payee, &payer_id, &payment_id)
+			).ok_or(<Error<T>>::ReceiptNonExistent)?;
+			Self::deposit_event(
+				Event::ReceiptPruned(payee, payer_id, payment_id)
+			);
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1).ref_time())]
+		/// An extrinsic that lets the payer issue a completion credential
+		/// from one of their NT-NFT collections to the payee of every
+		/// agreement for the RFP that completes from now on.
+		/// Passing no collection stops issuing them
+		pub fn set_completion_credential (
+			origin: OriginFor<T>, 
+			rfp_reference_id: T::RFPReferenceId,
+			collection_id: Option<T::CredentialCollectionId>,
+		) -> DispatchResult {
+			let payer = ensure_signed(origin)?;
+			match collection_id {
+				Some(collection_id) => {
+					ensure!(
+						T::Credentials::can_issue(&payer, &collection_id),
+						<Error<T>>::CannotIssueCredentials
+					);
+					<CompletionCredentials<T>>::insert(&payer, &rfp_reference_id, collection_id);
+				},
+				None => <CompletionCredentials<T>>::remove(&payer, &rfp_reference_id),
+			}
+			Self::deposit_event(
+				Event::CompletionCredentialSet(payer, rfp_reference_id, collection_id)
+			);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The escrow reservation holding the funds of a payment agreement
+		pub fn agreement_reservation(
+			payer: &T::AccountId,
+			payee: &T::AccountId,
+			payment_id: &T::PaymentId,
+		) -> T::Hash {
+			T::Hashing::hash_of(&(b"agreement", payer, payee, payment_id))
+		}
+
+		/// The escrow reservation holding the funds of a payment stream
+		pub fn stream_reservation(
+			payer: &T::AccountId,
+			payee: &T::AccountId,
+			payment_id: &T::PaymentId,
+		) -> T::Hash {
+			T::Hashing::hash_of(&(b"stream", payer, payee, payment_id))
+		}
+
+		/// Allocates the next payment id from the on-chain counter, skipping
+		/// up to [`MAX_ID_PROBES`] ids already chosen by the payer for
+		/// an agreement with this payee
+		pub fn allocate_payment_id(
+			payer: &T::AccountId,
+			payee: &T::AccountId,
+		) -> Result<T::PaymentId, DispatchError> {
+			<NextPaymentId<T>>::try_mutate(
+				| next_id | -> Result<T::PaymentId, DispatchError> {
+					for _ in 0..MAX_ID_PROBES {
+						let payment_id: T::PaymentId = (*next_id).into();
+						*next_id = next_id.checked_add(1)
+							.ok_or(<Error<T>>::PaymentIdsExhausted)?;
+						let key = (payer, payee, payment_id);
+						if !<PaymentAgreements<T>>::contains_key(key) &&
+							!<ProposedPaymentAgreements<T>>::contains_key(key) &&
+							!<PaymentReceipts<T>>::contains_key((payee, payer, payment_id))
+						{
+							return Ok(payment_id)
+						}
+					}
+					Err(<Error<T>>::PaymentIdsExhausted.into())
+				}
+			)
+		}
+
+		/// Proposes a payment agreement to its payee, reserving the
+		/// escrow funds for every scheduled payment when escrow-sourced
+		pub fn propose_payment(
+			payer: T::AccountId,
+			payment_details: PaymentDetails<T>,
+		) -> DispatchResult {
+			let payee = payment_details.payee.clone();
+			let payment_id = payment_details.payment_id;
+			let payment_details_exists = <PaymentAgreements<T>>::get(
+				(&payer, &payee, &payment_id)
+			);
+			ensure!(
+				payment_details_exists.is_none(),
+				Error::<T>::PaymentAlreadyInitialized
+			);
+			ensure!(
+				!<ProposedPaymentAgreements<T>>::contains_key((&payer, &payee, &payment_id)),
+				Error::<T>::PaymentAlreadyInitialized
+			);
+			// Receipts are kept under the id of the completed agreement
+			ensure!(
+				!<PaymentReceipts<T>>::contains_key((&payee, &payer, &payment_id)),
+				Error::<T>::PaymentAlreadyInitialized
+			);
+
+			// Personal payments can only be drawn from the payer's own account
+			ensure!(
+				payment_details.payment_method.payment_source != PaymentSource::PersonalAccount ||
+					payment_details.payment_method.account_id == payer,
+				Error::<T>::InvalidPaymentAccount
+			);
+
+			// Escrow funds are reserved for every scheduled payment up front,
+			// which the payer must be authorized to do as an escrow admin
+			if payment_details.payment_method.payment_source == PaymentSource::EscrowAccount {
+				T::Escrow::reserve_funds(
+					&payment_details.payment_method.account_id,
+					&payer,
+					payment_details.asset_id,
+					&Pallet::<T>::agreement_reservation(&payer, &payee, &payment_id),
+					Pallet::<T>::unclaimed_amount(&payment_details),
+				)?;
+			}
+			let expires_at = T::TimeProvider::now().as_secs()
+				.saturating_add(T::PaymentProposalExpiry::get());
+			<ProposedPaymentAgreements<T>>::insert(
+				(&payer, &payee, payment_id), 
+				PaymentProposal {
+					payment_details,
+					expires_at,
+				}
+			);
+			Self::deposit_event(
+				Event::PaymentProposed(
+					payer, 
+					payee, 
+					payment_id,
+					expires_at
+				)
+			);
+			Ok(())
+		}
+
+		pub fn change_next_payment_release_status(
+			payer: &T::AccountId,
+			payee_id: &T::AccountId,
+			payment_id: &T::PaymentId,
+			released: bool 
+		) -> DispatchResult {
+			<PaymentAgreements<T>>::try_mutate(
+				(&payer.clone(), &payee_id, &payment_id), 
+				| maybe_payment_agreements | -> DispatchResult {
+					let payment_details = 
+						&mut maybe_payment_agreements
+						.as_mut()
+						.ok_or(<Error<T>>::PaymentDetailsNonExistent)?;
+					let late_payment_terms = payment_details.late_payment_terms;
+					let payment_schedule = &mut payment_details.payment_schedule;
+					ensure!(
+						!payment_schedule.is_empty(), 
+						<Error<T>>::NoScheduledPaymentRecorded
+					);
+					let next_payment = &mut payment_schedule.get_mut(0)
+						.ok_or(
+						<Error<T>>::NoScheduledPaymentRecorded
+					)?;
+
+					// Blocking a payment that is already due makes it late,
+					// releasing it settles the penalty accrued so far
+					let time: u64 = T::TimeProvider::now().as_secs();
+					if !released && time >= next_payment.payment_date {
+						next_payment.delinquent_since.get_or_insert(time);
+					} else if released && next_payment.delinquent_since.is_some() {
+						next_payment.late_penalty = 
+							Pallet::<T>::late_penalty(&late_payment_terms, next_payment, time);
+						next_payment.delinquent_since = None;
+					}
 
 					// Modify the release of the next payment
 					next_payment.released = released;
@@ -424,69 +1665,370 @@ pub mod pallet {
 			Ok(())
 		}
 
-		pub fn transfer_funds_from_personal_account(
-			payment_account_id: &T::AccountId, 
+		/// The late penalty owed on a scheduled payment at the given time:
+		/// the penalty already settled, plus the penalty accrued since the
+		/// payment became late, once the grace period has passed
+		pub fn late_penalty(
+			late_payment_terms: &Option<LatePaymentTerms>,
+			scheduled_payment: &ScheduledPayment<T>,
+			time: u64,
+		) -> BalanceOf<T> {
+			let accrued_penalty = match (late_payment_terms, scheduled_payment.delinquent_since) {
+				(Some(terms), Some(delinquent_since)) => {
+					let penalized_time = time
+						.saturating_sub(delinquent_since)
+						.saturating_sub(terms.grace_period);
+					let penalty_periods: BalanceOf<T> = penalized_time
+						.checked_div(terms.penalty_period)
+						.unwrap_or_default()
+						.saturated_into::<u128>()
+						.saturated_into();
+					terms.penalty_rate
+						.mul_floor(scheduled_payment.amount_per_claim)
+						.saturating_mul(penalty_periods)
+				},
+				_ => Zero::zero(),
+			};
+			scheduled_payment.late_penalty.saturating_add(accrued_penalty)
+		}
+
+		/// Pays a late penalty to the payee. Escrows only reserve the
+		/// payment schedule, so the payer authorizes the penalty from
+		/// the escrow as well
+		pub fn transfer_late_penalty(
+			payer: &T::AccountId,
 			payee: &T::AccountId,
-			payment_amount: BalanceOf<T>,
+			payment_id: &T::PaymentId,
+			payment_method: &PaymentMethod<T>,
+			asset_id: Option<AssetIdOf<T>>,
+			late_penalty: BalanceOf<T>,
 		) -> DispatchResult {
-			T::PaymentCurrency::transfer(
-				payment_account_id,
+			let reservation_id = Pallet::<T>::agreement_reservation(payer, payee, payment_id);
+			if payment_method.payment_source == PaymentSource::EscrowAccount {
+				T::Escrow::reserve_funds(
+					&payment_method.account_id,
+					payer,
+					asset_id,
+					&reservation_id,
+					late_penalty,
+				)?;
+			}
+			Pallet::<T>::transfer_funds(
+				payment_method,
+				asset_id,
+				&reservation_id,
 				payee,
-				payment_amount,
-				AllowDeath,
+				late_penalty,
 			)
 		}
 
-		pub fn transfer_funds_from_escrow_account(
-			escrow_account_id: &T::AccountId,
-			admin_account_id: &T::AccountId, 
-			payee: &T::AccountId,
-			payment_amount: BalanceOf<T>,
-		) -> DispatchResult {
-			<pallet_escrow::Escrow<T>>::try_mutate(
-				escrow_account_id, 
-				| maybe_escrow_details | -> DispatchResult {
-					let escrow_details =
-						maybe_escrow_details.as_mut().ok_or(<Error<T>>::NoEscrowAccountFound)?;
-						
-					ensure!(
-						!escrow_details.is_frozen,
-						Error::<T>::Frozen
+		/// Pays the late penalty on a claimed payment, if the payment source
+		/// can cover it. Otherwise the penalty stays owed to the payee, who
+		/// can claim it later, so that it doesn't hold up the payment itself.
+		/// Returns the late penalty paid
+		pub fn settle_late_penalty(
+			payer: &T::AccountId,
+			payment_details: &PaymentDetails<T>,
+			late_penalty: BalanceOf<T>,
+		) -> BalanceOf<T> {
+			if late_penalty.is_zero() {
+				return Zero::zero()
+			}
+			let payee = &payment_details.payee;
+			let payment_id = payment_details.payment_id;
+			let transferred = with_transaction(|| {
+				match Pallet::<T>::transfer_late_penalty(
+					payer,
+					payee,
+					&payment_id,
+					&payment_details.payment_method,
+					payment_details.asset_id,
+					late_penalty,
+				) {
+					Ok(()) => TransactionOutcome::Commit(Ok(())),
+					Err(error) => TransactionOutcome::Rollback(Err::<(), DispatchError>(error)),
+				}
+			});
+			if transferred.is_ok() {
+				return late_penalty
+			}
+			<OutstandingPenalties<T>>::mutate(
+				(payer, payee, &payment_id),
+				| maybe_outstanding_penalty | {
+					let outstanding_penalty = maybe_outstanding_penalty.get_or_insert_with(
+						|| OutstandingPenalty {
+							payment_method: payment_details.payment_method.clone(),
+							asset_id: payment_details.asset_id,
+							amount: Zero::zero(),
+						}
 					);
-					// Make sure the payer is an Admin and the
-					// transfer can be completed
-					ensure!(
-						escrow_details.admins.iter().any(|x| *x == admin_account_id.clone()),
-						Error::<T>::Unauthorized
+					outstanding_penalty.amount = outstanding_penalty.amount.saturating_add(late_penalty);
+				}
+			);
+			Self::deposit_event(
+				Event::LatePenaltyDeferred(payee.clone(), payment_id, late_penalty)
+			);
+			Zero::zero()
+		}
+
+		/// Adds a claimed amount to the progress of a payment agreement
+		pub fn record_payment(
+			payer: &T::AccountId,
+			payment_details: &PaymentDetails<T>,
+			amount_paid: BalanceOf<T>,
+		) {
+			<PaymentAgreementProgress<T>>::mutate(
+				(payer, &payment_details.payee, &payment_details.payment_id),
+				| payment_progress | {
+					payment_progress.amount_paid = 
+						payment_progress.amount_paid.saturating_add(amount_paid);
+				}
+			);
+		}
+
+		/// Replaces the progress of a completed payment agreement
+		/// with a compact receipt
+		pub fn issue_receipt(payer: &T::AccountId, payment_details: &PaymentDetails<T>) {
+			let payee = &payment_details.payee;
+			let payment_id = payment_details.payment_id;
+			let payment_progress = 
+				<PaymentAgreementProgress<T>>::take((payer, payee, &payment_id));
+			let _ = <PaymentAdjustments<T>>::clear_prefix(
+				(payer, payee, &payment_id),
+				u32::MAX,
+				None
+			);
+			<PaymentReceipts<T>>::insert(
+				(payee, payer, &payment_id),
+				PaymentReceipt {
+					payer: payer.clone(),
+					payee: payee.clone(),
+					payment_id,
+					rfp_reference_id: payment_details.rfp_reference_id,
+					total_paid: payment_progress.amount_paid,
+					asset_id: payment_details.asset_id,
+					started_at: payment_progress.started_at,
+					completed_at: T::TimeProvider::now().as_secs(),
+				}
+			);
+			Self::deposit_event(
+				Event::PaymentCompleted(
+					payer.clone(),
+					payee.clone(),
+					payment_id,
+					payment_progress.amount_paid
+				)
+			);
+			Self::issue_completion_credential(payer, payment_details);
+		}
+
+		/// Assigns a completion credential to the payee, if the payer
+		/// opted the agreement's RFP into them. A credential that can't
+		/// be issued doesn't stop the last payment from being claimed
+		pub fn issue_completion_credential(payer: &T::AccountId, payment_details: &PaymentDetails<T>) {
+			let collection_id = match <CompletionCredentials<T>>::get(
+				payer,
+				&payment_details.rfp_reference_id
+			) {
+				Some(collection_id) => collection_id,
+				None => return,
+			};
+			let payee = &payment_details.payee;
+			let metadata = (payer, payment_details.rfp_reference_id, payment_details.payment_id)
+				.encode()
+				.try_into()
+				.unwrap_or_default();
+			let event = match T::Credentials::issue_credential(payer, &collection_id, payee, metadata) {
+				Ok(()) => Event::CompletionCredentialIssued(
+					payer.clone(),
+					payee.clone(),
+					payment_details.payment_id,
+					collection_id
+				),
+				Err(_) => Event::CompletionCredentialNotIssued(
+					payer.clone(),
+					payee.clone(),
+					payment_details.payment_id,
+					collection_id
+				),
+			};
+			Self::deposit_event(event);
+		}
+
+		/// Returns the receipts of all completed payment agreements
+		/// paid out to the payee
+		pub fn receipts_of(payee: &T::AccountId) -> Vec<PaymentReceiptOf<T>> {
+			<PaymentReceipts<T>>::iter_prefix_values((payee,)).collect()
+		}
+
+		/// Accrues funds to a payment stream for every block since
+		/// it was last settled, up to the stream's total amount
+		pub fn settle_stream(payment_stream: &mut PaymentStream<T>) {
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			if !payment_stream.is_paused {
+				let elapsed_blocks: BalanceOf<T> = current_block
+					.saturating_sub(payment_stream.last_settled_block)
+					.saturated_into::<u128>()
+					.saturated_into();
+				let unaccrued = payment_stream.total_amount
+					.saturating_sub(payment_stream.withdrawn)
+					.saturating_sub(payment_stream.accrued);
+				let newly_accrued = payment_stream.rate_per_block
+					.saturating_mul(elapsed_blocks)
+					.min(unaccrued);
+				payment_stream.accrued = payment_stream.accrued.saturating_add(newly_accrued);
+			}
+			payment_stream.last_settled_block = current_block;
+		}
+
+		/// Transfers a stream's accrued funds to the payee,
+		/// returning the amount transferred
+		pub fn pay_out_stream(
+			payer: &T::AccountId,
+			payee: &T::AccountId,
+			payment_id: &T::PaymentId,
+			payment_stream: &mut PaymentStream<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let payment_method = &payment_stream.payment_method;
+			let amount_paid = match payment_method.payment_source {
+				PaymentSource::PersonalAccount => {
+					// Personal streams are paid out of the reserved funds
+					let amount_not_unreserved = T::PaymentCurrency::unreserve(
+						&payment_method.account_id,
+						payment_stream.accrued,
 					);
+					let amount = payment_stream.accrued.saturating_sub(amount_not_unreserved);
+					Pallet::<T>::transfer_funds_from_personal_account(
+						&payment_method.account_id,
+						payee,
+						amount,
+					)?;
+					amount
+				},
+				PaymentSource::EscrowAccount => {
+					// Escrow streams are paid out of the escrow funds reserved for them
+					let amount = payment_stream.accrued;
+					if !amount.is_zero() {
+						T::Escrow::commit_funds(
+							&payment_method.account_id,
+							payee,
+							None,
+							&Pallet::<T>::stream_reservation(payer, payee, payment_id),
+							amount,
+						)?;
+					}
+					amount
+				},
+			};
+			payment_stream.accrued = payment_stream.accrued.saturating_sub(amount_paid);
+			payment_stream.withdrawn = payment_stream.withdrawn.saturating_add(amount_paid);
+			Ok(amount_paid)
+		}
 
-					// Unlock funds
-					T::EscrowCurrency::remove_lock(pallet_escrow::ESCROW_LOCK, escrow_account_id);
-					
-					// Transfer the unlocked funds
-					T::PaymentCurrency::transfer(
-						escrow_account_id,
+		/// Transfers funds to the payee from the source described
+		/// by the payment method, in the given asset if there is one.
+		/// Escrow payments are drawn from the funds the escrow
+		/// reserved for the payment agreement
+		pub fn transfer_funds(
+			payment_method: &PaymentMethod<T>,
+			asset_id: Option<AssetIdOf<T>>,
+			reservation_id: &T::Hash,
+			payee: &T::AccountId,
+			payment_amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let payment_account_id = &payment_method.account_id;
+			match (payment_method.payment_source, asset_id) {
+				(PaymentSource::PersonalAccount, Some(asset_id)) => {
+					<T::Assets as fungibles::Transfer<T::AccountId>>::transfer(
+						asset_id,
+						payment_account_id,
 						payee,
 						payment_amount,
-						AllowDeath,
+						false,
 					)?;
-
-					let payment_amount_as_128: u128 = 
-						TryInto::<u128>::try_into(payment_amount).ok().unwrap();
-					//let amount_as_128: u128 = TryInto::<u128>::try_into(escrow_details.amount.clone()).ok().unwrap();
-					escrow_details.amount -= payment_amount_as_128.try_into().ok().unwrap();
-					
-					// Lock the remaining funds
-					T::EscrowCurrency::set_lock(
-						pallet_escrow::ESCROW_LOCK,
-						escrow_account_id,
-						escrow_details.amount,
-						WithdrawReasons::all(),
-					);
 					Ok(())
-				}
-			)?;
+				},
+				(PaymentSource::PersonalAccount, None) =>
+					Pallet::<T>::transfer_funds_from_personal_account(
+						payment_account_id,
+						payee,
+						payment_amount,
+					),
+				(PaymentSource::EscrowAccount, asset_id) =>
+					T::Escrow::commit_funds(
+						payment_account_id,
+						payee,
+						asset_id,
+						reservation_id,
+						payment_amount,
+					),
+			}
+		}
+
+		/// Returns the amount the payment source can currently pay out,
+		/// in the given asset if there is one. Escrows can only pay out
+		/// what they reserved for the payment agreement
+		pub fn available_funds(
+			payment_method: &PaymentMethod<T>,
+			asset_id: Option<AssetIdOf<T>>,
+			reservation_id: &T::Hash,
+		) -> BalanceOf<T> {
+			match (payment_method.payment_source, asset_id) {
+				(PaymentSource::PersonalAccount, Some(asset_id)) =>
+					<T::Assets as fungibles::Inspect<T::AccountId>>::reducible_balance(
+						asset_id,
+						&payment_method.account_id,
+						false,
+					),
+				(PaymentSource::PersonalAccount, None) =>
+					T::PaymentCurrency::free_balance(&payment_method.account_id),
+				(PaymentSource::EscrowAccount, asset_id) =>
+					T::Escrow::reserved_balance(&payment_method.account_id, asset_id, reservation_id),
+			}
+		}
+
+		/// The sum of the scheduled payments that have not been claimed yet,
+		/// which is what an escrow keeps reserved for the payment agreement
+		pub fn unclaimed_amount(payment_details: &PaymentDetails<T>) -> BalanceOf<T> {
+			payment_details.payment_schedule
+				.iter()
+				.fold(Zero::zero(), |total: BalanceOf<T>, scheduled_payment| {
+					total.saturating_add(scheduled_payment.amount_per_claim)
+				})
+		}
+
+		/// Returns the escrow funds reserved for the unclaimed
+		/// payments of a payment agreement that won't go ahead
+		pub fn release_unclaimed_funds(
+			payer: &T::AccountId,
+			payment_details: &PaymentDetails<T>,
+		) -> DispatchResult {
+			if payment_details.payment_method.payment_source == PaymentSource::EscrowAccount {
+				T::Escrow::release_funds(
+					&payment_details.payment_method.account_id,
+					payment_details.asset_id,
+					&Pallet::<T>::agreement_reservation(
+						payer,
+						&payment_details.payee,
+						&payment_details.payment_id
+					),
+					Pallet::<T>::unclaimed_amount(payment_details),
+				)?;
+			}
 			Ok(())
 		}
+
+		pub fn transfer_funds_from_personal_account(
+			payment_account_id: &T::AccountId, 
+			payee: &T::AccountId,
+			payment_amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::PaymentCurrency::transfer(
+				payment_account_id,
+				payee,
+				payment_amount,
+				AllowDeath,
+			)
+		}
 	}
-}
\ No newline at end of file
+}
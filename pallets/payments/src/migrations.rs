@@ -0,0 +1,139 @@
+// This file is part of Ventur, it implements the instantiation
+// and claiming of scheduled or one-time payments
+
+// Copyright (C) 2022 Popular Coding LLC.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations for the Payments pallet.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::traits::Zero,
+	storage_alias,
+	traits::{StorageVersion, UnixTime},
+};
+use pallet_escrow::EscrowInterface;
+use sp_std::prelude::*;
+
+/// Pays existing payment agreements in the native currency, without late
+/// penalties, and starts tracking their progress and escrow reservations.
+pub mod v1 {
+	use super::*;
+
+	/// The storage layout from before payments could be made in fungible
+	/// assets or accrue late penalties
+	pub mod v0 {
+		use super::*;
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct ScheduledPayment<T: Config> {
+			pub payment_date: u64,
+			pub amount_per_claim: BalanceOf<T>,
+			pub released: bool,
+		}
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct PaymentDetails<T: Config> {
+			pub payer: T::AccountId,
+			pub payee: T::AccountId,
+			pub payment_id: T::PaymentId,
+			pub rfp_reference_id: T::RFPReferenceId,
+			pub total_payment_amount: BalanceOf<T>,
+			pub payment_schedule: BoundedVec<ScheduledPayment<T>, ConstU32<{VEC_LIMIT}>>,
+			pub payment_method: PaymentMethod<T>,
+			pub administrator_id: T::AccountId,
+		}
+
+		#[storage_alias]
+		pub type PaymentAgreements<T: Config> = StorageNMap<
+			Pallet<T>,
+			(
+				NMapKey<Blake2_128Concat, <T as frame_system::Config>::AccountId>,
+				NMapKey<Blake2_128Concat, <T as frame_system::Config>::AccountId>,
+				NMapKey<Blake2_128Concat, <T as Config>::PaymentId>,
+			),
+			PaymentDetails<T>,
+		>;
+	}
+
+	/// Existing agreements were active as soon as they were initialized,
+	/// so their progress is tracked from the time of the upgrade. Escrows
+	/// did not reserve funds for them, so the unclaimed amount of every
+	/// escrow-sourced agreement is reserved on the payer's authority. An
+	/// agreement the escrow can no longer cover is left without a reservation,
+	/// and can only be claimed as far as the payer reserves funds for it
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut translated: u64 = 0;
+		let started_at = T::TimeProvider::now().as_secs();
+
+		<PaymentAgreements<T>>::translate::<v0::PaymentDetails<T>, _>(|(payer, payee, payment_id), old| {
+			translated = translated.saturating_add(1);
+			let payment_schedule = old.payment_schedule
+				.into_iter()
+				.map(|scheduled_payment| ScheduledPayment {
+					payment_date: scheduled_payment.payment_date,
+					amount_per_claim: scheduled_payment.amount_per_claim,
+					released: scheduled_payment.released,
+					delinquent_since: None,
+					late_penalty: Zero::zero(),
+				})
+				.collect::<Vec<_>>()
+				.try_into()
+				.ok()?;
+			let payment_details = PaymentDetails {
+				payer: old.payer,
+				payee: old.payee,
+				payment_id: old.payment_id,
+				rfp_reference_id: old.rfp_reference_id,
+				total_payment_amount: old.total_payment_amount,
+				payment_schedule,
+				payment_method: old.payment_method,
+				administrator_id: old.administrator_id,
+				asset_id: None,
+				late_payment_terms: None,
+			};
+			<PaymentAgreementProgress<T>>::insert(
+				(&payer, &payee, &payment_id),
+				PaymentProgress {
+					started_at,
+					amount_paid: Zero::zero(),
+				}
+			);
+			if payment_details.payment_method.payment_source == PaymentSource::EscrowAccount {
+				let _ = T::Escrow::reserve_funds(
+					&payment_details.payment_method.account_id,
+					&payer,
+					None,
+					&Pallet::<T>::agreement_reservation(&payer, &payee, &payment_id),
+					Pallet::<T>::unclaimed_amount(&payment_details),
+				);
+			}
+			Some(payment_details)
+		});
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(
+			translated.saturating_mul(3).saturating_add(1),
+			translated.saturating_mul(3).saturating_add(1)
+		)
+	}
+}
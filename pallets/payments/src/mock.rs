@@ -1,7 +1,8 @@
 use crate as pallet_payments;
 use frame_support::{
 	parameter_types,
-	traits::{ConstU16, ConstU64},
+	traits::{ConstU16, ConstU32, ConstU64, ConstU128},
+	PalletId,
 };
 use frame_system as system;
 use sp_core::H256;
@@ -30,6 +31,8 @@ frame_support::construct_runtime!(
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		EscrowModule: pallet_escrow::{Pallet, Call, Storage, Event<T>},
+		NTNFTModule: pallet_ntnft::{Pallet, Call, Storage, Event<T>},
+		Assets: pallet_assets,
 	}
 );
 
@@ -66,11 +69,47 @@ impl pallet_payments::Config for Test {
 	type RFPReferenceId = u32;
 	type PaymentCurrency = Balances;
 	type TimeProvider = pallet_timestamp::Pallet<Test>;
+	type Assets = Assets;
+	type Escrow = EscrowModule;
+	type PaymentProposalExpiry = ConstU64<86400>;
+	type MaxBatchSize = ConstU32<50>;
+	type CredentialCollectionId = u128;
+	type Credentials = NTNFTModule;
+}
+
+impl pallet_ntnft::Config for Test {
+	type Event = Event;
+	type CollectionId = u128;
+	type ItemId = u128;
+}
+
+parameter_types! {
+	pub const EscrowPalletId: PalletId = PalletId(*b"py/escrw");
 }
 
 impl pallet_escrow::Config for Test {
 	type Event = Event;
 	type EscrowCurrency = Balances;
+	type AssetId = u32;
+	type EscrowAssets = Assets;
+	type PalletId = EscrowPalletId;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
 }
 
 pub fn test_externalities() -> sp_io::TestExternalities {
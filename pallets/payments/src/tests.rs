@@ -9,10 +9,14 @@ use frame_support::{
     assert_ok,
     traits::{
         Currency,
+        Get,
+        StorageVersion,
     },
 };
 use mock::*;
+use codec::Encode;
 use frame_support::bounded_vec;
+use sp_runtime::Permill;
 use pallet_timestamp::{self as timestamp};
 
 const PAYEE_ID: u64 = 1234;
@@ -22,6 +26,7 @@ const TOTAL_PAYMENT_AMOUNT: u128 = 24601;
 const ESCROW_ACCOUNT_ID: u64 = 1999;
 const ADMINISTRATOR_ID: u64 = 1410;
 const PAYER_ID: u64 = 124;
+const ASSET_ID: u32 = 7;
 
 #[test]
 fn test_initialize_payment() {
@@ -38,11 +43,15 @@ fn test_initialize_payment() {
             payment_date: time,
             amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
             released: true,
+            delinquent_since: None,
+            late_penalty: 0,
         };
         let scheduled_payment_2 = pallet_payments::ScheduledPayment::<Test> {
             payment_date: time + 200,
             amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
             released: true,
+            delinquent_since: None,
+            late_penalty: 0,
         };
         let payment_schedule = bounded_vec![
             scheduled_payment_1, 
@@ -61,11 +70,30 @@ fn test_initialize_payment() {
             payment_schedule,
             payment_method: payment_method.clone(),
             administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
         };
         assert_ok!(Payments::initialize_payment(
             Origin::signed(PAYER_ID),
             payment_details
         ));
+
+        // The agreement is only proposed until the payee accepts it
+        assert!(
+            Payments::payment_agreements(
+                (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+            ).is_none()
+        );
+        assert!(
+            Payments::proposed_payment_agreements(
+                (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+            ).is_some()
+        );
+        assert_noop!(
+            Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID),
+            Error::<Test>::PaymentDetailsNonExistent
+        );
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
         assert!(
             Payments::payment_agreements(
                 (PAYER_ID, PAYEE_ID, PAYMENT_ID)
@@ -81,6 +109,51 @@ fn test_initialize_payment() {
     });
 }
 
+#[test]
+fn test_personal_payment_cannot_draw_from_another_account() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &ESCROW_ACCOUNT_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule: bounded_vec![
+                pallet_payments::ScheduledPayment::<Test> {
+                    payment_date: 0,
+                    amount_per_claim: TOTAL_PAYMENT_AMOUNT,
+                    released: true,
+                    delinquent_since: None,
+                    late_penalty: 0,
+                }
+            ],
+            payment_method: pallet_payments::PaymentMethod::<Test>{
+                payment_source: pallet_payments::PaymentSource::PersonalAccount,
+                account_id: ESCROW_ACCOUNT_ID,
+            },
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+        assert_noop!(
+            Payments::initialize_payment(
+                Origin::signed(PAYER_ID),
+                payment_details
+            ),
+            Error::<Test>::InvalidPaymentAccount
+        );
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::free_balance(&ESCROW_ACCOUNT_ID),
+            TOTAL_PAYMENT_AMOUNT
+        );
+    });
+}
+
 #[test]
 fn test_claim_successful_payment() {
     let mut t = test_externalities();
@@ -102,11 +175,15 @@ fn test_claim_successful_payment() {
             payment_date: time,
             amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
             released: true,
+            delinquent_since: None,
+            late_penalty: 0,
         };
         let scheduled_payment_2 = pallet_payments::ScheduledPayment::<Test> {
             payment_date: time + 500,
             amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
             released: true,
+            delinquent_since: None,
+            late_penalty: 0,
         };
         let payment_schedule = bounded_vec![
             scheduled_payment_1, 
@@ -125,11 +202,14 @@ fn test_claim_successful_payment() {
             payment_schedule,
             payment_method: payment_method.clone(),
             administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
         };
         assert_ok!(Payments::initialize_payment(
             Origin::signed(PAYER_ID),
             payment_details
         ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
         assert_ok!(
             Payments::claim(
                 Origin::signed(PAYEE_ID),
@@ -140,7 +220,8 @@ fn test_claim_successful_payment() {
         let expected_event = 
             crate::Event::PartOfPaymentClaimed(
                 PAYEE_ID, 
-                TOTAL_PAYMENT_AMOUNT / 2
+                TOTAL_PAYMENT_AMOUNT / 2,
+                0
             );
         System::assert_last_event(mock::Event::Payments(expected_event));
         let payment_agreements = Payments::payment_agreements(
@@ -182,6 +263,8 @@ fn test_claim_fails_before_payment_date() {
             payment_date: time + 500,
             amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
             released: true,
+            delinquent_since: None,
+            late_penalty: 0,
         };
         let payment_schedule = bounded_vec![
             scheduled_payment.clone(), 
@@ -199,11 +282,14 @@ fn test_claim_fails_before_payment_date() {
             payment_schedule,
             payment_method: payment_method.clone(),
             administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
         };
         assert_ok!(Payments::initialize_payment(
             Origin::signed(PAYER_ID),
             payment_details
         ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
         assert_noop!(
             Payments::claim(
                 Origin::signed(PAYEE_ID),
@@ -250,6 +336,8 @@ fn test_block_and_unblock_payment() {
             payment_date: time,
             amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
             released: true,
+            delinquent_since: None,
+            late_penalty: 0,
         };
         let payment_schedule = bounded_vec![
             scheduled_payment.clone(), 
@@ -267,11 +355,14 @@ fn test_block_and_unblock_payment() {
             payment_schedule,
             payment_method: payment_method.clone(),
             administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
         };
         assert_ok!(Payments::initialize_payment(
             Origin::signed(PAYER_ID),
             payment_details
         ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
         assert_ok!(
             Payments::block_next_payment(
                 Origin::signed(PAYER_ID),
@@ -336,6 +427,8 @@ fn test_integration_with_escrow() {
             payment_date: time,
             amount_per_claim: TOTAL_PAYMENT_AMOUNT,
             released: true,
+            delinquent_since: None,
+            late_penalty: 0,
         };
 		assert_ok!(EscrowModule::create_escrow(Origin::signed(ESCROW_ACCOUNT_ID)));
 		assert_ok!(EscrowModule::add_admin(Origin::signed(ESCROW_ACCOUNT_ID), PAYER_ID, ESCROW_ACCOUNT_ID));
@@ -356,11 +449,14 @@ fn test_integration_with_escrow() {
             payment_schedule,
             payment_method: payment_method.clone(),
             administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
         };
         assert_ok!(Payments::initialize_payment(
             Origin::signed(PAYER_ID),
             payment_details
         ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
         
         assert_ok!(
             Payments::claim(
@@ -372,3 +468,1328 @@ fn test_integration_with_escrow() {
     });
 }
 
+
+#[test]
+fn test_claim_available_pays_part_of_underfunded_payment() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        assert!(System::events().is_empty());
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT / 4
+        );
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment_1 = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let scheduled_payment_2 = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time + 500,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_schedule = bounded_vec![
+            scheduled_payment_1, 
+            scheduled_payment_2
+        ];
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::PersonalAccount,
+            account_id: PAYER_ID,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule,
+            payment_method: payment_method.clone(),
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+
+        // Only a quarter of the total is available, so half of the
+        // first scheduled payment is paid out
+        assert_ok!(
+            Payments::claim_available(
+                Origin::signed(PAYEE_ID),
+                PAYER_ID, 
+                PAYMENT_ID
+            )
+        );
+        let expected_event = 
+            crate::Event::PartialPaymentClaimed(
+                PAYEE_ID, 
+                PAYMENT_ID,
+                TOTAL_PAYMENT_AMOUNT / 4,
+                TOTAL_PAYMENT_AMOUNT / 2 - TOTAL_PAYMENT_AMOUNT / 4,
+            );
+        System::assert_last_event(mock::Event::Payments(expected_event));
+        let payment_agreements = Payments::payment_agreements(
+            (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+        ).unwrap();
+        assert_eq!(payment_agreements.payment_schedule.len(), 2);
+        assert_eq!(
+            payment_agreements.payment_schedule.first().unwrap().amount_per_claim, 
+            TOTAL_PAYMENT_AMOUNT / 2 - TOTAL_PAYMENT_AMOUNT / 4,
+        );
+        assert_noop!(
+            Payments::claim_available(
+                Origin::signed(PAYEE_ID),
+                PAYER_ID, 
+                PAYMENT_ID
+            ),
+            Error::<Test>::NoFundsAvailable
+        );
+
+        // Once the payer is funded again, the remainder can be claimed
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(
+            Payments::claim_available(
+                Origin::signed(PAYEE_ID),
+                PAYER_ID, 
+                PAYMENT_ID
+            )
+        );
+        let expected_event = 
+            crate::Event::PartOfPaymentClaimed(
+                PAYEE_ID, 
+                TOTAL_PAYMENT_AMOUNT / 2 - TOTAL_PAYMENT_AMOUNT / 4,
+                0,
+            );
+        System::assert_last_event(mock::Event::Payments(expected_event));
+        let payment_agreements = Payments::payment_agreements(
+            (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+        ).unwrap();
+        assert_eq!(payment_agreements.payment_schedule.len(), 1);
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(
+                &PAYEE_ID
+            ), 
+            TOTAL_PAYMENT_AMOUNT / 2,
+        );
+    });
+}
+
+#[test]
+fn test_payment_adjustment_requires_payee_acceptance() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        assert!(System::events().is_empty());
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment_1 = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let scheduled_payment_2 = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time + 500,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_schedule = bounded_vec![
+            scheduled_payment_1, 
+            scheduled_payment_2
+        ];
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::PersonalAccount,
+            account_id: PAYER_ID,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule,
+            payment_method: payment_method.clone(),
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert_noop!(
+            Payments::propose_payment_adjustment(
+                Origin::signed(PAYER_ID),
+                PAYEE_ID,
+                PAYMENT_ID,
+                time + 1,
+                TOTAL_PAYMENT_AMOUNT,
+            ),
+            Error::<Test>::ScheduledPaymentNotFound
+        );
+        assert_ok!(
+            Payments::propose_payment_adjustment(
+                Origin::signed(PAYER_ID),
+                PAYEE_ID,
+                PAYMENT_ID,
+                time + 500,
+                TOTAL_PAYMENT_AMOUNT,
+            )
+        );
+
+        // The schedule is untouched until the payee accepts
+        let payment_agreements = Payments::payment_agreements(
+            (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+        ).unwrap();
+        assert_eq!(
+            payment_agreements.payment_schedule.get(1).unwrap().amount_per_claim, 
+            TOTAL_PAYMENT_AMOUNT / 2,
+        );
+        assert_ok!(
+            Payments::accept_payment_adjustment(
+                Origin::signed(PAYEE_ID),
+                PAYER_ID,
+                PAYMENT_ID,
+                time + 500,
+            )
+        );
+        let expected_event = 
+            crate::Event::PaymentAdjustmentAccepted(
+                PAYER_ID, 
+                PAYEE_ID,
+                PAYMENT_ID,
+                time + 500,
+                TOTAL_PAYMENT_AMOUNT / 2,
+                TOTAL_PAYMENT_AMOUNT,
+            );
+        System::assert_last_event(mock::Event::Payments(expected_event));
+        let payment_agreements = Payments::payment_agreements(
+            (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+        ).unwrap();
+        assert_eq!(
+            payment_agreements.payment_schedule.get(1).unwrap().amount_per_claim, 
+            TOTAL_PAYMENT_AMOUNT,
+        );
+        assert_eq!(
+            payment_agreements.total_payment_amount, 
+            TOTAL_PAYMENT_AMOUNT / 2 + TOTAL_PAYMENT_AMOUNT,
+        );
+        assert_noop!(
+            Payments::accept_payment_adjustment(
+                Origin::signed(PAYEE_ID),
+                PAYER_ID,
+                PAYMENT_ID,
+                time + 500,
+            ),
+            Error::<Test>::NoAdjustmentProposed
+        );
+    });
+}
+
+#[test]
+fn test_stream_accrues_per_block_and_settles_on_pause_and_cancel() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        assert!(System::events().is_empty());
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        let rate_per_block: u128 = 10;
+        let stream_amount: u128 = 1000;
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::PersonalAccount,
+            account_id: PAYER_ID,
+        };
+        assert_ok!(Payments::initialize_stream(
+            Origin::signed(PAYER_ID),
+            PAYEE_ID,
+            PAYMENT_ID,
+            rate_per_block,
+            stream_amount,
+            payment_method,
+        ));
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::reserved_balance(&PAYER_ID), 
+            stream_amount
+        );
+
+        // 10 blocks accrue before the payee withdraws
+        System::set_block_number(11);
+        assert_ok!(Payments::withdraw_from_stream(
+            Origin::signed(PAYEE_ID),
+            PAYER_ID,
+            PAYMENT_ID,
+        ));
+        let expected_event = 
+            crate::Event::StreamWithdrawn(PAYEE_ID, PAYMENT_ID, 10 * rate_per_block);
+        System::assert_last_event(mock::Event::Payments(expected_event));
+
+        // Nothing accrues while the stream is paused
+        System::set_block_number(21);
+        assert_ok!(Payments::pause_stream(
+            Origin::signed(PAYER_ID),
+            PAYEE_ID,
+            PAYMENT_ID,
+        ));
+        System::set_block_number(51);
+        assert_ok!(Payments::resume_stream(
+            Origin::signed(PAYER_ID),
+            PAYEE_ID,
+            PAYMENT_ID,
+        ));
+        System::set_block_number(61);
+        assert_eq!(
+            Payments::payment_streams((PAYER_ID, PAYEE_ID, PAYMENT_ID)).unwrap().accrued, 
+            10 * rate_per_block
+        );
+
+        // Cancelling pays out everything accrued up to this block
+        assert_ok!(Payments::cancel_stream(
+            Origin::signed(PAYER_ID),
+            PAYEE_ID,
+            PAYMENT_ID,
+        ));
+        let expected_event = crate::Event::StreamCancelled(
+            PAYER_ID, 
+            PAYMENT_ID, 
+            20 * rate_per_block,
+            stream_amount - 30 * rate_per_block,
+        );
+        System::assert_last_event(mock::Event::Payments(expected_event));
+        assert!(Payments::payment_streams((PAYER_ID, PAYEE_ID, PAYMENT_ID)).is_none());
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            30 * rate_per_block
+        );
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::reserved_balance(&PAYER_ID), 
+            0
+        );
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::free_balance(&PAYER_ID), 
+            TOTAL_PAYMENT_AMOUNT - 30 * rate_per_block
+        );
+    });
+}
+
+#[test]
+fn test_stream_accrual_is_capped_at_total_amount() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::PersonalAccount,
+            account_id: PAYER_ID,
+        };
+        assert_noop!(
+            Payments::initialize_stream(
+                Origin::signed(PAYER_ID),
+                PAYEE_ID,
+                PAYMENT_ID,
+                10,
+                1000,
+                pallet_payments::PaymentMethod::<Test>{
+                    payment_source: pallet_payments::PaymentSource::PersonalAccount,
+                    account_id: ESCROW_ACCOUNT_ID,
+                },
+            ),
+            Error::<Test>::InvalidPaymentAccount
+        );
+        assert_ok!(Payments::initialize_stream(
+            Origin::signed(PAYER_ID),
+            PAYEE_ID,
+            PAYMENT_ID,
+            10,
+            1000,
+            payment_method,
+        ));
+        System::set_block_number(1000);
+        assert_ok!(Payments::withdraw_from_stream(
+            Origin::signed(PAYEE_ID),
+            PAYER_ID,
+            PAYMENT_ID,
+        ));
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            1000
+        );
+        assert_noop!(
+            Payments::withdraw_from_stream(
+                Origin::signed(PAYEE_ID),
+                PAYER_ID,
+                PAYMENT_ID,
+            ),
+            Error::<Test>::NoFundsAvailable
+        );
+    });
+}
+
+#[test]
+fn test_claim_asset_payment_from_escrow() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        assert!(System::events().is_empty());
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &ESCROW_ACCOUNT_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(Assets::force_create(Origin::root(), ASSET_ID, ESCROW_ACCOUNT_ID, true, 1));
+        assert_ok!(Assets::mint(Origin::signed(ESCROW_ACCOUNT_ID), ASSET_ID, ESCROW_ACCOUNT_ID, TOTAL_PAYMENT_AMOUNT));
+		assert_ok!(EscrowModule::create_escrow(Origin::signed(ESCROW_ACCOUNT_ID)));
+		assert_ok!(EscrowModule::add_admin(Origin::signed(ESCROW_ACCOUNT_ID), PAYER_ID, ESCROW_ACCOUNT_ID));
+		assert_ok!(EscrowModule::fund_escrow_asset(Origin::signed(ESCROW_ACCOUNT_ID), ESCROW_ACCOUNT_ID, ASSET_ID, TOTAL_PAYMENT_AMOUNT));
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_schedule = bounded_vec![
+            scheduled_payment.clone(), 
+        ];
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::EscrowAccount,
+            account_id: ESCROW_ACCOUNT_ID,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: ESCROW_ACCOUNT_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule,
+            payment_method: payment_method.clone(),
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: Some(ASSET_ID),
+            late_payment_terms: None,
+        };
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert_ok!(
+            Payments::claim(
+                Origin::signed(PAYEE_ID),
+                PAYER_ID, 
+                PAYMENT_ID
+            )
+        );
+
+        // The payment is drawn from the escrow's asset balance,
+        // leaving its native funds untouched
+        assert_eq!(Assets::balance(ASSET_ID, PAYEE_ID), TOTAL_PAYMENT_AMOUNT);
+        assert_eq!(EscrowModule::escrow_asset_balance(ESCROW_ACCOUNT_ID, ASSET_ID), 0);
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            0
+        );
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&ESCROW_ACCOUNT_ID), 
+            TOTAL_PAYMENT_AMOUNT
+        );
+    });
+}
+
+#[test]
+fn test_completed_payment_is_replaced_by_receipt() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_schedule = bounded_vec![
+            scheduled_payment.clone(), 
+            scheduled_payment
+        ];
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::PersonalAccount,
+            account_id: PAYER_ID,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule,
+            payment_method: payment_method.clone(),
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details.clone()
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert_ok!(Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert!(Payments::payment_receipts((PAYEE_ID, PAYER_ID, PAYMENT_ID)).is_none());
+        assert_ok!(Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+
+        // The completed agreement is removed and replaced by a receipt
+        assert!(
+            Payments::payment_agreements(
+                (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+            ).is_none()
+        );
+        assert!(
+            !pallet_payments::PaymentAgreementProgress::<Test>::contains_key(
+                (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+            )
+        );
+        let expected_receipt = pallet_payments::PaymentReceipt {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_paid: TOTAL_PAYMENT_AMOUNT / 2 * 2,
+            asset_id: None,
+            started_at: time / 1000,
+            completed_at: time / 1000,
+        };
+        assert_eq!(
+            Payments::payment_receipts((PAYEE_ID, PAYER_ID, PAYMENT_ID)),
+            Some(expected_receipt.clone())
+        );
+        assert_eq!(Payments::receipts_of(&PAYEE_ID), vec![expected_receipt]);
+
+        // The payment id cannot be reused until the receipt is pruned
+        assert_noop!(
+            Payments::initialize_payment(
+                Origin::signed(PAYER_ID),
+                payment_details
+            ),
+            Error::<Test>::PaymentAlreadyInitialized
+        );
+        assert_noop!(
+            Payments::prune_receipt(Origin::signed(PAYER_ID), PAYEE_ID, PAYMENT_ID),
+            Error::<Test>::ReceiptNonExistent
+        );
+        assert_ok!(Payments::prune_receipt(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert!(Payments::receipts_of(&PAYEE_ID).is_empty());
+        let expected_event = 
+            crate::Event::ReceiptPruned(PAYEE_ID, PAYER_ID, PAYMENT_ID);
+        System::assert_last_event(mock::Event::Payments(expected_event));
+    });
+}
+
+#[test]
+fn test_escrow_payment_reserves_funds_with_admin_authorization() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &ESCROW_ACCOUNT_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(EscrowModule::create_escrow(Origin::signed(ESCROW_ACCOUNT_ID)));
+        assert_ok!(EscrowModule::fund_escrow(Origin::signed(ESCROW_ACCOUNT_ID), ESCROW_ACCOUNT_ID, TOTAL_PAYMENT_AMOUNT));
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_schedule = bounded_vec![
+            scheduled_payment.clone(), 
+            scheduled_payment
+        ];
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::EscrowAccount,
+            account_id: ESCROW_ACCOUNT_ID,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule,
+            payment_method: payment_method.clone(),
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+
+        // Only an admin of the escrow can draw payments from it
+        assert_noop!(
+            Payments::initialize_payment(
+                Origin::signed(PAYER_ID),
+                payment_details.clone()
+            ),
+            pallet_escrow::Error::<Test>::Unauthorized
+        );
+        assert_ok!(EscrowModule::add_admin(Origin::signed(ESCROW_ACCOUNT_ID), PAYER_ID, ESCROW_ACCOUNT_ID));
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert_eq!(
+            EscrowModule::total_reserved_funds(ESCROW_ACCOUNT_ID, None::<u32>),
+            TOTAL_PAYMENT_AMOUNT / 2 * 2
+        );
+
+        // The reserved funds can't be paid out from the escrow directly
+        assert_noop!(
+            EscrowModule::payout_escrow(
+                Origin::signed(ESCROW_ACCOUNT_ID),
+                PAYEE_ID,
+                ESCROW_ACCOUNT_ID,
+                TOTAL_PAYMENT_AMOUNT / 2
+            ),
+            pallet_escrow::Error::<Test>::InsufficientEscrowFunds
+        );
+        assert_ok!(Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert_eq!(
+            EscrowModule::total_reserved_funds(ESCROW_ACCOUNT_ID, None::<u32>),
+            TOTAL_PAYMENT_AMOUNT / 2
+        );
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            TOTAL_PAYMENT_AMOUNT / 2
+        );
+    });
+}
+
+#[test]
+fn test_claim_available_only_draws_on_the_agreements_reservation() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &ESCROW_ACCOUNT_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(EscrowModule::create_escrow(Origin::signed(ESCROW_ACCOUNT_ID)));
+        assert_ok!(EscrowModule::fund_escrow(Origin::signed(ESCROW_ACCOUNT_ID), ESCROW_ACCOUNT_ID, TOTAL_PAYMENT_AMOUNT));
+        assert_ok!(EscrowModule::add_admin(Origin::signed(ESCROW_ACCOUNT_ID), PAYER_ID, ESCROW_ACCOUNT_ID));
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::EscrowAccount,
+            account_id: ESCROW_ACCOUNT_ID,
+        };
+        for (payment_id, amount) in [(PAYMENT_ID, TOTAL_PAYMENT_AMOUNT / 4), (PAYMENT_ID + 1, TOTAL_PAYMENT_AMOUNT / 2)] {
+            let payment_details = pallet_payments::PaymentDetails::<Test> {
+                payer: PAYER_ID,
+                payee: PAYEE_ID,
+                payment_id,
+                rfp_reference_id: RFP_REFERENCE_ID,
+                total_payment_amount: amount,
+                payment_schedule: bounded_vec![
+                    pallet_payments::ScheduledPayment::<Test> {
+                        payment_date: time,
+                        amount_per_claim: amount,
+                        released: true,
+                        delinquent_since: None,
+                        late_penalty: 0,
+                    }
+                ],
+                payment_method: payment_method.clone(),
+                administrator_id: ADMINISTRATOR_ID,
+                asset_id: None,
+                late_payment_terms: None,
+            };
+            assert_ok!(Payments::initialize_payment(
+                Origin::signed(PAYER_ID),
+                payment_details
+            ));
+            assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, payment_id));
+        }
+
+        // Each agreement can only pay out what the escrow reserved for it
+        let reservation_id = Payments::agreement_reservation(&PAYER_ID, &PAYEE_ID, &PAYMENT_ID);
+        let other_reservation_id = Payments::agreement_reservation(&PAYER_ID, &PAYEE_ID, &(PAYMENT_ID + 1));
+        assert_eq!(
+            Payments::available_funds(&payment_method, None, &reservation_id),
+            TOTAL_PAYMENT_AMOUNT / 4
+        );
+        assert_eq!(
+            Payments::available_funds(&payment_method, None, &other_reservation_id),
+            TOTAL_PAYMENT_AMOUNT / 2
+        );
+        assert_ok!(Payments::claim_available(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            TOTAL_PAYMENT_AMOUNT / 4
+        );
+        assert_eq!(
+            EscrowModule::reserved_funds((ESCROW_ACCOUNT_ID, None::<u32>, other_reservation_id)),
+            TOTAL_PAYMENT_AMOUNT / 2
+        );
+    });
+}
+
+#[test]
+fn test_payment_proposal_can_be_rejected_withdrawn_or_expire() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &ESCROW_ACCOUNT_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(EscrowModule::create_escrow(Origin::signed(ESCROW_ACCOUNT_ID)));
+        assert_ok!(EscrowModule::add_admin(Origin::signed(ESCROW_ACCOUNT_ID), PAYER_ID, ESCROW_ACCOUNT_ID));
+        assert_ok!(EscrowModule::fund_escrow(Origin::signed(ESCROW_ACCOUNT_ID), ESCROW_ACCOUNT_ID, TOTAL_PAYMENT_AMOUNT));
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::EscrowAccount,
+            account_id: ESCROW_ACCOUNT_ID,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule: bounded_vec![scheduled_payment],
+            payment_method: payment_method.clone(),
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+
+        // Rejecting a proposal returns the reserved escrow funds
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details.clone()
+        ));
+        assert_eq!(
+            EscrowModule::total_reserved_funds(ESCROW_ACCOUNT_ID, None::<u32>),
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(Payments::reject_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert!(
+            Payments::proposed_payment_agreements(
+                (PAYER_ID, PAYEE_ID, PAYMENT_ID)
+            ).is_none()
+        );
+        assert_eq!(EscrowModule::total_reserved_funds(ESCROW_ACCOUNT_ID, None::<u32>), 0);
+        let expected_event = 
+            crate::Event::PaymentProposalRejected(PAYER_ID, PAYEE_ID, PAYMENT_ID);
+        System::assert_last_event(mock::Event::Payments(expected_event));
+
+        // So does withdrawing it
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details.clone()
+        ));
+        assert_ok!(Payments::withdraw_payment_proposal(Origin::signed(PAYER_ID), PAYEE_ID, PAYMENT_ID));
+        assert_eq!(EscrowModule::total_reserved_funds(ESCROW_ACCOUNT_ID, None::<u32>), 0);
+        assert_noop!(
+            Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID),
+            Error::<Test>::ProposalNonExistent
+        );
+
+        // An expired proposal can no longer be accepted
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        let expiry: u64 = <Test as MyConfig>::PaymentProposalExpiry::get();
+        <timestamp::Pallet<Test>>::set_timestamp(time + (expiry + 1) * 1000);
+        assert_noop!(
+            Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID),
+            Error::<Test>::ProposalExpired
+        );
+    });
+}
+
+#[test]
+fn test_late_penalty_accrues_on_blocked_payment() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time / 1000,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::PersonalAccount,
+            account_id: PAYER_ID,
+        };
+        let late_payment_terms = pallet_payments::LatePaymentTerms {
+            grace_period: 100,
+            penalty_rate: Permill::from_percent(1),
+            penalty_period: 1000,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule: bounded_vec![scheduled_payment.clone(), scheduled_payment],
+            payment_method: payment_method.clone(),
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: Some(late_payment_terms),
+        };
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+
+        // A claimable payment isn't late
+        assert_noop!(
+            Payments::report_late_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID),
+            Error::<Test>::PaymentNotLate
+        );
+
+        // Blocking the due payment makes it late, and after the grace
+        // period a penalty accrues for every full penalty period
+        assert_ok!(Payments::block_next_payment(Origin::signed(PAYER_ID), PAYEE_ID, PAYMENT_ID));
+        <timestamp::Pallet<Test>>::set_timestamp(time + (100 + 2 * 1000 + 50) * 1000);
+        assert_ok!(Payments::release_next_payment(Origin::signed(PAYER_ID), PAYEE_ID, PAYMENT_ID));
+        let late_penalty = Permill::from_percent(1) * (TOTAL_PAYMENT_AMOUNT / 2) * 2;
+        assert_eq!(
+            Payments::payment_agreements((PAYER_ID, PAYEE_ID, PAYMENT_ID))
+                .unwrap()
+                .payment_schedule
+                .first()
+                .unwrap()
+                .late_penalty,
+            late_penalty
+        );
+
+        // Nothing more accrues once the payment is released
+        <timestamp::Pallet<Test>>::set_timestamp(time + (100 + 5 * 1000) * 1000);
+        assert_ok!(Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        let expected_event = 
+            crate::Event::PartOfPaymentClaimed(
+                PAYEE_ID, 
+                TOTAL_PAYMENT_AMOUNT / 2 + late_penalty,
+                late_penalty
+            );
+        System::assert_last_event(mock::Event::Payments(expected_event));
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            TOTAL_PAYMENT_AMOUNT / 2 + late_penalty
+        );
+    });
+}
+
+#[test]
+fn test_late_penalty_the_escrow_cannot_cover_is_owed_to_payee() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &ESCROW_ACCOUNT_ID, 
+            2 * TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(EscrowModule::create_escrow(Origin::signed(ESCROW_ACCOUNT_ID)));
+        assert_ok!(EscrowModule::fund_escrow(Origin::signed(ESCROW_ACCOUNT_ID), ESCROW_ACCOUNT_ID, TOTAL_PAYMENT_AMOUNT));
+        assert_ok!(EscrowModule::add_admin(Origin::signed(ESCROW_ACCOUNT_ID), PAYER_ID, ESCROW_ACCOUNT_ID));
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule: bounded_vec![
+                pallet_payments::ScheduledPayment::<Test> {
+                    payment_date: time / 1000,
+                    amount_per_claim: TOTAL_PAYMENT_AMOUNT,
+                    released: true,
+                    delinquent_since: None,
+                    late_penalty: 0,
+                }
+            ],
+            payment_method: pallet_payments::PaymentMethod::<Test>{
+                payment_source: pallet_payments::PaymentSource::EscrowAccount,
+                account_id: ESCROW_ACCOUNT_ID,
+            },
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: Some(pallet_payments::LatePaymentTerms {
+                grace_period: 100,
+                penalty_rate: Permill::from_percent(1),
+                penalty_period: 1000,
+            }),
+        };
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert_ok!(Payments::block_next_payment(Origin::signed(PAYER_ID), PAYEE_ID, PAYMENT_ID));
+        <timestamp::Pallet<Test>>::set_timestamp(time + (100 + 2 * 1000 + 50) * 1000);
+        assert_ok!(Payments::release_next_payment(Origin::signed(PAYER_ID), PAYEE_ID, PAYMENT_ID));
+
+        // The escrow only holds the scheduled amount, so the payment
+        // is paid out and the penalty stays owed to the payee
+        let late_penalty = TOTAL_PAYMENT_AMOUNT / 100 * 2;
+        assert_ok!(Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        System::assert_has_event(mock::Event::Payments(
+            crate::Event::LatePenaltyDeferred(PAYEE_ID, PAYMENT_ID, late_penalty)
+        ));
+        System::assert_last_event(mock::Event::Payments(
+            crate::Event::PartOfPaymentClaimed(PAYEE_ID, TOTAL_PAYMENT_AMOUNT, 0)
+        ));
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_eq!(
+            Payments::outstanding_penalties((PAYER_ID, PAYEE_ID, PAYMENT_ID)).unwrap().amount,
+            late_penalty
+        );
+        assert_noop!(
+            Payments::claim_late_penalty(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID),
+            pallet_escrow::Error::<Test>::InsufficientEscrowFunds
+        );
+
+        // Once the escrow can cover it, the payee claims the penalty
+        assert_ok!(EscrowModule::fund_escrow(Origin::signed(ESCROW_ACCOUNT_ID), ESCROW_ACCOUNT_ID, late_penalty));
+        assert_ok!(Payments::claim_late_penalty(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        System::assert_last_event(mock::Event::Payments(
+            crate::Event::LatePenaltyClaimed(PAYEE_ID, PAYMENT_ID, late_penalty)
+        ));
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            TOTAL_PAYMENT_AMOUNT + late_penalty
+        );
+        assert_noop!(
+            Payments::claim_late_penalty(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID),
+            Error::<Test>::NoPenaltyOutstanding
+        );
+    });
+}
+
+#[test]
+fn test_claim_available_pays_late_penalty_with_the_remainder() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT / 4
+        );
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: (TOTAL_PAYMENT_AMOUNT / 2).into(),
+            payment_schedule: bounded_vec![
+                pallet_payments::ScheduledPayment::<Test> {
+                    payment_date: time / 1000,
+                    amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+                    released: true,
+                    delinquent_since: None,
+                    late_penalty: 0,
+                }
+            ],
+            payment_method: pallet_payments::PaymentMethod::<Test>{
+                payment_source: pallet_payments::PaymentSource::PersonalAccount,
+                account_id: PAYER_ID,
+            },
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: Some(pallet_payments::LatePaymentTerms {
+                grace_period: 100,
+                penalty_rate: Permill::from_percent(1),
+                penalty_period: 1000,
+            }),
+        };
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        assert_ok!(Payments::block_next_payment(Origin::signed(PAYER_ID), PAYEE_ID, PAYMENT_ID));
+        <timestamp::Pallet<Test>>::set_timestamp(time + (100 + 2 * 1000 + 50) * 1000);
+        assert_ok!(Payments::release_next_payment(Origin::signed(PAYER_ID), PAYEE_ID, PAYMENT_ID));
+        let late_penalty = Permill::from_percent(1) * (TOTAL_PAYMENT_AMOUNT / 2) * 2;
+
+        // The penalty is carried over to the part of the payment still owed
+        assert_ok!(Payments::claim_available(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        let next_payment = Payments::payment_agreements((PAYER_ID, PAYEE_ID, PAYMENT_ID))
+            .unwrap()
+            .payment_schedule[0]
+            .clone();
+        assert_eq!(next_payment.amount_per_claim, TOTAL_PAYMENT_AMOUNT / 2 - TOTAL_PAYMENT_AMOUNT / 4);
+        assert_eq!(next_payment.late_penalty, late_penalty);
+
+        // And paid out along with it
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(Payments::claim_available(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        System::assert_last_event(mock::Event::Payments(
+            crate::Event::PartOfPaymentClaimed(
+                PAYEE_ID,
+                TOTAL_PAYMENT_AMOUNT / 2 - TOTAL_PAYMENT_AMOUNT / 4 + late_penalty,
+                late_penalty
+            )
+        ));
+        System::assert_has_event(mock::Event::Payments(
+            crate::Event::PaymentCompleted(
+                PAYER_ID,
+                PAYEE_ID,
+                PAYMENT_ID,
+                TOTAL_PAYMENT_AMOUNT / 2 + late_penalty
+            )
+        ));
+        assert_eq!(
+            <Test as MyConfig>::PaymentCurrency::total_balance(&PAYEE_ID), 
+            TOTAL_PAYMENT_AMOUNT / 2 + late_penalty
+        );
+    });
+}
+
+#[test]
+fn test_initialize_payment_batch_checks_escrow_balance() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        const SECOND_PAYEE_ID: u64 = 1235;
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &ESCROW_ACCOUNT_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(EscrowModule::create_escrow(Origin::signed(ESCROW_ACCOUNT_ID)));
+        assert_ok!(EscrowModule::add_admin(Origin::signed(ESCROW_ACCOUNT_ID), PAYER_ID, ESCROW_ACCOUNT_ID));
+        assert_ok!(EscrowModule::fund_escrow(Origin::signed(ESCROW_ACCOUNT_ID), ESCROW_ACCOUNT_ID, TOTAL_PAYMENT_AMOUNT));
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::EscrowAccount,
+            account_id: ESCROW_ACCOUNT_ID,
+        };
+        let payment_for = |payee: u64, amount: u128| pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: amount,
+            payment_schedule: bounded_vec![
+                pallet_payments::ScheduledPayment::<Test> {
+                    payment_date: time,
+                    amount_per_claim: amount,
+                    released: true,
+                    delinquent_since: None,
+                    late_penalty: 0,
+                }
+            ],
+            payment_method: payment_method.clone(),
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+
+        // The combined payments can't exceed what the escrow has uncommitted
+        assert_noop!(
+            Payments::initialize_payment_batch(
+                Origin::signed(PAYER_ID),
+                ESCROW_ACCOUNT_ID,
+                bounded_vec![
+                    payment_for(PAYEE_ID, TOTAL_PAYMENT_AMOUNT / 2),
+                    payment_for(SECOND_PAYEE_ID, TOTAL_PAYMENT_AMOUNT),
+                ]
+            ),
+            Error::<Test>::InsufficientEscrowFunds
+        );
+
+        // Every payment in the batch must be drawn from the batch's escrow
+        let mut personal_payment = payment_for(SECOND_PAYEE_ID, TOTAL_PAYMENT_AMOUNT / 2);
+        personal_payment.payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::PersonalAccount,
+            account_id: PAYER_ID,
+        };
+        assert_noop!(
+            Payments::initialize_payment_batch(
+                Origin::signed(PAYER_ID),
+                ESCROW_ACCOUNT_ID,
+                bounded_vec![
+                    payment_for(PAYEE_ID, TOTAL_PAYMENT_AMOUNT / 2),
+                    personal_payment,
+                ]
+            ),
+            Error::<Test>::PaymentNotFromBatchEscrow
+        );
+
+        assert_ok!(Payments::initialize_payment_batch(
+            Origin::signed(PAYER_ID),
+            ESCROW_ACCOUNT_ID,
+            bounded_vec![
+                payment_for(PAYEE_ID, TOTAL_PAYMENT_AMOUNT / 2),
+                payment_for(SECOND_PAYEE_ID, TOTAL_PAYMENT_AMOUNT / 2),
+            ]
+        ));
+        assert!(Payments::proposed_payment_agreements((PAYER_ID, PAYEE_ID, PAYMENT_ID)).is_some());
+        assert!(Payments::proposed_payment_agreements((PAYER_ID, SECOND_PAYEE_ID, PAYMENT_ID)).is_some());
+        assert_eq!(
+            EscrowModule::total_reserved_funds(ESCROW_ACCOUNT_ID, None::<u32>),
+            TOTAL_PAYMENT_AMOUNT / 2 * 2
+        );
+        System::assert_last_event(mock::Event::Payments(
+            crate::Event::PaymentBatchProposed(PAYER_ID, ESCROW_ACCOUNT_ID, 2)
+        ));
+    });
+}
+
+#[test]
+fn test_initialize_payment_with_auto_id_skips_taken_ids() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_method = pallet_payments::PaymentMethod::<Test>{
+            payment_source: pallet_payments::PaymentSource::PersonalAccount,
+            account_id: PAYER_ID,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule: bounded_vec![scheduled_payment],
+            payment_method,
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+
+        // A caller-chosen id is still accepted, and is skipped by the counter
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details.clone()
+        ));
+        assert_ok!(Payments::initialize_payment_with_auto_id(
+            Origin::signed(PAYER_ID),
+            payment_details.clone()
+        ));
+        assert!(Payments::proposed_payment_agreements((PAYER_ID, PAYEE_ID, 0)).is_some());
+        assert_ok!(Payments::initialize_payment_with_auto_id(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        let proposal = Payments::proposed_payment_agreements((PAYER_ID, PAYEE_ID, 2)).unwrap();
+        assert_eq!(proposal.payment_details.payment_id, 2);
+        assert_eq!(Payments::payment_id_counter(), 3);
+        let expires_at = time / 1000 + <Test as MyConfig>::PaymentProposalExpiry::get();
+        System::assert_last_event(mock::Event::Payments(
+            crate::Event::PaymentProposed(PAYER_ID, PAYEE_ID, 2, expires_at)
+        ));
+    });
+}
+
+#[test]
+fn test_completion_credential_is_issued_to_payee() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let collection_id: u128 = 42;
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &PAYER_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_noop!(
+            Payments::set_completion_credential(
+                Origin::signed(PAYER_ID),
+                RFP_REFERENCE_ID,
+                Some(collection_id)
+            ),
+            Error::<Test>::CannotIssueCredentials
+        );
+        assert_ok!(NTNFTModule::create_collection(
+            Origin::signed(PAYER_ID),
+            collection_id,
+            bounded_vec![b'i'],
+            bounded_vec![b'm'],
+        ));
+        assert_ok!(Payments::set_completion_credential(
+            Origin::signed(PAYER_ID),
+            RFP_REFERENCE_ID,
+            Some(collection_id)
+        ));
+        assert_eq!(
+            Payments::completion_credentials(PAYER_ID, RFP_REFERENCE_ID),
+            Some(collection_id)
+        );
+
+        let time: u64 = <timestamp::Pallet<Test>>::now();
+        let scheduled_payment = pallet_payments::ScheduledPayment::<Test> {
+            payment_date: time,
+            amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+            released: true,
+            delinquent_since: None,
+            late_penalty: 0,
+        };
+        let payment_details = pallet_payments::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id: PAYMENT_ID,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT.into(),
+            payment_schedule: bounded_vec![
+                scheduled_payment.clone(),
+                scheduled_payment
+            ],
+            payment_method: pallet_payments::PaymentMethod::<Test>{
+                payment_source: pallet_payments::PaymentSource::PersonalAccount,
+                account_id: PAYER_ID,
+            },
+            administrator_id: ADMINISTRATOR_ID,
+            asset_id: None,
+            late_payment_terms: None,
+        };
+        assert_ok!(Payments::initialize_payment(
+            Origin::signed(PAYER_ID),
+            payment_details
+        ));
+        assert_ok!(Payments::accept_payment(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+
+        // Nothing is issued before the last instalment is claimed
+        assert_ok!(Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        let ntnft_id = 0;
+        assert!(NTNFTModule::proposed_assignment((collection_id, PAYEE_ID, ntnft_id)).is_none());
+        assert_ok!(Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID));
+        System::assert_has_event(mock::Event::Payments(
+            crate::Event::CompletionCredentialIssued(PAYER_ID, PAYEE_ID, PAYMENT_ID, collection_id)
+        ));
+
+        // The credential references the RFP and the payment agreement
+        assert!(NTNFTModule::proposed_assignment((collection_id, PAYEE_ID, ntnft_id)).is_some());
+        assert_eq!(
+            NTNFTModule::item_metadata(collection_id, ntnft_id).unwrap().into_inner(),
+            (PAYER_ID, RFP_REFERENCE_ID, PAYMENT_ID).encode()
+        );
+        assert_ok!(NTNFTModule::accept_assignment(Origin::signed(PAYEE_ID), collection_id, ntnft_id));
+
+        assert_ok!(Payments::set_completion_credential(
+            Origin::signed(PAYER_ID),
+            RFP_REFERENCE_ID,
+            None
+        ));
+        assert!(Payments::completion_credentials(PAYER_ID, RFP_REFERENCE_ID).is_none());
+    });
+}
+
+#[test]
+fn test_migrate_payment_agreements_to_v1() {
+    let mut t = test_externalities();
+    t.execute_with(|| {
+        let _ = <Test as MyConfig>::PaymentCurrency::deposit_creating(
+            &ESCROW_ACCOUNT_ID, 
+            TOTAL_PAYMENT_AMOUNT
+        );
+        assert_ok!(EscrowModule::create_escrow(Origin::signed(ESCROW_ACCOUNT_ID)));
+        assert_ok!(EscrowModule::fund_escrow(Origin::signed(ESCROW_ACCOUNT_ID), ESCROW_ACCOUNT_ID, TOTAL_PAYMENT_AMOUNT));
+        assert_ok!(EscrowModule::add_admin(Origin::signed(ESCROW_ACCOUNT_ID), PAYER_ID, ESCROW_ACCOUNT_ID));
+        let old_payment_for = |payment_id: u32, payment_source: pallet_payments::PaymentSource| migrations::v1::v0::PaymentDetails::<Test> {
+            payer: PAYER_ID,
+            payee: PAYEE_ID,
+            payment_id,
+            rfp_reference_id: RFP_REFERENCE_ID,
+            total_payment_amount: TOTAL_PAYMENT_AMOUNT,
+            payment_schedule: bounded_vec![
+                migrations::v1::v0::ScheduledPayment::<Test> {
+                    payment_date: 0,
+                    amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+                    released: true,
+                },
+                migrations::v1::v0::ScheduledPayment::<Test> {
+                    payment_date: 500,
+                    amount_per_claim: TOTAL_PAYMENT_AMOUNT / 2,
+                    released: false,
+                }
+            ],
+            payment_method: pallet_payments::PaymentMethod::<Test>{
+                payment_source,
+                account_id: match payment_source {
+                    pallet_payments::PaymentSource::PersonalAccount => PAYER_ID,
+                    pallet_payments::PaymentSource::EscrowAccount => ESCROW_ACCOUNT_ID,
+                },
+            },
+            administrator_id: ADMINISTRATOR_ID,
+        };
+        migrations::v1::v0::PaymentAgreements::<Test>::insert(
+            (PAYER_ID, PAYEE_ID, PAYMENT_ID),
+            old_payment_for(PAYMENT_ID, pallet_payments::PaymentSource::PersonalAccount)
+        );
+        migrations::v1::v0::PaymentAgreements::<Test>::insert(
+            (PAYER_ID, PAYEE_ID, PAYMENT_ID + 1),
+            old_payment_for(PAYMENT_ID + 1, pallet_payments::PaymentSource::EscrowAccount)
+        );
+        StorageVersion::new(0).put::<Payments>();
+
+        migrations::v1::migrate::<Test>();
+        assert_eq!(StorageVersion::get::<Payments>(), StorageVersion::new(1));
+        let payment_details = Payments::payment_agreements((PAYER_ID, PAYEE_ID, PAYMENT_ID)).unwrap();
+        assert_eq!(payment_details.asset_id, None);
+        assert_eq!(payment_details.late_payment_terms, None);
+        assert_eq!(payment_details.payment_schedule.len(), 2);
+        assert_eq!(payment_details.payment_schedule[1].payment_date, 500);
+        assert_eq!(payment_details.payment_schedule[1].amount_per_claim, TOTAL_PAYMENT_AMOUNT / 2);
+        assert!(!payment_details.payment_schedule[1].released);
+        assert_eq!(payment_details.payment_schedule[1].delinquent_since, None);
+        assert_eq!(payment_details.payment_schedule[1].late_penalty, 0);
+
+        // Escrow-sourced agreements hold a reservation for their unclaimed payments
+        assert_eq!(
+            EscrowModule::reserved_funds((
+                ESCROW_ACCOUNT_ID,
+                None::<u32>,
+                Payments::agreement_reservation(&PAYER_ID, &PAYEE_ID, &(PAYMENT_ID + 1))
+            )),
+            TOTAL_PAYMENT_AMOUNT / 2 * 2
+        );
+        assert_ok!(Payments::claim(Origin::signed(PAYEE_ID), PAYER_ID, PAYMENT_ID + 1));
+        assert_eq!(
+            Payments::payment_progress((PAYER_ID, PAYEE_ID, PAYMENT_ID + 1)).amount_paid,
+            TOTAL_PAYMENT_AMOUNT / 2
+        );
+    });
+}
@@ -35,18 +35,84 @@
 //! - Accepting RFP bids.
 //! - Updating RFPs.
 //! - Canceling RFPs.
+//! - Closing bidding and expiring undecided RFPs at their scheduled blocks.
+//! - Sealed bidding, where bids are committed as hashes and revealed in a reveal period
+//!   after bidding closes, and the bonds of bids that are never revealed are slashed.
+//! - Bid bonds and storage deposits, reserved from bidders and refunded once the RFP is decided.
+//! - Withdrawing and rejecting bids, with every bid tracking where it is in the RFP process.
+//! - Versioning bid updates, keeping the terms of every version of a bid.
+//! - Cleaning up the bids of cancelled RFPs, in bounded batches, returning their bonds.
+//! - Awarding RFPs in lots, with one accepted bid and payment agreement per lot.
+//! - Keeping an RFP's budget range, payout asset and category tags on-chain,
+//!   and rejecting bids outside the budget range.
+//! - Requiring bidders to hold credentials, such as NT-NFT badges, checked
+//!   through the `CredentialInspect` trait.
+//! - Locking an RFP's budget in an escrow while it is open, and paying
+//!   accepted bids out of it.
+//! - Amending RFPs, keeping the CID of every previous version, and having
+//!   bidders acknowledge amendments before their bids can be shortlisted.
+//! - Private RFPs, which only take bids from invited accounts and the holders
+//!   of the RFP's invite credential.
+//! - Evaluation committees, whose members score bids on weighted criteria and approve
+//!   them, and award rules that only accept the top-scoring or approved bids.
+//!
+//! An RFP with a `budget_escrow` reserves its maximum budget in that escrow when it is
+//! created, so bidders can see the funds are committed. If the escrow is the owner's own
+//! account and they have no escrow yet, one is created and funded with the budget.
+//! Accepting a bid proposes a payment agreement built from the bid, paying its owner the
+//! bid amount in a single payment, in the RFP's asset. It is paid out of the escrow of an
+//! RFP with a budget escrow, and out of the owner's account otherwise. Whatever is left of
+//! the budget is released once every lot is decided, or the RFP expires or is cancelled.
+//!
+//! Every update of an RFP is a numbered amendment, which keeps the CID the RFP had before.
+//! Bidders who bid before an amendment must acknowledge it, or update their bid, before
+//! their bid can be shortlisted or accepted.
+//!
+//! An RFP can have an evaluation committee, whose members score its bids on the criteria
+//! weighted in its evaluation terms, and approve them. The average weighted score and the
+//! approvals of every bid are kept on-chain. Depending on its award rule, an RFP can only
+//! accept its top-scoring active bid, or a bid approved by a majority of its committee.
+//!
+//! An RFP is identified by its owner and RFP id together, so different owners can use the
+//! same RFP id. Every storage item about an RFP is keyed by both.
 //! 
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
-//! - `create_rfp` - 
-//! - `update_rfp` - 
+//! - `create_rfp` - Creates an RFP owned by the caller, accepting bids
+//! - `create_rfp_with_auto_id` - Creates an RFP under the next free RFP id,
+//!   which is reported in the `CreateRFP` event
+//! - `update_rfp` - Amends an RFP, keeping its previous CID; its owner and status can't change
 //! - `cancel_rfp` - 
+//! - `clean_up_cancelled_rfp` - Removes the next batch of bids of a cancelled RFP, returning their bonds
 //! - `bid_on_rfp` - 
+//! - `bid_on_rfp_with_auto_id` - Bids on an RFP under the next free bid id,
+//!   which is reported in the `BidOnRFP` event
+//! - `commit_sealed_bid` - Commits to a bid on a sealed RFP by the hash of its owner, RFP, bid id,
+//!   amount, CID and a salt
+//! - `reveal_bid` - Reveals a committed bid during the reveal period after bidding closes;
+//!   a reveal that doesn't match the commitment or the budget disqualifies the bid
+//! - `release_bid_bond` - Returns a bid's bond and storage deposit once the RFP is decided
+//! - `withdraw_bid` - Bidder withdraws a submitted or shortlisted bid, forfeiting its bond
+//! - `reject_bid` - RFP owner rejects a submitted or shortlisted bid, returning its bond
 //! - `shortlist_bid` -
 //! - `update_rfp_bid` -
-//! - `accept_rfp_bid` -
+//! - `acknowledge_rfp_amendment` - Bidder acknowledges the latest amendment of the RFP, keeping their bid
+//! - `accept_rfp_bid` - Awards a lot of an RFP to a bid, proposing a payment agreement for it
+//!   that is built from the bid
+//! - `leave_lot_unawarded` - RFP owner decides not to award a lot of an RFP
+//! - `add_rfp_invitee` - RFP owner invites an account to bid on their private RFP
+//! - `remove_rfp_invitee` - RFP owner withdraws an invitation to bid on their private RFP
+//! - `set_evaluation_committee` - RFP owner sets the committee that evaluates the bids on their RFP
+//! - `score_bid` - Committee member scores a bid on every evaluation criterion of the RFP
+//! - `approve_bid` - Committee member approves a bid
+//!
+//! ### Hooks
+//!
+//! - `on_initialize` - Stops RFPs from accepting bids once their bid-close block
+//!   is reached, and expires RFPs that have no accepted bid by their decision deadline
+//! - `on_runtime_upgrade` - Runs the pallet's storage migrations, see [`migrations`]
 
 #![cfg_attr(not(feature = "std"), no_std)]
 pub use pallet::*;
@@ -60,32 +126,112 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::{
 		pallet_prelude::*,
 		traits::{
 			Currency,
-			LockableCurrency
+			LockableCurrency,
+			ReservableCurrency,
+			UnixTime,
 		},
 		storage::bounded_vec::BoundedVec,
+		bounded_vec,
 	};
-	use frame_system::pallet_prelude::*;
+	use frame_system::{pallet_prelude::*, RawOrigin};
 	use pallet_payments;
+	use pallet_escrow::EscrowInterface;
+	use pallet_ntnft::CredentialInspect;
+	use sp_runtime::{
+		traits::{CheckedSub, Hash, Saturating, Zero},
+		SaturatedConversion,
+	};
 
 	pub const VEC_LIMIT: u32 = u32::MAX;
 
+	/// How many ids in a row an allocation skips over because
+	/// they are already in use, before it gives up
+	pub const MAX_ID_PROBES: u32 = 64;
+
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_payments::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-		type RFPId: Member + Parameter + MaxEncodedLen + From<u32> + Copy + Clone + Eq + TypeInfo;
-		type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+		type RFPId: Member + Parameter + MaxEncodedLen + From<u32> + Copy + Clone + Eq + TypeInfo
+			+ Into<Self::RFPReferenceId>;
+		/// The currency bonds are reserved in, which bids are paid out in as well
+		type Currency: LockableCurrency<
+				Self::AccountId,
+				Moment = Self::BlockNumber,
+				Balance = pallet_payments::BalanceOf<Self>,
+			>
+			+ ReservableCurrency<Self::AccountId>;
 		type Cid: MaxEncodedLen + TypeInfo + Decode + Encode + Clone + Eq + sp_std::fmt::Debug;
 		type BidId: Member + Parameter + MaxEncodedLen + From<u32> + Copy + Clone + Eq + TypeInfo;
+		/// The maximum number of RFPs whose bidding closes,
+		/// or whose decision deadline falls, in the same block
+		#[pallet::constant]
+		type MaxRFPDeadlinesPerBlock: Get<u32>;
+		/// The deposit reserved from a bidder for the storage of each bid
+		#[pallet::constant]
+		type BidDeposit: Get<BalanceOf<Self>>;
+		/// The maximum number of bids of a cancelled RFP removed in one call
+		#[pallet::constant]
+		type MaxBidsCleanedPerCall: Get<u32>;
+		/// The maximum number of lots an RFP can be awarded in
+		#[pallet::constant]
+		type MaxLotsPerRFP: Get<u32>;
+		/// The maximum number of category tags on an RFP
+		#[pallet::constant]
+		type MaxCategoryTags: Get<u32>;
+		/// The maximum length of a category tag, in bytes
+		#[pallet::constant]
+		type MaxCategoryTagLength: Get<u32>;
+		/// Identifies a kind of credential, such as the NT-NFT collection of a KYC badge
+		type CredentialId: Member + Parameter + MaxEncodedLen + Copy;
+		/// Checks the credentials held by bidders
+		type Credentials: CredentialInspect<Self::AccountId, Self::CredentialId>;
+		/// The maximum number of credentials an RFP can require of its bidders
+		#[pallet::constant]
+		type MaxRequiredCredentials: Get<u32>;
+		/// The maximum number of criteria bids on an RFP can be scored on
+		#[pallet::constant]
+		type MaxEvaluationCriteria: Get<u32>;
+		/// The maximum number of members of an RFP's evaluation committee
+		#[pallet::constant]
+		type MaxCommitteeMembers: Get<u32>;
+		/// The highest score a committee member can give a bid on one criterion
+		#[pallet::constant]
+		type MaxCriterionScore: Get<u8>;
+		/// The number of blocks after bidding closes during which
+		/// the bids on a sealed RFP can be revealed
+		#[pallet::constant]
+		type RevealPeriod: Get<Self::BlockNumber>;
+		/// The maximum number of bids an RFP can take, sealed or not
+		#[pallet::constant]
+		type MaxBidsPerRFP: Get<u32>;
+		/// The maximum number of sealed bids an RFP can take
+		#[pallet::constant]
+		type MaxSealedBidsPerRFP: Get<u32>;
+		/// The maximum number of versions of a bid, counting the bid as first made
+		#[pallet::constant]
+		type MaxBidVersions: Get<u32>;
+		/// The maximum number of times an RFP can be amended
+		#[pallet::constant]
+		type MaxRFPAmendments: Get<u32>;
+		/// The maximum number of accounts invited to bid on an RFP
+		#[pallet::constant]
+		type MaxRFPInvitees: Get<u32>;
 	}
 
 	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
@@ -96,16 +242,174 @@ pub mod pallet {
 		pub(super) ipfs_hash: T::Cid,
 
 		pub(super) rfp_status: RFPStatus,
+
+		/// The first block bids are accepted in
+		pub(super) bid_open: T::BlockNumber,
+
+		/// Bids are no longer accepted from this block on
+		pub(super) bid_close: T::BlockNumber,
+
+		/// The RFP expires if no bid is accepted by this block
+		pub(super) decision_deadline: Option<T::BlockNumber>,
+
+		pub(super) bidding_mode: BiddingMode,
+
+		/// The bond reserved from every bidder, returned once the RFP is decided
+		pub(super) bid_bond: BalanceOf<T>,
+
+		/// The number of lots the RFP is awarded in, each to a different bid
+		pub(super) lots: u32,
+
+		pub(super) requirements: RFPRequirements<T>,
+
+		/// The escrow the maximum budget is locked in while the RFP is open.
+		/// Set to the owner's own account to create and fund their escrow
+		pub(super) budget_escrow: Option<T::AccountId>,
+
+		pub(super) visibility: RFPVisibility,
+
+		/// Holders of this credential can bid on a private RFP without an invitation
+		pub(super) invite_credential: Option<T::CredentialId>,
+
+		pub(super) evaluation: EvaluationTerms<T>,
+	}
+
+	#[derive(DefaultNoBound, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// How the evaluation committee of an RFP scores its bids,
+	/// and what it takes for a bid to be accepted
+	pub struct EvaluationTerms<T: Config>{
+		/// The weight of every criterion bids are scored on
+		pub(super) criteria_weights: BoundedVec<u32, T::MaxEvaluationCriteria>,
+
+		pub(super) award_rule: AwardRule,
+	}
+
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, Eq, TypeInfo, Copy, MaxEncodedLen)]
+	/// Describes whether the RFP owner accepts bids at their own discretion, or
+	/// only the top-scoring bid, or only bids approved by a majority of the committee
+	pub enum AwardRule {
+		#[default]
+		OwnerDecides,
+		TopScore,
+		CommitteeApproval
+	}
+
+	#[derive(Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// The accounts that evaluate the bids on an RFP
+	pub struct EvaluationCommittee<T: Config>{
+		pub(super) members: BoundedVec<T::AccountId, T::MaxCommitteeMembers>,
+
+		/// Once a bid is scored or approved, neither the committee
+		/// nor the evaluation terms of the RFP can change
+		pub(super) evaluation_started: bool,
+	}
+
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	/// The scores and approvals a bid got from the evaluation committee
+	pub struct BidEvaluation {
+		/// The sum of the weighted scores given by every member who scored the bid
+		pub(super) weighted_total: u64,
+
+		/// The number of members who scored the bid
+		pub(super) scorers: u32,
+
+		/// The number of members who approved the bid
+		pub(super) approvals: u32,
+	}
+
+	impl BidEvaluation {
+		/// Whether the average score of this bid is at least that of `other`
+		pub fn scores_at_least(&self, other: &BidEvaluation) -> bool {
+			(self.weighted_total as u128).saturating_mul(other.scorers as u128) >=
+				(other.weighted_total as u128).saturating_mul(self.scorers as u128)
+		}
+	}
+
+	pub type CategoryTag<T> = BoundedVec<u8, <T as Config>::MaxCategoryTagLength>;
+
+	#[derive(DefaultNoBound, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// The terms of an RFP that are kept on-chain, so bids can be checked
+	/// against them, and RFPs can be filtered without fetching their CID
+	pub struct RFPRequirements<T: Config>{
+		/// The lowest bid amount accepted, if any
+		pub(super) budget_min: Option<BalanceOf<T>>,
+
+		/// The highest bid amount accepted, if any
+		pub(super) budget_max: Option<BalanceOf<T>>,
+
+		/// The asset the awarded bids are paid in, or the native currency if `None`
+		pub(super) asset_id: Option<pallet_payments::AssetIdOf<T>>,
+
+		/// Tags describing the kind of work the RFP is for
+		pub(super) category_tags: BoundedVec<CategoryTag<T>, T::MaxCategoryTags>,
+
+		/// The credentials every bidder must hold
+		pub(super) required_credentials: BoundedVec<T::CredentialId, T::MaxRequiredCredentials>,
+	}
+
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, Eq, TypeInfo, Copy, MaxEncodedLen)]
+	/// Describes whether bids on an RFP are public as soon as they are made,
+	/// or sealed until bidding closes
+	pub enum BiddingMode {
+		#[default]
+		Open,
+		Sealed
+	}
+
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, Eq, TypeInfo, Copy, MaxEncodedLen)]
+	/// Describes whether anyone can bid on an RFP, or only the accounts
+	/// the RFP owner invited and the holders of its invite credential
+	pub enum RFPVisibility {
+		#[default]
+		Public,
+		Private
 	}
 
 	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, Eq, TypeInfo, Copy, MaxEncodedLen)]
 	/// Describes whether the RPF Owner is accepting bids, not accepting new bids,
-	/// or if a bid has already been accepted for this RFP
+	/// if every lot of this RFP has been decided with at least one bid accepted,
+	/// or if the RFP expired without a bid being accepted. An RFP with some of
+	/// its lots awarded, but not all of its lots decided, is partially awarded
 	pub enum RFPStatus {
 		#[default]
 		AcceptingBids,
 		NotAcceptingNewBids,
-		AcceptedBid
+		AcceptedBid,
+		Expired,
+		PartiallyAwarded
+	}
+
+	#[derive(Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// A bid on a sealed RFP that has not been revealed yet
+	pub struct SealedBid<T: Config>{
+		pub(super) bid_owner: T::AccountId,
+
+		pub(super) rfp_owner: T::AccountId,
+
+		pub(super) rfp_id: T::RFPId,
+
+		/// Hash of the bid owner, RFP owner, RFP id, bid id,
+		/// bid amount, the bid's CID and a salt
+		pub(super) commitment: T::Hash,
+	}
+
+	#[derive(Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// The funds reserved from a bidder for a bid
+	pub struct BidBond<T: Config>{
+		pub(super) bidder: T::AccountId,
+
+		pub(super) rfp_owner: T::AccountId,
+
+		pub(super) rfp_id: T::RFPId,
+
+		pub(super) bond: BalanceOf<T>,
+
+		pub(super) storage_deposit: BalanceOf<T>,
 	}
 
 	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
@@ -116,10 +420,46 @@ pub mod pallet {
 
 		pub(super) ipfs_hash: T::Cid,
 
-		pub(super) bid_amount: BalanceOf<T>
+		pub(super) bid_amount: BalanceOf<T>,
+
+		pub(super) bid_status: BidStatus,
+
+		/// Incremented every time the bidder updates the bid
+		pub(super) version: u32,
+	}
+
+	#[derive(Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// The terms of one version of a bid
+	pub struct BidTerms<T: Config>{
+		pub(super) ipfs_hash: T::Cid,
+
+		pub(super) bid_amount: BalanceOf<T>,
+
+		/// The block the bid was submitted, or updated, with these terms
+		pub(super) submitted_at: T::BlockNumber,
+	}
+
+	#[derive(Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	/// What an RFP looked like before one of its amendments
+	pub struct RFPAmendment<T: Config>{
+		/// The CID the RFP had before it was amended
+		pub(super) previous_ipfs_hash: T::Cid,
+
+		/// The block the RFP was amended in
+		pub(super) amended_at: T::BlockNumber,
+	}
 
-		// TODO: Add status (bid, shortlisted, accepted)
-		// TODO: Add rfpid associated with this bid
+	#[derive(Default, Clone, Encode, Decode, RuntimeDebugNoBound, PartialEq, Eq, TypeInfo, Copy, MaxEncodedLen)]
+	/// Describes where a bid is in the RFP process
+	pub enum BidStatus {
+		#[default]
+		Submitted,
+		Shortlisted,
+		Withdrawn,
+		Rejected,
+		Accepted
 	}
 
 	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<
@@ -148,12 +488,68 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn sealed_bids)]
+	pub type SealedBids<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BidId, // bid_id
+		SealedBid<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_unrevealed_bids)]
+	pub type RFPUnrevealedBids<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // rfp owner
+		Blake2_128Concat,
+		T::RFPId, // rfp_id
+		BoundedVec<T::BidId, T::MaxSealedBidsPerRFP>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bid_history)]
+	pub type BidHistory<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::BidId, // bid_id
+		Twox64Concat,
+		u32, // version
+		BidTerms<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bid_to_rfp)]
+	pub type BidToRFP<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BidId, // bid_id
+		(T::AccountId, T::RFPId), // (rfp owner, rfp_id)
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bid_bonds)]
+	pub type BidBonds<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BidId, // bid_id
+		BidBond<T>,
+		OptionQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn rfp_to_bids)]
-	pub type RFPToBids<T: Config> = StorageMap<
+	pub type RFPToBids<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		T::RFPId, // rfP_id
+		T::AccountId, // rfp owner
+		Blake2_128Concat,
+		T::RFPId, // rfp_id
 		BoundedVec<
 				T::BidId, ConstU32<{VEC_LIMIT}>
 			>,
@@ -162,9 +558,11 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn rfp_to_shortlisted_bids)]
-	pub type RFPToShortlistedBids<T: Config> = StorageMap<
+	pub type RFPToShortlistedBids<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
+		T::AccountId, // rfp owner
+		Blake2_128Concat,
 		T::RFPId, // rfp_id
 		BoundedVec<
 				T::BidId, ConstU32<{VEC_LIMIT}>
@@ -174,23 +572,220 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn rfp_to_accepted_bid)]
-	pub type RFPToAcceptedBid<T: Config> = StorageMap<
+	pub type RFPToAcceptedBid<T: Config> = StorageNMap<
 		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // rfp owner
+			NMapKey<Blake2_128Concat, T::RFPId>, // rfp_id
+			NMapKey<Twox64Concat, u32>, // lot
+		),
+		T::BidId, // bid_id
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_unawarded_lots)]
+	pub type RFPUnawardedLots<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // rfp owner
+			NMapKey<Blake2_128Concat, T::RFPId>, // rfp_id
+			NMapKey<Twox64Concat, u32>, // lot
+		),
+		(),
+		OptionQuery,
+	>;
+
+	/// The number of times an RFP has been amended
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_amendment_count)]
+	pub type RFPAmendmentCount<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // rfp owner
+		Blake2_128Concat,
+		T::RFPId, // rfp_id
+		u32,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_amendments)]
+	pub type RFPAmendments<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // rfp owner
+			NMapKey<Blake2_128Concat, T::RFPId>, // rfp_id
+			NMapKey<Twox64Concat, u32>, // amendment
+		),
+		RFPAmendment<T>,
+		OptionQuery,
+	>;
+
+	/// The latest amendment of its RFP a bid was made, updated or acknowledged under
+	#[pallet::storage]
+	#[pallet::getter(fn bid_acknowledged_amendment)]
+	pub type BidAcknowledgedAmendment<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BidId, // bid_id
+		u32, // amendment
+		ValueQuery,
+	>;
+
+	/// The accounts invited to bid on a private RFP
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_invitees)]
+	pub type RFPInvitees<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>, // rfp owner
+			NMapKey<Blake2_128Concat, T::RFPId>, // rfp_id
+			NMapKey<Blake2_128Concat, T::AccountId>, // invitee
+		),
+		(),
+		OptionQuery,
+	>;
+
+	/// The number of accounts invited to bid on an RFP
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_invitee_count)]
+	pub type RFPInviteeCount<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // rfp owner
+		Blake2_128Concat,
+		T::RFPId, // rfp_id
+		u32,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_committees)]
+	pub type RFPCommittees<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // rfp owner
 		Blake2_128Concat,
 		T::RFPId, // rfp_id
+		EvaluationCommittee<T>,
+		OptionQuery,
+	>;
+
+	/// The scores a committee member gave a bid, one for every criterion of the RFP
+	#[pallet::storage]
+	#[pallet::getter(fn bid_scores)]
+	pub type BidScores<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::BidId, // bid_id
+		Blake2_128Concat,
+		T::AccountId, // committee member
+		BoundedVec<u8, T::MaxEvaluationCriteria>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bid_approvals)]
+	pub type BidApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::BidId, // bid_id
+		Blake2_128Concat,
+		T::AccountId, // committee member
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bid_evaluations)]
+	pub type BidEvaluations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
 		T::BidId, // bid_id
+		BidEvaluation,
+		ValueQuery,
+	>;
+
+	/// The number of lots of an RFP that have been awarded or left unawarded
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_lots_decided)]
+	pub type RFPLotsDecided<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // rfp owner
+		Blake2_128Concat,
+		T::RFPId, // rfp_id
+		u32,
+		ValueQuery,
+	>;
+
+	/// The part of an RFP's budget still reserved in its budget escrow
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_budget_reserved)]
+	pub type RFPBudgetReserved<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // rfp owner
+		Blake2_128Concat,
+		T::RFPId, // rfp_id
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn cancelled_rfps)]
+	pub type CancelledRFPs<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // rfp owner
+		Blake2_128Concat,
+		T::RFPId, // rfp_id
+		(),
 		OptionQuery,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn rfps_closing_at)]
+	pub type RFPsClosingAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber, // bid_close
+		BoundedVec<
+				(T::AccountId, T::RFPId), T::MaxRFPDeadlinesPerBlock
+			>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rfps_expiring_at)]
+	pub type RFPsExpiringAt<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber, // decision_deadline
+		BoundedVec<
+				(T::AccountId, T::RFPId), T::MaxRFPDeadlinesPerBlock
+			>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rfp_id_counter)]
+	pub type NextRFPId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bid_id_counter)]
+	pub type NextBidId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Creates an RFP
 		/// [account, rfp]
 		CreateRFP(T::AccountId, T::RFPId),
-		/// Updates an RFP
-		/// [account, rfp]
-		UpdateRFP(T::AccountId, T::RFPId),
+		/// Amends an RFP
+		/// [account, rfp, amendment]
+		UpdateRFP(T::AccountId, T::RFPId, u32),
 		/// Cancels an RFP
 		/// [account, rfp]
 		CancelRFP(T::AccountId, T::RFPId),
@@ -201,11 +796,78 @@ pub mod pallet {
 		/// [account, rfp, bid_id]
 		ShortlistBid(T::AccountId, T::RFPId, T::BidId),
 		/// Updates a bid on an RFP
+		/// [account, rfp, bid_id, version]
+		UpdateRFPBid(T::AccountId, T::RFPId, T::BidId, u32),
+		/// Accepts a bid on an RFP, awarding it a lot of the RFP
+		/// [account, rfp, bid_id, lot]
+		AcceptRFPBid(T::AccountId, T::RFPId, T::BidId, u32),
+		/// RFP Admin decides not to award a lot of an RFP
+		/// [account, rfp, lot]
+		LotLeftUnawarded(T::AccountId, T::RFPId, u32),
+		/// Every lot of an RFP has been awarded or left unawarded
+		/// [account, rfp]
+		RFPAwardsCompleted(T::AccountId, T::RFPId),
+		/// Every lot of an RFP was left unawarded
+		/// [account, rfp]
+		RFPClosedWithoutAward(T::AccountId, T::RFPId),
+		/// The budget of an RFP has been reserved in its budget escrow
+		/// [account, rfp, escrow_id, amount]
+		RFPBudgetLocked(T::AccountId, T::RFPId, T::AccountId, BalanceOf<T>),
+		/// What was left of an RFP's budget has been released in its budget escrow
+		/// [account, rfp, escrow_id, amount]
+		RFPBudgetReleased(T::AccountId, T::RFPId, T::AccountId, BalanceOf<T>),
+		/// Bidding on an RFP closed at its bid-close block
+		/// [account, rfp]
+		RFPBiddingClosed(T::AccountId, T::RFPId),
+		/// An RFP had no accepted bid by its decision deadline
+		/// [account, rfp]
+		RFPExpired(T::AccountId, T::RFPId),
+		/// Commits to a sealed bid on an RFP
 		/// [account, rfp, bid_id]
-		UpdateRFPBid(T::AccountId, T::RFPId, T::BidId),
-		/// Accepts a bid on an RFP
+		SealedBidCommitted(T::AccountId, T::RFPId, T::BidId),
+		/// Reveals a sealed bid on an RFP
 		/// [account, rfp, bid_id]
-		AcceptRFPBid(T::AccountId, T::RFPId, T::BidId),
+		BidRevealed(T::AccountId, T::RFPId, T::BidId),
+		/// A revealed bid did not match its commitment, or the RFP's budget
+		/// [account, rfp, bid_id]
+		BidDisqualified(T::AccountId, T::RFPId, T::BidId),
+		/// A bid's bond and storage deposit were returned to the bidder
+		/// [account, rfp, bid_id]
+		BidBondReleased(T::AccountId, T::RFPId, T::BidId),
+		/// A bid's bond was slashed, and its storage deposit returned
+		/// [account, rfp, bid_id]
+		BidBondSlashed(T::AccountId, T::RFPId, T::BidId),
+		/// Withdraws a bid on an RFP
+		/// [account, rfp, bid_id]
+		BidWithdrawn(T::AccountId, T::RFPId, T::BidId),
+		/// RFP Admin rejects a bid on an RFP
+		/// [account, rfp, bid_id]
+		BidRejected(T::AccountId, T::RFPId, T::BidId),
+		/// A bid was removed because its RFP was cancelled,
+		/// and its bond returned to the bidder
+		/// [account, rfp, bid_id]
+		BidCancelled(T::AccountId, T::RFPId, T::BidId),
+		/// Every bid of a cancelled RFP has been removed
+		/// [account, rfp]
+		RFPCleanupCompleted(T::AccountId, T::RFPId),
+		/// A bidder acknowledged the latest amendment of an RFP
+		/// [account, rfp, bid_id, amendment]
+		RFPAmendmentAcknowledged(T::AccountId, T::RFPId, T::BidId, u32),
+		/// An account was invited to bid on a private RFP
+		/// [account, rfp, invitee]
+		BidderInvited(T::AccountId, T::RFPId, T::AccountId),
+		/// An account's invitation to bid on a private RFP was withdrawn
+		/// [account, rfp, invitee]
+		BidderUninvited(T::AccountId, T::RFPId, T::AccountId),
+		/// RFP Admin sets the evaluation committee of an RFP
+		/// [account, rfp]
+		EvaluationCommitteeSet(T::AccountId, T::RFPId),
+		/// A committee member scores a bid on an RFP
+		/// [account, rfp, bid_id, weighted_score]
+		BidScored(T::AccountId, T::RFPId, T::BidId, u64),
+		/// A committee member approves a bid on an RFP
+		/// [account, rfp, bid_id]
+		BidApproved(T::AccountId, T::RFPId, T::BidId),
 	}
 
 	#[pallet::error]
@@ -257,58 +919,265 @@ pub mod pallet {
 		/// Accepting a bid for an RFP that has already had a bid accepted
 		BidAlreadyAccepted,
 
-		/// Bid was accepted, but there was an error with the payment initialization
-		PaymentInitializationFailed,
-
 		/// Bid on an RFP that is not currently accepting new bids
-		RFPNotAcceptingBids
-	}
+		RFPNotAcceptingBids,
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// A dispatchable to create an RFP
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
-		pub fn create_rfp(
-			origin: OriginFor<T>, 
-			rfp_id: T::RFPId,
+		/// Every RFP id the counter can allocate is taken,
+		/// or the next [`MAX_ID_PROBES`] ids are
+		RFPIdsExhausted,
+
+		/// Every bid id the counter can allocate is taken,
+		/// or the next [`MAX_ID_PROBES`] ids are
+		BidIdsExhausted,
+
+		/// Bids must open before they close, bidding must close in the
+		/// future, and the decision deadline must come after bidding closes,
+		/// and after the reveal period of a sealed RFP
+		InvalidRFPSchedule,
+
+		/// Bid on an RFP before its bid-open block
+		BiddingNotOpen,
+
+		/// Bid on an RFP at or after its bid-close block
+		BiddingClosed,
+
+		/// Too many RFPs already close bidding or expire in that block
+		TooManyRFPDeadlines,
+
+		/// Accepting a bid for an RFP that has expired
+		RFPHasExpired,
+
+		/// Bidding in the clear on an RFP that takes sealed bids
+		RFPRequiresSealedBids,
+
+		/// Committing a sealed bid on an RFP that takes open bids
+		RFPDoesNotTakeSealedBids,
+
+		/// Trying to reveal a sealed bid that doesn't exist
+		NonExistentSealedBid,
+
+		/// Someone other than the bid owner attempted
+		/// to reveal the bid
+		UnauthorizedRevealOfBid,
+
+		/// Revealing a bid before bidding on the RFP has closed,
+		/// or after its reveal period has ended
+		NotRevealPeriod,
+
+		/// Deciding on the bids of a sealed RFP before its reveal period has ended
+		RevealPeriodNotOver,
+
+		/// The RFP already takes the maximum number of sealed bids
+		TooManySealedBids,
+
+		/// Trying to release a bid bond that doesn't exist
+		NonExistentBidBond,
+
+		/// Someone other than the bidder attempted
+		/// to release the bid bond
+		UnauthorizedReleaseOfBidBond,
+
+		/// Releasing a bid bond before a bid was accepted, or the RFP expired
+		RFPNotDecided,
+
+		/// Trying to withdraw or reject a bid that doesn't exist
+		NonExistentBid,
+
+		/// Someone other than the bid owner attempted
+		/// to withdraw the bid
+		UnauthorizedWithdrawalOfBid,
+
+		/// Someone other than the RFP owner attempted
+		/// to reject the bid
+		UnauthorizedRejectionOfBid,
+
+		/// The bid has already been withdrawn, rejected or accepted
+		BidNotActive,
+
+		/// Trying to shortlist a bid that is already shortlisted
+		BidAlreadyShortlisted,
+
+		/// Trying to update a bid that has been shortlisted
+		BidNoLongerUpdatable,
+
+		/// The bid already has the maximum number of versions
+		BidVersionOverflow,
+
+		/// The bids of a cancelled RFP with this id are still being removed
+		RFPCleanupPending,
+
+		/// There is no cancelled RFP with this id left to clean up
+		NoRFPCleanupPending,
+
+		/// Withdrawing or rejecting a bid on an RFP that
+		/// has already accepted a bid, or expired
+		RFPAlreadyDecided,
+
+		/// An RFP must have at least one lot, and no more than `MaxLotsPerRFP`
+		InvalidRFPLots,
+
+		/// The RFP has no lot with this number
+		NonExistentLot,
+
+		/// The lot has already been awarded or left unawarded
+		LotAlreadyDecided,
+
+		/// The lots of an RFP can't be changed once any lot is decided
+		LotsAlreadyDecided,
+
+		/// The minimum of an RFP's budget is above its maximum
+		InvalidBudgetRange,
+
+		/// The bid amount is outside the RFP's budget range
+		BidOutsideBudget,
+
+		/// The bidder doesn't hold a credential the RFP requires
+		MissingRequiredCredential,
+
+		/// The bid names an account other than the bidder as its owner
+		BidOwnerMismatch,
+
+		/// An RFP with a budget escrow must have a maximum budget
+		BudgetEscrowWithoutBudget,
+
+		/// The owner can't cover the budget of the escrow created for the RFP
+		InsufficientBudgetFunds,
+
+		/// The budget escrow, maximum budget and asset of an RFP can't change once locked
+		BudgetEscrowLocked,
+
+		/// The accepted bid is more than what is left of the RFP's locked budget
+		BudgetExceeded,
+
+		/// The owner of an RFP can't be changed by updating it
+		RFPOwnerChanged,
+
+		/// The status of an RFP can't be changed by updating it
+		RFPStatusChanged,
+
+		/// An RFP can only be created with the account creating it as its owner
+		RFPOwnerMismatch,
+
+		/// The bidding mode, bid bond, visibility and invite credential of an RFP
+		/// can't change once it has bids, or is no longer accepting them
+		BiddingTermsLocked,
+
+		/// The RFP has already been amended the maximum number of times
+		RFPAmendmentOverflow,
+
+		/// The bidder has neither acknowledged the latest amendment
+		/// of the RFP, nor updated their bid since
+		AmendmentNotAcknowledged,
+
+		/// Someone other than the bid owner attempted
+		/// to acknowledge an amendment for the bid
+		UnauthorizedAcknowledgementOfAmendment,
+
+		/// Bid on a private RFP without an invitation or its invite credential
+		BidderNotInvited,
+
+		/// The account has already been invited to bid on the RFP
+		AlreadyInvited,
+
+		/// The RFP already has the maximum number of invitees
+		TooManyInvitees,
+
+		/// The account hasn't been invited to bid on the RFP
+		NotAnInvitee,
+
+		/// An RFP awarded to the top-scoring bid must have evaluation criteria
+		InvalidEvaluationCriteria,
+
+		/// The committee and evaluation terms of an RFP can't
+		/// change once its bids are being evaluated
+		EvaluationLocked,
+
+		/// The RFP's award rule needs an evaluation committee, which it doesn't have
+		NoEvaluationCommittee,
+
+		/// Someone other than a member of the RFP's evaluation
+		/// committee attempted to evaluate a bid
+		NotCommitteeMember,
+
+		/// A bid must be given exactly one score per evaluation criterion
+		ScoresDontMatchCriteria,
+
+		/// A score is above the highest score a bid can be given on a criterion
+		ScoreTooHigh,
+
+		/// An account can only be a member of an evaluation committee once
+		DuplicateCommitteeMember,
+
+		/// The committee member has already approved the bid
+		BidAlreadyApproved,
+
+		/// The RFP is awarded to the top-scoring bid, and this bid isn't it
+		NotTopScoringBid,
+
+		/// The RFP only awards bids approved by a majority of its committee
+		CommitteeApprovalRequired,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(2, 2);
+			for (rfp_owner, rfp_id) in <RFPsClosingAt<T>>::take(now) {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				Self::close_bidding(now, rfp_owner, rfp_id);
+			}
+			for (rfp_owner, rfp_id) in <RFPsExpiringAt<T>>::take(now) {
+				weight = weight.saturating_add(Self::expire_rfp(now, rfp_owner, rfp_id));
+			}
+			weight
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>()
+				.saturating_add(crate::migrations::v2::migrate::<T>())
+				.saturating_add(crate::migrations::v3::migrate::<T>())
+				.saturating_add(crate::migrations::v4::migrate::<T>())
+				.saturating_add(crate::migrations::v5::migrate::<T>())
+				.saturating_add(crate::migrations::v6::migrate::<T>())
+				.saturating_add(crate::migrations::v7::migrate::<T>())
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// A dispatchable to create an RFP. The caller must be the RFP's owner,
+		/// and the RFP always starts out accepting bids
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 6).ref_time())]
+		pub fn create_rfp(
+			origin: OriginFor<T>, 
+			rfp_id: T::RFPId,
 			rfp_details: RFPDetails<T>
 		) -> DispatchResult {
 			let rfp_owner = ensure_signed(origin)?;
-			
-			// Assert rfp doesn't already exist
-			let rfp_exists = <RFPs<T>>::get(
-				&rfp_owner,
-				&rfp_id,
-			);
-
-			ensure!(
-				rfp_exists.is_none(),
-				Error::<T>::RFPAlreadyExists
-			);
+			Self::do_create_rfp(rfp_owner, rfp_id, rfp_details)
+		}
 
-			// Insert the RFP details into storage
-			<RFPs<T>>::insert(
-				&rfp_owner, 
-				&rfp_id,
-				rfp_details
-			);
-			let rfps_to_bids: BoundedVec<
-				T::BidId, ConstU32<{VEC_LIMIT}>
-			> = BoundedVec::<
-					T::BidId, ConstU32<{VEC_LIMIT}>
-				>::default();
-			<RFPToBids<T>>::insert::<&T::RFPId, BoundedVec<
-				T::BidId, ConstU32<{VEC_LIMIT}>
-			>>(
-				&rfp_id,
-				rfps_to_bids.into()
-			);
-			Self::deposit_event(Event::CreateRFP(rfp_owner, rfp_id));
-			Ok(())
+		/// A dispatchable to create an RFP under the next
+		/// RFP id allocated by the chain
+		#[pallet::weight(
+			10_000 + T::DbWeight::get()
+				.reads_writes(4 + 2 * MAX_ID_PROBES as u64, 7)
+				.ref_time()
+		)]
+		pub fn create_rfp_with_auto_id(
+			origin: OriginFor<T>, 
+			rfp_details: RFPDetails<T>
+		) -> DispatchResult {
+			let rfp_owner = ensure_signed(origin)?;
+			let rfp_id = Self::allocate_rfp_id(&rfp_owner)?;
+			Self::do_create_rfp(rfp_owner, rfp_id, rfp_details)
 		}
 
-		/// A dispatchable to modify an existing RFP
-		 #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
+		/// A dispatchable to amend an existing RFP. Every update is numbered,
+		/// and the RFP's previous CID kept with the amendment. The owner and
+		/// status of the RFP can't be changed this way, nor can its bidding
+		/// mode, bond, visibility and invite credential once it has bids
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7, 8).ref_time())]
 		pub fn update_rfp(
 			origin: OriginFor<T>, 
 			rfp_id: T::RFPId, 
@@ -317,54 +1186,173 @@ pub mod pallet {
 			let rfp_owner = ensure_signed(origin)?;
 
 			// Update the stored value with the new details
-			<RFPs<T>>::try_mutate(
+			let amendment = <RFPs<T>>::try_mutate(
 				&rfp_owner,
 				&rfp_id,
-				| maybe_rfp_details | -> DispatchResult {
+				| maybe_rfp_details | -> Result<u32, DispatchError> {
 					let rfp_details = 
 						maybe_rfp_details.as_mut()
 							.ok_or(
 								<Error<T>>::UpdatingNonExistentRFP
 							)?;
+					ensure!(
+						new_rfp_details.rfp_owner == rfp_details.rfp_owner,
+						<Error<T>>::RFPOwnerChanged
+					);
+					ensure!(
+						new_rfp_details.rfp_status == rfp_details.rfp_status,
+						<Error<T>>::RFPStatusChanged
+					);
+					Self::ensure_valid_lots(&new_rfp_details)?;
+					Self::ensure_valid_requirements(&new_rfp_details)?;
+					Self::ensure_valid_evaluation(&new_rfp_details)?;
+					// Bids are committed, bonded and checked against the
+					// terms the RFP had when they were placed
+					ensure!(
+						(new_rfp_details.bidding_mode == rfp_details.bidding_mode &&
+							new_rfp_details.bid_bond == rfp_details.bid_bond &&
+							new_rfp_details.visibility == rfp_details.visibility &&
+							new_rfp_details.invite_credential == rfp_details.invite_credential) ||
+						(rfp_details.rfp_status == RFPStatus::AcceptingBids &&
+							<RFPToBids<T>>::get(&rfp_owner, &rfp_id)
+								.map_or(true, |bids| bids.is_empty())),
+						<Error<T>>::BiddingTermsLocked
+					);
+					ensure!(
+						new_rfp_details.evaluation == rfp_details.evaluation ||
+							!Self::evaluation_started(&rfp_owner, &rfp_id),
+						<Error<T>>::EvaluationLocked
+					);
+					ensure!(
+						new_rfp_details.lots == rfp_details.lots ||
+							<RFPLotsDecided<T>>::get(&rfp_owner, &rfp_id) == 0,
+						<Error<T>>::LotsAlreadyDecided
+					);
+					ensure!(
+						new_rfp_details.budget_escrow == rfp_details.budget_escrow &&
+							(rfp_details.budget_escrow.is_none() ||
+								(new_rfp_details.requirements.budget_max ==
+									rfp_details.requirements.budget_max &&
+								new_rfp_details.requirements.asset_id ==
+									rfp_details.requirements.asset_id)),
+						<Error<T>>::BudgetEscrowLocked
+					);
+					Self::schedule_rfp_deadlines(
+						&rfp_owner,
+						&rfp_id,
+						&new_rfp_details,
+						Some(&*rfp_details)
+					)?;
+					let amendment = <RFPAmendmentCount<T>>::get(&rfp_owner, &rfp_id)
+						.checked_add(1)
+						.filter(|amendment| *amendment <= T::MaxRFPAmendments::get())
+						.ok_or(<Error<T>>::RFPAmendmentOverflow)?;
+					<RFPAmendmentCount<T>>::insert(&rfp_owner, &rfp_id, amendment);
+					<RFPAmendments<T>>::insert(
+						(&rfp_owner, &rfp_id, amendment),
+						RFPAmendment {
+							previous_ipfs_hash: rfp_details.ipfs_hash.clone(),
+							amended_at: <frame_system::Pallet<T>>::block_number(),
+						}
+					);
 					*rfp_details = new_rfp_details;
-					Ok(())
+					Ok(amendment)
 				}
 			)?;
 			Self::deposit_event(
 				Event::UpdateRFP(
 					rfp_owner, 
-					rfp_id
+					rfp_id,
+					amendment
 				)
 			);
 			Ok(())
 		}
 		
 		/// A dispatchable to cancel an existing RFP
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time()
+				+ Pallet::<T>::clean_up_weight()
+		)]
 		pub fn cancel_rfp(origin: OriginFor<T>, rfp_id: T::RFPId) -> DispatchResult {
 			let rfp_owner = ensure_signed(origin)?;
+			let rfp_details = <RFPs<T>>::get(
+				&rfp_owner,
+				&rfp_id
+			).ok_or(<Error<T>>::CancelingNonExistentRFP)?;
 			ensure!(
-				<RFPs<T>>::contains_key(
-					&rfp_owner,
-					&rfp_id
-				),
-				<Error<T>>::CancelingNonExistentRFP
+				rfp_details.rfp_status != RFPStatus::AcceptedBid &&
+					rfp_details.rfp_status != RFPStatus::PartiallyAwarded,
+				<Error<T>>::BidAlreadyAccepted
 			);
 
+			Self::release_budget(&rfp_owner, &rfp_id, &rfp_details);
 			<RFPs<T>>::remove(&rfp_owner, &rfp_id);
-			// TODO: Delete bids associated with this RFP as well
+			<CancelledRFPs<T>>::insert(&rfp_owner, &rfp_id, ());
+			Self::clean_up_rfp(&rfp_owner, &rfp_id);
 			Self::deposit_event(Event::CancelRFP(rfp_owner, rfp_id));
 			Ok(())
 		}
 
+		/// A dispatchable to remove the next batch of bids of a cancelled RFP,
+		/// for when it had too many bids to remove when it was cancelled
+		#[pallet::weight(10_000 + Pallet::<T>::clean_up_weight())]
+		pub fn clean_up_cancelled_rfp(
+			origin: OriginFor<T>,
+			rfp_owner: T::AccountId,
+			rfp_id: T::RFPId
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(
+				<CancelledRFPs<T>>::contains_key(&rfp_owner, &rfp_id),
+				<Error<T>>::NoRFPCleanupPending
+			);
+			Self::clean_up_rfp(&rfp_owner, &rfp_id);
+			Ok(())
+		}
+
 		/// A dispatchable to Bid on an RFP
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3).ref_time())]
 		pub fn bid_on_rfp(
 			origin: OriginFor<T>, 
 			rfp_owner: T::AccountId, 
 			rfp_id: T::RFPId, 
 			bid_id: T::BidId,
 			bid_details: BidDetails<T>
+		) -> DispatchResult {
+			let bid_owner = ensure_signed(origin)?;
+			Self::do_bid_on_rfp(bid_owner, rfp_owner, rfp_id, bid_id, bid_details)
+		}
+
+		/// A dispatchable to Bid on an RFP under the next
+		/// bid id allocated by the chain
+		#[pallet::weight(
+			10_000 + T::DbWeight::get()
+				.reads_writes(3 + 2 * MAX_ID_PROBES as u64, 4)
+				.ref_time()
+		)]
+		pub fn bid_on_rfp_with_auto_id(
+			origin: OriginFor<T>, 
+			rfp_owner: T::AccountId, 
+			rfp_id: T::RFPId, 
+			bid_details: BidDetails<T>
+		) -> DispatchResult {
+			let bid_owner = ensure_signed(origin)?;
+			let bid_id = Self::allocate_bid_id()?;
+			Self::do_bid_on_rfp(bid_owner, rfp_owner, rfp_id, bid_id, bid_details)
+		}
+
+		/// A dispatchable to commit to a sealed bid on an RFP, keeping the bid
+		/// itself private until bidding closes. The commitment is the hash of
+		/// `(bid_owner, rfp_owner, rfp_id, bid_id, bid_amount, ipfs_hash, salt)`,
+		/// so it can't be copied by another bidder, or to another bid
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 3).ref_time())]
+		pub fn commit_sealed_bid(
+			origin: OriginFor<T>, 
+			rfp_owner: T::AccountId, 
+			rfp_id: T::RFPId, 
+			bid_id: T::BidId,
+			commitment: T::Hash
 		) -> DispatchResult {
 			let bid_owner = ensure_signed(origin)?;
 			let rfp_details = <RFPs<T>>::get(
@@ -372,64 +1360,228 @@ pub mod pallet {
 				&rfp_id
 			).ok_or(<Error<T>>::NonExistentRFP)?;
 			ensure!(
-				rfp_details.rfp_status == RFPStatus::AcceptingBids,
-				<Error<T>>::RFPNotAcceptingBids
+				rfp_details.bidding_mode == BiddingMode::Sealed,
+				<Error<T>>::RFPDoesNotTakeSealedBids
 			);
+			Self::ensure_bidding_open(&rfp_details)?;
+			Self::ensure_credentials(&rfp_details, &bid_owner)?;
+			Self::ensure_invited(&rfp_owner, &rfp_id, &rfp_details, &bid_owner)?;
 			ensure!(
-				!<AllBids<T>>::contains_key(
-					&bid_id
-				),
+				!<AllBids<T>>::contains_key(&bid_id) &&
+					!<SealedBids<T>>::contains_key(&bid_id),
 				<Error<T>>::BidAlreadyExists
 			);
+			Self::reserve_bid_bond(&bid_owner, &rfp_owner, &rfp_id, &bid_id, rfp_details.bid_bond)?;
+			<BidToRFP<T>>::insert(&bid_id, (&rfp_owner, rfp_id));
+			// Sealed bids are listed with the RFP's bids right away,
+			// but can't be shortlisted or accepted until revealed
+			Self::add_bid_to_rfp(&rfp_owner, &rfp_id, bid_id)?;
+			Self::acknowledge_amendment(&rfp_owner, &rfp_id, &bid_id);
+			<RFPUnrevealedBids<T>>::try_append(&rfp_owner, &rfp_id, bid_id)
+				.ok()
+				.ok_or(<Error<T>>::TooManySealedBids)?;
+			<SealedBids<T>>::insert(
+				&bid_id,
+				SealedBid {
+					bid_owner: bid_owner.clone(),
+					rfp_owner,
+					rfp_id,
+					commitment,
+				}
+			);
+			Self::deposit_event(Event::SealedBidCommitted(bid_owner, rfp_id, bid_id));
+			Ok(())
+		}
+
+		/// A dispatchable to reveal a sealed bid during the reveal period that follows
+		/// the close of bidding on the RFP. A bid that doesn't match its commitment,
+		/// or is outside the RFP's budget, is disqualified and its bond slashed
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 9).ref_time())]
+		pub fn reveal_bid(
+			origin: OriginFor<T>, 
+			bid_id: T::BidId,
+			mut bid_details: BidDetails<T>,
+			salt: [u8; 32]
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let sealed_bid = <SealedBids<T>>::get(&bid_id)
+				.ok_or(<Error<T>>::NonExistentSealedBid)?;
+			ensure!(
+				who == sealed_bid.bid_owner && who == bid_details.bid_owner,
+				<Error<T>>::UnauthorizedRevealOfBid
+			);
+			let rfp_details = <RFPs<T>>::get(
+				&sealed_bid.rfp_owner,
+				&sealed_bid.rfp_id
+			).ok_or(<Error<T>>::NonExistentRFP)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				now >= rfp_details.bid_close &&
+					now < Self::reveal_period_end(&rfp_details) &&
+					rfp_details.rfp_status == RFPStatus::NotAcceptingNewBids,
+				<Error<T>>::NotRevealPeriod
+			);
+
+			<RFPUnrevealedBids<T>>::mutate(&sealed_bid.rfp_owner, &sealed_bid.rfp_id, | unrevealed_bids | {
+				unrevealed_bids.retain(|id| *id != bid_id);
+			});
+			let revealed_commitment = T::Hashing::hash_of(
+				&(
+					&who,
+					&sealed_bid.rfp_owner,
+					&sealed_bid.rfp_id,
+					&bid_id,
+					bid_details.bid_amount,
+					&bid_details.ipfs_hash,
+					salt,
+				)
+			);
+			if revealed_commitment != sealed_bid.commitment ||
+				Self::ensure_within_budget(&rfp_details, bid_details.bid_amount).is_err() {
+				Self::disqualify_sealed_bid(&sealed_bid.rfp_owner, &sealed_bid.rfp_id, &bid_id);
+				Self::deposit_event(Event::BidDisqualified(who, sealed_bid.rfp_id, bid_id));
+				return Ok(())
+			}
+			<SealedBids<T>>::remove(&bid_id);
+
+			bid_details.bid_status = BidStatus::Submitted;
+			bid_details.version = 0;
+			Self::record_bid_terms(&bid_id, &bid_details);
 			<AllBids<T>>::insert(
 				&bid_id,
 				bid_details
 			);
-			<RFPToBids<T>>::try_mutate(
-				&rfp_id,
-				| maybe_bids_for_rfp | -> DispatchResult {
-					let bids_for_rfp = 
-						maybe_bids_for_rfp.as_mut()
-							.ok_or(
-								<Error<T>>::NoBidsForRFPFound
-							)?;
-					bids_for_rfp
-						.try_push(bid_id)
-						.ok()
-						.ok_or(
-							<Error<T>>::TooManyBids
-						)?;
+			Self::deposit_event(Event::BidRevealed(who, sealed_bid.rfp_id, bid_id));
+			Ok(())
+		}
+
+		/// A dispatchable to return a bid's bond and storage deposit to the bidder
+		/// once the RFP is decided. Only the accepted bid is kept in storage
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(4, 4).ref_time()
+				+ T::DbWeight::get().writes(T::MaxBidVersions::get() as u64).ref_time()
+		)]
+		pub fn release_bid_bond(
+			origin: OriginFor<T>, 
+			bid_id: T::BidId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let bid_bond = <BidBonds<T>>::get(&bid_id)
+				.ok_or(<Error<T>>::NonExistentBidBond)?;
+			ensure!(
+				who == bid_bond.bidder,
+				<Error<T>>::UnauthorizedReleaseOfBidBond
+			);
+			// A cancelled RFP no longer holds its bidders' funds either
+			let maybe_rfp_details = <RFPs<T>>::get(&bid_bond.rfp_owner, &bid_bond.rfp_id);
+			let rfp_decided = maybe_rfp_details.as_ref()
+				.map_or(true, |rfp_details| {
+					rfp_details.rfp_status == RFPStatus::AcceptedBid ||
+						rfp_details.rfp_status == RFPStatus::Expired
+				});
+			ensure!(rfp_decided, <Error<T>>::RFPNotDecided);
+
+			// The bonds of sealed bids that were never revealed are slashed when
+			// the RFP is decided, so only the bids of a cancelled RFP are left sealed
+			<SealedBids<T>>::remove(&bid_id);
+			Self::settle_bid_bond(&bid_id, false);
+			let accepted = <AllBids<T>>::get(&bid_id)
+				.map_or(false, |bid_details| bid_details.bid_status == BidStatus::Accepted);
+			if !accepted {
+				Self::remove_bid(&bid_bond.rfp_owner, &bid_bond.rfp_id, &bid_id);
+			}
+
+			Self::deposit_event(Event::BidBondReleased(who, bid_bond.rfp_id, bid_id));
+			Ok(())
+		}
+
+		/// A dispatchable to withdraw a bid on an RFP that hasn't been decided yet.
+		/// The bid's bond is slashed, and its storage deposit returned along
+		/// with the bid's storage
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(4, 8).ref_time()
+				+ T::DbWeight::get().writes(T::MaxBidVersions::get() as u64).ref_time()
+		)]
+		pub fn withdraw_bid(
+			origin: OriginFor<T>, 
+			bid_id: T::BidId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let rfp_id = Self::close_bid(
+				&bid_id,
+				true,
+				| bid_details, _ | {
+					ensure!(
+						who == bid_details.bid_owner,
+						<Error<T>>::UnauthorizedWithdrawalOfBid
+					);
 					Ok(())
 				}
 			)?;
+			Self::deposit_event(Event::BidWithdrawn(who, rfp_id, bid_id));
+			Ok(())
+		}
 
-			Self::deposit_event(Event::BidOnRFP(bid_owner, rfp_id, bid_id));
+		/// A dispatchable for the RFP owner to reject a bid on an RFP that
+		/// hasn't been decided yet. The bid's bond and storage deposit are returned,
+		/// and the bid's storage removed
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(4, 8).ref_time()
+				+ T::DbWeight::get().writes(T::MaxBidVersions::get() as u64).ref_time()
+		)]
+		pub fn reject_bid(
+			origin: OriginFor<T>, 
+			bid_id: T::BidId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let rfp_id = Self::close_bid(
+				&bid_id,
+				false,
+				| _, rfp_owner | {
+					ensure!(
+						who == *rfp_owner,
+						<Error<T>>::UnauthorizedRejectionOfBid
+					);
+					Ok(())
+				}
+			)?;
+			Self::deposit_event(Event::BidRejected(who, rfp_id, bid_id));
 			Ok(())
 		}
 
-		/// A dispatchable to create a shortlist of bids
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
+		/// A dispatchable to create a shortlist of bids. A bid can only be
+		/// shortlisted once its bidder has caught up with the RFP's latest amendment,
+		/// and the reveal period of a sealed RFP has ended
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2).ref_time())]
 		pub fn shortlist_bid(
 			origin: OriginFor<T>, 
 			rfp_id: T::RFPId, 
 			bid_id: T::BidId,
 		) -> DispatchResult {
 			let rfp_owner = ensure_signed(origin)?;
-			let maybe_rfp_details = <RFPs<T>>::get(
+			let rfp_details = <RFPs<T>>::get(
 				&rfp_owner,
 				&rfp_id,
-			);
-			ensure!(maybe_rfp_details.is_some(),
+			).ok_or(
 				Error::<T>::NonExistentRFP
-			);
-			let maybe_bid_details = <AllBids<T>>::get(
+			)?;
+			Self::ensure_reveal_period_over(&rfp_details)?;
+			let bid_details = <AllBids<T>>::get(
 				&bid_id
-			);
-			ensure!(maybe_bid_details.is_some(),
+			).ok_or(
 				Error::<T>::ShortlistingNonExistentBid
+			)?;
+			ensure!(
+				bid_details.bid_status != BidStatus::Shortlisted,
+				Error::<T>::BidAlreadyShortlisted
+			);
+			ensure!(
+				bid_details.bid_status == BidStatus::Submitted,
+				Error::<T>::BidNotActive
 			);
 
 			let all_bids_for_rfp = <RFPToBids<T>>::get(
+				&rfp_owner,
 				&rfp_id,
 			).ok_or(
 				Error::<T>::NoBidsForRFP
@@ -439,11 +1591,14 @@ pub mod pallet {
 				all_bids_for_rfp.contains(&bid_id),
 				Error::<T>::NoSuchBidForRFP
 			);
+			Self::ensure_amendment_acknowledged(&rfp_owner, &rfp_id, &bid_id)?;
 			let maybe_shortlisted_bids = <RFPToShortlistedBids<T>>::get(
+				&rfp_owner,
 				&rfp_id,
 			);
 			if let Some(mut _shortlisted_bids) = maybe_shortlisted_bids {
 				<RFPToShortlistedBids<T>>::mutate(
+					&rfp_owner,
 					&rfp_id,
 					| maybe_shortlisted_bids | -> DispatchResult {
 						let shortlisted_bids = maybe_shortlisted_bids.as_mut().unwrap();
@@ -464,13 +1619,17 @@ pub mod pallet {
 					.ok_or(
 						<Error<T>>::TooManyBids
 					)?;
-				<RFPToShortlistedBids<T>>::insert::<&T::RFPId, BoundedVec<
-					T::BidId, ConstU32<{VEC_LIMIT}>
-				>>(
+				<RFPToShortlistedBids<T>>::insert(
+					&rfp_owner,
 					&rfp_id,
-					shortlisted_bids.into()
+					shortlisted_bids
 				);
 			}
+			<AllBids<T>>::mutate(&bid_id, | maybe_bid_details | {
+				if let Some(bid_details) = maybe_bid_details {
+					bid_details.bid_status = BidStatus::Shortlisted;
+				}
+			});
 			Self::deposit_event(
 				Event::ShortlistBid(
 					rfp_owner,
@@ -481,18 +1640,21 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// A dispatchable to update a bid on an RFP
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
+		/// A dispatchable to update the CID and amount of a bid on an RFP,
+		/// while the RFP is accepting bids. Every update creates a new
+		/// version of the bid, whose terms are kept in the bid history
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 2).ref_time())]
 		pub fn update_rfp_bid(
 			origin: OriginFor<T>, 
 			rfp_id: T::RFPId, 
 			bid_id: T::BidId,
-			updated_bid_details: BidDetails<T>
+			ipfs_hash: T::Cid,
+			bid_amount: BalanceOf<T>
 		) -> DispatchResult {
 			let updater_id = ensure_signed(origin)?;
-			<AllBids<T>>::try_mutate(
+			let version = <AllBids<T>>::try_mutate(
 				&bid_id,
-				| maybe_bid_details | -> DispatchResult {
+				| maybe_bid_details | -> Result<u32, DispatchError> {
 					let bid_details = 
 						maybe_bid_details.as_mut()
 							.ok_or(
@@ -502,36 +1664,102 @@ pub mod pallet {
 						updater_id == bid_details.bid_owner,
 						<Error<T>>::UnauthorizedUpdateOfBid,
 					);
-					*bid_details = updated_bid_details;
-					Ok(())
+					let (rfp_owner, _) = <BidToRFP<T>>::get(&bid_id)
+						.ok_or(<Error<T>>::NonExistentRFP)?;
+					let all_bids_for_rfp = <RFPToBids<T>>::get(
+						&rfp_owner,
+						&rfp_id,
+					).ok_or(
+						Error::<T>::NoBidsForRFP
+					)?;
+					ensure!(
+						all_bids_for_rfp.contains(&bid_id),
+						Error::<T>::NoSuchBidForRFP
+					);
+					ensure!(
+						bid_details.bid_status == BidStatus::Submitted,
+						<Error<T>>::BidNoLongerUpdatable,
+					);
+					let rfp_details = <RFPs<T>>::get(&rfp_owner, &rfp_id)
+						.ok_or(<Error<T>>::NonExistentRFP)?;
+					Self::ensure_bidding_open(&rfp_details)?;
+					Self::ensure_within_budget(&rfp_details, bid_amount)?;
+
+					bid_details.ipfs_hash = ipfs_hash;
+					bid_details.bid_amount = bid_amount;
+					bid_details.version = bid_details.version.checked_add(1)
+						.filter(|version| *version < T::MaxBidVersions::get())
+						.ok_or(<Error<T>>::BidVersionOverflow)?;
+					Self::record_bid_terms(&bid_id, bid_details);
+					// Updating a bid takes the RFP's latest amendment into account
+					Self::acknowledge_amendment(&rfp_owner, &rfp_id, &bid_id);
+					Ok(bid_details.version)
 				}
 			)?;
 			Self::deposit_event(
 				Event::UpdateRFPBid(
 					updater_id, 
 					rfp_id, 
-					bid_id
+					bid_id,
+					version
 				)
 			);
 			Ok(())
 		}
 
-		/// A dispatchable to accept a bid on an RFP
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2).ref_time())]
-		pub fn accept_rfp_bid(
+		/// A dispatchable for a bidder to acknowledge the latest amendment of the
+		/// RFP they bid on, keeping their bid as it is
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1).ref_time())]
+		pub fn acknowledge_rfp_amendment(
 			origin: OriginFor<T>, 
-			rfp_id: T::RFPId, 
 			bid_id: T::BidId,
-			payment_details: pallet_payments::PaymentDetails::<T>
 		) -> DispatchResult {
-			let rfp_owner = ensure_signed(origin.clone())?;
-
-			<RFPs<T>>::try_mutate(
-				&rfp_owner,
-				&rfp_id,
-				| maybe_rfp_details | -> DispatchResult {
-					let rfp_details = 
-						maybe_rfp_details.as_mut()
+			let who = ensure_signed(origin)?;
+			let bid_details = <AllBids<T>>::get(&bid_id)
+				.ok_or(<Error<T>>::NonExistentBid)?;
+			ensure!(
+				who == bid_details.bid_owner,
+				<Error<T>>::UnauthorizedAcknowledgementOfAmendment
+			);
+			ensure!(
+				bid_details.bid_status == BidStatus::Submitted ||
+					bid_details.bid_status == BidStatus::Shortlisted,
+				<Error<T>>::BidNotActive
+			);
+			let (rfp_owner, rfp_id) = <BidToRFP<T>>::get(&bid_id)
+				.ok_or(<Error<T>>::NonExistentRFP)?;
+			let amendment = Self::acknowledge_amendment(&rfp_owner, &rfp_id, &bid_id);
+			Self::deposit_event(Event::RFPAmendmentAcknowledged(who, rfp_id, bid_id, amendment));
+			Ok(())
+		}
+
+		/// A dispatchable to accept a bid on an RFP, awarding it one lot of the RFP.
+		/// The RFP is complete once every lot is awarded or left unawarded.
+		/// The agreement paying the bid amount is built from the bid.
+		/// The bid must also satisfy the RFP's award rule
+		#[pallet::weight(
+			10_000 + T::DbWeight::get()
+				.reads_writes(11 + 3 * pallet_payments::MAX_ID_PROBES as u64, 7)
+				.ref_time()
+				+ T::DbWeight::get().reads_writes(4, 8).ref_time()
+					.saturating_mul(T::MaxSealedBidsPerRFP::get() as u64)
+				+ T::DbWeight::get().reads(2).ref_time()
+					.saturating_mul(T::MaxBidsPerRFP::get() as u64)
+		)]
+		pub fn accept_rfp_bid(
+			origin: OriginFor<T>, 
+			rfp_id: T::RFPId, 
+			bid_id: T::BidId,
+			lot: u32,
+		) -> DispatchResult {
+			let rfp_owner = ensure_signed(origin.clone())?;
+
+			let completed = <RFPs<T>>::try_mutate(
+				&rfp_owner,
+				&rfp_id,
+				| maybe_rfp_details | -> Result<bool, DispatchError> {
+					let rfp_details = 
+						maybe_rfp_details.as_mut()
 							.ok_or(
 								<Error<T>>::NonExistentRFP
 							)?;
@@ -539,8 +1767,15 @@ pub mod pallet {
 						rfp_details.rfp_status != RFPStatus::AcceptedBid,
 						<Error<T>>::BidAlreadyAccepted
 					);
+					ensure!(
+						rfp_details.rfp_status != RFPStatus::Expired,
+						<Error<T>>::RFPHasExpired
+					);
+					Self::ensure_reveal_period_over(rfp_details)?;
+					Self::ensure_lot_undecided(&rfp_owner, &rfp_id, rfp_details, lot)?;
 
 					let shortlisted_bids = <RFPToShortlistedBids<T>>::get(
+						&rfp_owner,
 						&rfp_id
 					);
 
@@ -555,6 +1790,7 @@ pub mod pallet {
 					} else {
 						// Otherwise, just make sure the bid exists
 						let all_bids_for_rfp = <RFPToBids<T>>::get(
+							&rfp_owner,
 							&rfp_id,
 						).ok_or(
 							Error::<T>::NoBidsForRFP
@@ -564,33 +1800,1200 @@ pub mod pallet {
 							Error::<T>::NoSuchBidForRFP
 						);
 					}
-		
+
+					// The payment agreement is only proposed here,
+					// the winning bidder countersigns it in the payments pallet
+					let bid_details = <AllBids<T>>::get(&bid_id).ok_or(
+						Error::<T>::NoSuchBidForRFP
+					)?;
 					ensure!(
-						<RFPToAcceptedBid<T>>::get(&rfp_id).is_none(),
-						Error::<T>::BidAlreadyAccepted
+						bid_details.bid_status == BidStatus::Submitted ||
+							bid_details.bid_status == BidStatus::Shortlisted,
+						Error::<T>::BidNotActive
 					);
+					Self::ensure_amendment_acknowledged(&rfp_owner, &rfp_id, &bid_id)?;
+					Self::ensure_award_rule(&rfp_owner, &rfp_id, rfp_details, &bid_id)?;
+					let payment_details = Self::bid_payment(
+						&rfp_owner,
+						&rfp_id,
+						rfp_details,
+						&bid_details
+					)?;
+
+					// The bid's share of the locked budget is handed back to the
+					// escrow, which reserves it again for an escrow-sourced agreement
+					if let Some(escrow_id) = &rfp_details.budget_escrow {
+						<RFPBudgetReserved<T>>::try_mutate(
+							&rfp_owner,
+							&rfp_id,
+							| reserved | -> DispatchResult {
+								*reserved = reserved.checked_sub(&bid_details.bid_amount)
+									.ok_or(<Error<T>>::BudgetExceeded)?;
+								Ok(())
+							}
+						)?;
+						<T as pallet_payments::Config>::Escrow::release_funds(
+							escrow_id,
+							rfp_details.requirements.asset_id,
+							&Self::budget_reservation(&rfp_owner, &rfp_id),
+							bid_details.bid_amount,
+						)?;
+					}
 		
 					<pallet_payments::Pallet<T>>::initialize_payment(
 						origin,
 						payment_details
-					).ok().ok_or(Error::<T>::PaymentInitializationFailed)?;
+					)?;
 
 					<RFPToAcceptedBid<T>>::insert(
-						&rfp_id,
+						(&rfp_owner, &rfp_id, lot),
 						&bid_id,
 					);
-					rfp_details.rfp_status = RFPStatus::AcceptedBid;
-					Ok(())
+					<AllBids<T>>::mutate(&bid_id, | maybe_bid_details | {
+						if let Some(bid_details) = maybe_bid_details {
+							bid_details.bid_status = BidStatus::Accepted;
+						}
+					});
+					let completed = Self::decide_lot(&rfp_owner, &rfp_id, rfp_details, true);
+					if completed {
+						Self::release_budget(&rfp_owner, &rfp_id, rfp_details);
+						Self::slash_unrevealed_bids(&rfp_owner, &rfp_id);
+					}
+					Ok(completed)
 				}
 			)?;
 
 			Self::deposit_event(
 				Event::AcceptRFPBid(
-					rfp_owner, 
+					rfp_owner.clone(), 
 					rfp_id,
 					bid_id,
+					lot,
 				)
 			);
+			if completed {
+				Self::deposit_event(Event::RFPAwardsCompleted(rfp_owner, rfp_id));
+			}
+			Ok(())
+		}
+
+		/// A dispatchable for the RFP owner to invite an account to bid on their RFP,
+		/// which only matters once the RFP is private
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2).ref_time())]
+		pub fn add_rfp_invitee(
+			origin: OriginFor<T>, 
+			rfp_id: T::RFPId, 
+			invitee: T::AccountId,
+		) -> DispatchResult {
+			let rfp_owner = ensure_signed(origin)?;
+			ensure!(
+				<RFPs<T>>::contains_key(&rfp_owner, &rfp_id),
+				<Error<T>>::NonExistentRFP
+			);
+			ensure!(
+				!<RFPInvitees<T>>::contains_key((&rfp_owner, &rfp_id, &invitee)),
+				<Error<T>>::AlreadyInvited
+			);
+			<RFPInviteeCount<T>>::try_mutate(&rfp_owner, &rfp_id, | invitee_count | -> DispatchResult {
+				*invitee_count = invitee_count.checked_add(1)
+					.filter(|count| *count <= T::MaxRFPInvitees::get())
+					.ok_or(<Error<T>>::TooManyInvitees)?;
+				Ok(())
+			})?;
+			<RFPInvitees<T>>::insert((&rfp_owner, &rfp_id, &invitee), ());
+			Self::deposit_event(Event::BidderInvited(rfp_owner, rfp_id, invitee));
+			Ok(())
+		}
+
+		/// A dispatchable for the RFP owner to withdraw an invitation to bid on
+		/// their RFP. Bids the invitee already made are kept
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2).ref_time())]
+		pub fn remove_rfp_invitee(
+			origin: OriginFor<T>, 
+			rfp_id: T::RFPId, 
+			invitee: T::AccountId,
+		) -> DispatchResult {
+			let rfp_owner = ensure_signed(origin)?;
+			ensure!(
+				<RFPInvitees<T>>::contains_key((&rfp_owner, &rfp_id, &invitee)),
+				<Error<T>>::NotAnInvitee
+			);
+			<RFPInvitees<T>>::remove((&rfp_owner, &rfp_id, &invitee));
+			<RFPInviteeCount<T>>::mutate(&rfp_owner, &rfp_id, | invitee_count | {
+				*invitee_count = invitee_count.saturating_sub(1);
+			});
+			Self::deposit_event(Event::BidderUninvited(rfp_owner, rfp_id, invitee));
+			Ok(())
+		}
+
+		/// A dispatchable for the RFP owner to set the committee that evaluates the
+		/// bids on their RFP, or to remove it by setting no members. The committee
+		/// can't be changed once it has started evaluating bids
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1).ref_time())]
+		pub fn set_evaluation_committee(
+			origin: OriginFor<T>, 
+			rfp_id: T::RFPId, 
+			members: BoundedVec<T::AccountId, T::MaxCommitteeMembers>,
+		) -> DispatchResult {
+			let rfp_owner = ensure_signed(origin)?;
+			ensure!(
+				<RFPs<T>>::contains_key(&rfp_owner, &rfp_id),
+				<Error<T>>::NonExistentRFP
+			);
+			ensure!(
+				!Self::evaluation_started(&rfp_owner, &rfp_id),
+				<Error<T>>::EvaluationLocked
+			);
+			ensure!(
+				members.iter().enumerate()
+					.all(|(index, member)| !members[..index].contains(member)),
+				<Error<T>>::DuplicateCommitteeMember
+			);
+			if members.is_empty() {
+				<RFPCommittees<T>>::remove(&rfp_owner, &rfp_id);
+			} else {
+				<RFPCommittees<T>>::insert(
+					&rfp_owner,
+					&rfp_id,
+					EvaluationCommittee {
+						members,
+						evaluation_started: false,
+					}
+				);
+			}
+			Self::deposit_event(Event::EvaluationCommitteeSet(rfp_owner, rfp_id));
+			Ok(())
+		}
+
+		/// A dispatchable for a member of an RFP's evaluation committee to score
+		/// a bid on it, once per criterion, up to `MaxCriterionScore`. Scoring
+		/// a bid again replaces the member's previous scores
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 3).ref_time())]
+		pub fn score_bid(
+			origin: OriginFor<T>, 
+			bid_id: T::BidId,
+			scores: BoundedVec<u8, T::MaxEvaluationCriteria>,
+		) -> DispatchResult {
+			let member = ensure_signed(origin)?;
+			let (_, rfp_id, rfp_details) = Self::start_evaluation(&member, &bid_id)?;
+			let criteria_weights = &rfp_details.evaluation.criteria_weights;
+			ensure!(
+				scores.len() == criteria_weights.len(),
+				<Error<T>>::ScoresDontMatchCriteria
+			);
+			ensure!(
+				scores.iter().all(|score| *score <= T::MaxCriterionScore::get()),
+				<Error<T>>::ScoreTooHigh
+			);
+			let weighted_score = Self::weighted_score(criteria_weights, &scores);
+			let previous_score = <BidScores<T>>::get(&bid_id, &member)
+				.map(|previous_scores| Self::weighted_score(criteria_weights, &previous_scores));
+			<BidEvaluations<T>>::mutate(&bid_id, |bid_evaluation| {
+				match previous_score {
+					Some(previous_score) => {
+						bid_evaluation.weighted_total =
+							bid_evaluation.weighted_total.saturating_sub(previous_score);
+					},
+					None => {
+						bid_evaluation.scorers = bid_evaluation.scorers.saturating_add(1);
+					},
+				}
+				bid_evaluation.weighted_total =
+					bid_evaluation.weighted_total.saturating_add(weighted_score);
+			});
+			<BidScores<T>>::insert(&bid_id, &member, scores);
+			Self::deposit_event(Event::BidScored(member, rfp_id, bid_id, weighted_score));
+			Ok(())
+		}
+
+		/// A dispatchable for a member of an RFP's evaluation committee to approve a bid on it
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 3).ref_time())]
+		pub fn approve_bid(
+			origin: OriginFor<T>, 
+			bid_id: T::BidId,
+		) -> DispatchResult {
+			let member = ensure_signed(origin)?;
+			let (_, rfp_id, _) = Self::start_evaluation(&member, &bid_id)?;
+			ensure!(
+				!<BidApprovals<T>>::contains_key(&bid_id, &member),
+				<Error<T>>::BidAlreadyApproved
+			);
+			<BidApprovals<T>>::insert(&bid_id, &member, ());
+			<BidEvaluations<T>>::mutate(&bid_id, |bid_evaluation| {
+				bid_evaluation.approvals = bid_evaluation.approvals.saturating_add(1);
+			});
+			Self::deposit_event(Event::BidApproved(member, rfp_id, bid_id));
+			Ok(())
+		}
+
+		/// A dispatchable for the RFP owner to decide not to award a lot of an RFP
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(4, 3).ref_time()
+				+ T::DbWeight::get().reads_writes(4, 8).ref_time()
+					.saturating_mul(T::MaxSealedBidsPerRFP::get() as u64)
+		)]
+		pub fn leave_lot_unawarded(
+			origin: OriginFor<T>, 
+			rfp_id: T::RFPId, 
+			lot: u32,
+		) -> DispatchResult {
+			let rfp_owner = ensure_signed(origin)?;
+			let completed = <RFPs<T>>::try_mutate(
+				&rfp_owner,
+				&rfp_id,
+				| maybe_rfp_details | -> Result<Option<RFPStatus>, DispatchError> {
+					let rfp_details = 
+						maybe_rfp_details.as_mut()
+							.ok_or(
+								<Error<T>>::NonExistentRFP
+							)?;
+					ensure!(
+						rfp_details.rfp_status != RFPStatus::AcceptedBid &&
+							rfp_details.rfp_status != RFPStatus::Expired,
+						<Error<T>>::RFPAlreadyDecided
+					);
+					Self::ensure_reveal_period_over(rfp_details)?;
+					Self::ensure_lot_undecided(&rfp_owner, &rfp_id, rfp_details, lot)?;
+					<RFPUnawardedLots<T>>::insert((&rfp_owner, &rfp_id, lot), ());
+					if !Self::decide_lot(&rfp_owner, &rfp_id, rfp_details, false) {
+						return Ok(None)
+					}
+					Self::release_budget(&rfp_owner, &rfp_id, rfp_details);
+					Self::slash_unrevealed_bids(&rfp_owner, &rfp_id);
+					Ok(Some(rfp_details.rfp_status))
+				}
+			)?;
+
+			Self::deposit_event(Event::LotLeftUnawarded(rfp_owner.clone(), rfp_id, lot));
+			match completed {
+				Some(RFPStatus::Expired) =>
+					Self::deposit_event(Event::RFPClosedWithoutAward(rfp_owner, rfp_id)),
+				Some(_) =>
+					Self::deposit_event(Event::RFPAwardsCompleted(rfp_owner, rfp_id)),
+				None => (),
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Allocates the next RFP id from the on-chain counter, skipping
+		/// up to [`MAX_ID_PROBES`] ids that are already in use
+		pub fn allocate_rfp_id(rfp_owner: &T::AccountId) -> Result<T::RFPId, DispatchError> {
+			<NextRFPId<T>>::try_mutate(
+				| next_id | -> Result<T::RFPId, DispatchError> {
+					for _ in 0..MAX_ID_PROBES {
+						let rfp_id: T::RFPId = (*next_id).into();
+						*next_id = next_id.checked_add(1)
+							.ok_or(<Error<T>>::RFPIdsExhausted)?;
+						if !<RFPs<T>>::contains_key(rfp_owner, &rfp_id) &&
+							!<CancelledRFPs<T>>::contains_key(rfp_owner, &rfp_id)
+						{
+							return Ok(rfp_id)
+						}
+					}
+					Err(<Error<T>>::RFPIdsExhausted.into())
+				}
+			)
+		}
+
+		/// Allocates the next bid id from the on-chain counter, skipping
+		/// up to [`MAX_ID_PROBES`] ids that are already in use
+		pub fn allocate_bid_id() -> Result<T::BidId, DispatchError> {
+			<NextBidId<T>>::try_mutate(
+				| next_id | -> Result<T::BidId, DispatchError> {
+					for _ in 0..MAX_ID_PROBES {
+						let bid_id: T::BidId = (*next_id).into();
+						*next_id = next_id.checked_add(1)
+							.ok_or(<Error<T>>::BidIdsExhausted)?;
+						if !<AllBids<T>>::contains_key(&bid_id) &&
+							!<SealedBids<T>>::contains_key(&bid_id)
+						{
+							return Ok(bid_id)
+						}
+					}
+					Err(<Error<T>>::BidIdsExhausted.into())
+				}
+			)
+		}
+
+		/// Checks the bidding schedule of an RFP, and registers its bid-close
+		/// block and decision deadline with the hook, unless they are
+		/// unchanged from the RFP's previous details
+		pub fn schedule_rfp_deadlines(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			rfp_details: &RFPDetails<T>,
+			previous_details: Option<&RFPDetails<T>>,
+		) -> DispatchResult {
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				rfp_details.bid_open < rfp_details.bid_close,
+				<Error<T>>::InvalidRFPSchedule
+			);
+			if let Some(decision_deadline) = rfp_details.decision_deadline {
+				ensure!(
+					decision_deadline > rfp_details.bid_close &&
+						(rfp_details.bidding_mode == BiddingMode::Open ||
+							decision_deadline >= Self::reveal_period_end(rfp_details)),
+					<Error<T>>::InvalidRFPSchedule
+				);
+			}
+
+			// A rescheduled RFP is taken off the blocks it was scheduled for,
+			// so it doesn't hold on to their deadline slots
+			let rfp_key = (rfp_owner.clone(), *rfp_id);
+			let bid_close_changed = previous_details
+				.map_or(true, |previous| previous.bid_close != rfp_details.bid_close);
+			if bid_close_changed {
+				ensure!(
+					rfp_details.bid_close > now,
+					<Error<T>>::InvalidRFPSchedule
+				);
+				if let Some(previous) = previous_details {
+					<RFPsClosingAt<T>>::mutate_exists(previous.bid_close, | maybe_rfps | {
+						if let Some(rfps) = maybe_rfps {
+							rfps.retain(|rfp| *rfp != rfp_key);
+							if rfps.is_empty() {
+								*maybe_rfps = None;
+							}
+						}
+					});
+				}
+				<RFPsClosingAt<T>>::try_append(
+					rfp_details.bid_close,
+					(rfp_owner.clone(), *rfp_id)
+				).ok().ok_or(<Error<T>>::TooManyRFPDeadlines)?;
+			}
+
+			let decision_deadline_changed = previous_details
+				.map_or(true, |previous| previous.decision_deadline != rfp_details.decision_deadline);
+			if let (true, Some(previous_deadline)) = (
+				decision_deadline_changed,
+				previous_details.and_then(|previous| previous.decision_deadline)
+			) {
+				<RFPsExpiringAt<T>>::mutate_exists(previous_deadline, | maybe_rfps | {
+					if let Some(rfps) = maybe_rfps {
+						rfps.retain(|rfp| *rfp != rfp_key);
+						if rfps.is_empty() {
+							*maybe_rfps = None;
+						}
+					}
+				});
+			}
+			if let (true, Some(decision_deadline)) = (decision_deadline_changed, rfp_details.decision_deadline) {
+				<RFPsExpiringAt<T>>::try_append(
+					decision_deadline,
+					(rfp_owner.clone(), *rfp_id)
+				).ok().ok_or(<Error<T>>::TooManyRFPDeadlines)?;
+			}
+			Ok(())
+		}
+
+		/// Reserves the bid bond required by an RFP from the bidder,
+		/// along with the storage deposit for the bid
+		fn reserve_bid_bond(
+			bidder: &T::AccountId,
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			bid_id: &T::BidId,
+			bond: BalanceOf<T>,
+		) -> DispatchResult {
+			let storage_deposit = T::BidDeposit::get();
+			T::Currency::reserve(bidder, bond.saturating_add(storage_deposit))?;
+			<BidBonds<T>>::insert(
+				bid_id,
+				BidBond {
+					bidder: bidder.clone(),
+					rfp_owner: rfp_owner.clone(),
+					rfp_id: *rfp_id,
+					bond,
+					storage_deposit,
+				}
+			);
+			Ok(())
+		}
+
+		/// Returns the storage deposit of a bid to the bidder, along with
+		/// the bid bond, unless the bond is forfeited and slashed instead
+		fn settle_bid_bond(bid_id: &T::BidId, forfeit_bond: bool) {
+			if let Some(bid_bond) = <BidBonds<T>>::take(bid_id) {
+				if forfeit_bond {
+					let _ = T::Currency::slash_reserved(&bid_bond.bidder, bid_bond.bond);
+				} else {
+					T::Currency::unreserve(&bid_bond.bidder, bid_bond.bond);
+				}
+				T::Currency::unreserve(&bid_bond.bidder, bid_bond.storage_deposit);
+			}
+		}
+
+		/// Adds a bid to the list of bids made on an RFP
+		fn add_bid_to_rfp(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			bid_id: T::BidId
+		) -> DispatchResult {
+			<RFPToBids<T>>::try_mutate(
+				rfp_owner,
+				rfp_id,
+				| maybe_bids_for_rfp | -> DispatchResult {
+					let bids_for_rfp = 
+						maybe_bids_for_rfp.as_mut()
+							.ok_or(
+								<Error<T>>::NoBidsForRFPFound
+							)?;
+					ensure!(
+						(bids_for_rfp.len() as u32) < T::MaxBidsPerRFP::get(),
+						<Error<T>>::TooManyBids
+					);
+					bids_for_rfp
+						.try_push(bid_id)
+						.ok()
+						.ok_or(
+							<Error<T>>::TooManyBids
+						)?;
+					Ok(())
+				}
+			)
+		}
+
+		/// The block the reveal period of a sealed RFP ends at
+		fn reveal_period_end(rfp_details: &RFPDetails<T>) -> T::BlockNumber {
+			rfp_details.bid_close.saturating_add(T::RevealPeriod::get())
+		}
+
+		/// Checks that the bids on an RFP can be decided on,
+		/// which for a sealed RFP is once its reveal period has ended
+		fn ensure_reveal_period_over(rfp_details: &RFPDetails<T>) -> DispatchResult {
+			ensure!(
+				rfp_details.bidding_mode == BiddingMode::Open ||
+					<frame_system::Pallet<T>>::block_number() >= Self::reveal_period_end(rfp_details),
+				<Error<T>>::RevealPeriodNotOver
+			);
+			Ok(())
+		}
+
+		/// Removes a sealed bid from its RFP, slashing its bond
+		fn disqualify_sealed_bid(rfp_owner: &T::AccountId, rfp_id: &T::RFPId, bid_id: &T::BidId) {
+			<SealedBids<T>>::remove(bid_id);
+			Self::settle_bid_bond(bid_id, true);
+			<BidToRFP<T>>::remove(bid_id);
+			<BidAcknowledgedAmendment<T>>::remove(bid_id);
+			<RFPToBids<T>>::mutate(rfp_owner, rfp_id, | maybe_bids_for_rfp | {
+				if let Some(bids_for_rfp) = maybe_bids_for_rfp {
+					bids_for_rfp.retain(|id| id != bid_id);
+				}
+			});
+		}
+
+		/// Disqualifies the sealed bids of a decided RFP that were never revealed,
+		/// slashing their bonds. Returns the weight consumed
+		fn slash_unrevealed_bids(rfp_owner: &T::AccountId, rfp_id: &T::RFPId) -> Weight {
+			let unrevealed_bids = <RFPUnrevealedBids<T>>::take(rfp_owner, rfp_id);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			for bid_id in unrevealed_bids {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 8));
+				if let Some(sealed_bid) = <SealedBids<T>>::get(&bid_id) {
+					Self::disqualify_sealed_bid(rfp_owner, rfp_id, &bid_id);
+					Self::deposit_event(Event::BidBondSlashed(sealed_bid.bid_owner, *rfp_id, bid_id));
+				}
+			}
+			weight
+		}
+
+		/// The most weight `clean_up_rfp` can consume, in ref time, removing
+		/// a full batch of bids along with the rest of the RFP's storage
+		fn clean_up_weight() -> u64 {
+			let db_weight = T::DbWeight::get();
+			let bid_weight = db_weight.reads_writes(5, 6).ref_time()
+				.saturating_add(db_weight.writes(T::MaxBidVersions::get() as u64).ref_time());
+			let rfp_storage = T::MaxRFPAmendments::get()
+				.saturating_add(T::MaxRFPInvitees::get())
+				.saturating_add(T::MaxLotsPerRFP::get().saturating_mul(2));
+			bid_weight.saturating_mul(T::MaxBidsCleanedPerCall::get() as u64)
+				.saturating_add(db_weight.reads_writes(1, 10).ref_time())
+				.saturating_add(db_weight.writes(rfp_storage as u64).ref_time())
+		}
+
+		/// Removes up to `MaxBidsCleanedPerCall` bids of a cancelled RFP, returning
+		/// their bonds and storage deposits. Once no bids are left, the rest of
+		/// the RFP's storage is removed, and its id can be used again
+		fn clean_up_rfp(rfp_owner: &T::AccountId, rfp_id: &T::RFPId) {
+			let mut bids_for_rfp = <RFPToBids<T>>::get(rfp_owner, rfp_id).unwrap_or_default();
+			for _ in 0..T::MaxBidsCleanedPerCall::get() {
+				if bids_for_rfp.is_empty() {
+					break
+				}
+				let bid_id = bids_for_rfp.remove(bids_for_rfp.len() - 1);
+				let maybe_bidder = <AllBids<T>>::take(&bid_id)
+					.map(|bid_details| bid_details.bid_owner)
+					.or_else(|| <SealedBids<T>>::get(&bid_id).map(|sealed_bid| sealed_bid.bid_owner));
+				<SealedBids<T>>::remove(&bid_id);
+				<BidToRFP<T>>::remove(&bid_id);
+				<BidAcknowledgedAmendment<T>>::remove(&bid_id);
+				let _ = <BidHistory<T>>::clear_prefix(&bid_id, T::MaxBidVersions::get(), None);
+				Self::remove_bid_evaluation(&bid_id);
+				Self::settle_bid_bond(&bid_id, false);
+				if let Some(bidder) = maybe_bidder {
+					Self::deposit_event(Event::BidCancelled(bidder, *rfp_id, bid_id));
+				}
+			}
+
+			if bids_for_rfp.is_empty() {
+				<RFPToBids<T>>::remove(rfp_owner, rfp_id);
+				<RFPToShortlistedBids<T>>::remove(rfp_owner, rfp_id);
+				let _ = <RFPToAcceptedBid<T>>::clear_prefix(
+					(rfp_owner, rfp_id),
+					T::MaxLotsPerRFP::get(),
+					None
+				);
+				let _ = <RFPUnawardedLots<T>>::clear_prefix(
+					(rfp_owner, rfp_id),
+					T::MaxLotsPerRFP::get(),
+					None
+				);
+				<RFPLotsDecided<T>>::remove(rfp_owner, rfp_id);
+				<RFPAmendmentCount<T>>::remove(rfp_owner, rfp_id);
+				let _ = <RFPAmendments<T>>::clear_prefix(
+					(rfp_owner, rfp_id),
+					T::MaxRFPAmendments::get(),
+					None
+				);
+				let _ = <RFPInvitees<T>>::clear_prefix(
+					(rfp_owner, rfp_id),
+					T::MaxRFPInvitees::get(),
+					None
+				);
+				<RFPInviteeCount<T>>::remove(rfp_owner, rfp_id);
+				<RFPCommittees<T>>::remove(rfp_owner, rfp_id);
+				<RFPUnrevealedBids<T>>::remove(rfp_owner, rfp_id);
+				<CancelledRFPs<T>>::remove(rfp_owner, rfp_id);
+				Self::deposit_event(Event::RFPCleanupCompleted(rfp_owner.clone(), *rfp_id));
+			} else {
+				<RFPToBids<T>>::insert(rfp_owner, rfp_id, bids_for_rfp);
+			}
+		}
+
+		/// Removes a bid and everything stored about it, and takes it off its RFP.
+		/// The bid's bond and storage deposit are settled separately
+		fn remove_bid(rfp_owner: &T::AccountId, rfp_id: &T::RFPId, bid_id: &T::BidId) {
+			<AllBids<T>>::remove(bid_id);
+			<BidToRFP<T>>::remove(bid_id);
+			<BidAcknowledgedAmendment<T>>::remove(bid_id);
+			let _ = <BidHistory<T>>::clear_prefix(bid_id, T::MaxBidVersions::get(), None);
+			Self::remove_bid_evaluation(bid_id);
+			<RFPToBids<T>>::mutate(rfp_owner, rfp_id, | maybe_bids_for_rfp | {
+				if let Some(bids_for_rfp) = maybe_bids_for_rfp {
+					bids_for_rfp.retain(|id| id != bid_id);
+				}
+			});
+			<RFPToShortlistedBids<T>>::mutate(rfp_owner, rfp_id, | maybe_shortlisted_bids | {
+				if let Some(shortlisted_bids) = maybe_shortlisted_bids {
+					shortlisted_bids.retain(|id| id != bid_id);
+				}
+			});
+		}
+
+		/// Removes the scores and approvals the evaluation committee gave a bid
+		fn remove_bid_evaluation(bid_id: &T::BidId) {
+			let _ = <BidScores<T>>::clear_prefix(bid_id, T::MaxCommitteeMembers::get(), None);
+			let _ = <BidApprovals<T>>::clear_prefix(bid_id, T::MaxCommitteeMembers::get(), None);
+			<BidEvaluations<T>>::remove(bid_id);
+		}
+
+		/// Checks that an RFP is awarded in at least one,
+		/// and no more than `MaxLotsPerRFP`, lots
+		fn ensure_valid_lots(rfp_details: &RFPDetails<T>) -> DispatchResult {
+			ensure!(
+				rfp_details.lots >= 1 && rfp_details.lots <= T::MaxLotsPerRFP::get(),
+				<Error<T>>::InvalidRFPLots
+			);
+			Ok(())
+		}
+
+		/// Checks that the budget range of an RFP, if any, is not empty
+		fn ensure_valid_requirements(rfp_details: &RFPDetails<T>) -> DispatchResult {
+			if let (Some(budget_min), Some(budget_max)) = (
+				rfp_details.requirements.budget_min,
+				rfp_details.requirements.budget_max
+			) {
+				ensure!(budget_min <= budget_max, <Error<T>>::InvalidBudgetRange);
+			}
+			Ok(())
+		}
+
+		/// Checks that an RFP awarded to its top-scoring bid has criteria to score bids on
+		fn ensure_valid_evaluation(rfp_details: &RFPDetails<T>) -> DispatchResult {
+			ensure!(
+				rfp_details.evaluation.award_rule != AwardRule::TopScore ||
+					!rfp_details.evaluation.criteria_weights.is_empty(),
+				<Error<T>>::InvalidEvaluationCriteria
+			);
+			Ok(())
+		}
+
+		/// Sums the scores given on every criterion, weighted by the criterion's weight
+		fn weighted_score(
+			criteria_weights: &BoundedVec<u32, T::MaxEvaluationCriteria>,
+			scores: &BoundedVec<u8, T::MaxEvaluationCriteria>,
+		) -> u64 {
+			criteria_weights.iter()
+				.zip(scores.iter())
+				.fold(0u64, |total, (weight, score)| {
+					total.saturating_add((*weight as u64).saturating_mul(*score as u64))
+				})
+		}
+
+		/// Whether the evaluation committee of an RFP has started evaluating its bids
+		fn evaluation_started(rfp_owner: &T::AccountId, rfp_id: &T::RFPId) -> bool {
+			<RFPCommittees<T>>::get(rfp_owner, rfp_id)
+				.map_or(false, |committee| committee.evaluation_started)
+		}
+
+		/// Checks that a committee member can evaluate a bid on an undecided RFP,
+		/// and locks the committee and evaluation terms of the RFP.
+		/// Returns the RFP the bid was made on
+		fn start_evaluation(
+			member: &T::AccountId,
+			bid_id: &T::BidId,
+		) -> Result<(T::AccountId, T::RFPId, RFPDetails<T>), DispatchError> {
+			let bid_details = <AllBids<T>>::get(bid_id)
+				.ok_or(<Error<T>>::NonExistentBid)?;
+			ensure!(
+				bid_details.bid_status == BidStatus::Submitted ||
+					bid_details.bid_status == BidStatus::Shortlisted,
+				<Error<T>>::BidNotActive
+			);
+			let (rfp_owner, rfp_id) = <BidToRFP<T>>::get(bid_id)
+				.ok_or(<Error<T>>::NonExistentRFP)?;
+			let rfp_details = <RFPs<T>>::get(&rfp_owner, &rfp_id)
+				.ok_or(<Error<T>>::NonExistentRFP)?;
+			ensure!(
+				rfp_details.rfp_status != RFPStatus::AcceptedBid &&
+					rfp_details.rfp_status != RFPStatus::Expired,
+				<Error<T>>::RFPAlreadyDecided
+			);
+			<RFPCommittees<T>>::try_mutate(
+				&rfp_owner,
+				&rfp_id,
+				| maybe_committee | -> DispatchResult {
+					let committee = maybe_committee.as_mut()
+						.ok_or(<Error<T>>::NoEvaluationCommittee)?;
+					ensure!(
+						committee.members.contains(member),
+						<Error<T>>::NotCommitteeMember
+					);
+					committee.evaluation_started = true;
+					Ok(())
+				}
+			)?;
+			Ok((rfp_owner, rfp_id, rfp_details))
+		}
+
+		/// Checks that a bid can be accepted under the award rule of an RFP,
+		/// being its top-scoring active bid, or approved by a majority of its committee
+		fn ensure_award_rule(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			rfp_details: &RFPDetails<T>,
+			bid_id: &T::BidId,
+		) -> DispatchResult {
+			let award_rule = rfp_details.evaluation.award_rule;
+			if award_rule == AwardRule::OwnerDecides {
+				return Ok(())
+			}
+			let committee = <RFPCommittees<T>>::get(rfp_owner, rfp_id)
+				.ok_or(<Error<T>>::NoEvaluationCommittee)?;
+			let bid_evaluation = <BidEvaluations<T>>::get(bid_id);
+			match award_rule {
+				AwardRule::TopScore => {
+					ensure!(bid_evaluation.scorers > 0, <Error<T>>::NotTopScoringBid);
+					let top_scoring = <RFPToBids<T>>::get(rfp_owner, rfp_id)
+						.unwrap_or_default()
+						.iter()
+						.filter(|other_bid_id| *other_bid_id != bid_id)
+						.filter(|other_bid_id| {
+							<AllBids<T>>::get(*other_bid_id).map_or(false, |other_bid| {
+								other_bid.bid_status == BidStatus::Submitted ||
+									other_bid.bid_status == BidStatus::Shortlisted
+							})
+						})
+						.all(|other_bid_id| {
+							bid_evaluation.scores_at_least(&<BidEvaluations<T>>::get(other_bid_id))
+						});
+					ensure!(top_scoring, <Error<T>>::NotTopScoringBid);
+				},
+				AwardRule::CommitteeApproval => {
+					ensure!(
+						(bid_evaluation.approvals as usize).saturating_mul(2) > committee.members.len(),
+						<Error<T>>::CommitteeApprovalRequired
+					);
+				},
+				AwardRule::OwnerDecides => {},
+			}
+			Ok(())
+		}
+
+		/// Checks that a bid amount is within the budget range of an RFP
+		fn ensure_within_budget(rfp_details: &RFPDetails<T>, bid_amount: BalanceOf<T>) -> DispatchResult {
+			let requirements = &rfp_details.requirements;
+			ensure!(
+				requirements.budget_min.map_or(true, |budget_min| bid_amount >= budget_min) &&
+					requirements.budget_max.map_or(true, |budget_max| bid_amount <= budget_max),
+				<Error<T>>::BidOutsideBudget
+			);
+			Ok(())
+		}
+
+		/// The escrow reservation holding the budget of an RFP
+		fn budget_reservation(rfp_owner: &T::AccountId, rfp_id: &T::RFPId) -> T::Hash {
+			T::Hashing::hash_of(&(b"rfp_budget", rfp_owner, rfp_id))
+		}
+
+		/// Reserves the maximum budget of an RFP in its budget escrow. The owner's
+		/// own escrow is created and funded with the budget if they have none,
+		/// as long as the RFP is paid in the native currency
+		fn lock_budget(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			rfp_details: &RFPDetails<T>,
+		) -> DispatchResult {
+			let escrow_id = match &rfp_details.budget_escrow {
+				Some(escrow_id) => escrow_id,
+				None => return Ok(()),
+			};
+			let budget = rfp_details.requirements.budget_max
+				.ok_or(<Error<T>>::BudgetEscrowWithoutBudget)?;
+			if escrow_id == rfp_owner &&
+				rfp_details.requirements.asset_id.is_none() &&
+				<pallet_escrow::Pallet<T>>::escrow(escrow_id).is_none()
+			{
+				let amount: pallet_escrow::BalanceOf<T> =
+					budget.saturated_into::<u128>().saturated_into();
+				ensure!(
+					<T as pallet_escrow::Config>::EscrowCurrency::free_balance(rfp_owner) >= amount,
+					<Error<T>>::InsufficientBudgetFunds
+				);
+				<pallet_escrow::Pallet<T>>::create_escrow(
+					RawOrigin::Signed(rfp_owner.clone()).into()
+				)?;
+				<pallet_escrow::Pallet<T>>::fund_escrow(
+					RawOrigin::Signed(rfp_owner.clone()).into(),
+					escrow_id.clone(),
+					amount
+				)?;
+			}
+			<T as pallet_payments::Config>::Escrow::reserve_funds(
+				escrow_id,
+				rfp_owner,
+				rfp_details.requirements.asset_id,
+				&Self::budget_reservation(rfp_owner, rfp_id),
+				budget,
+			)?;
+			<RFPBudgetReserved<T>>::insert(rfp_owner, rfp_id, budget);
+			Self::deposit_event(
+				Event::RFPBudgetLocked(rfp_owner.clone(), *rfp_id, escrow_id.clone(), budget)
+			);
+			Ok(())
+		}
+
+		/// Releases whatever is left of an RFP's budget in its budget escrow
+		fn release_budget(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			rfp_details: &RFPDetails<T>,
+		) {
+			let escrow_id = match &rfp_details.budget_escrow {
+				Some(escrow_id) => escrow_id,
+				None => return,
+			};
+			let reserved = <RFPBudgetReserved<T>>::take(rfp_owner, rfp_id);
+			if reserved.is_zero() {
+				return
+			}
+			// Only funds this pallet reserved are released, which can't fail
+			let _ = <T as pallet_payments::Config>::Escrow::release_funds(
+				escrow_id,
+				rfp_details.requirements.asset_id,
+				&Self::budget_reservation(rfp_owner, rfp_id),
+				reserved,
+			);
+			Self::deposit_event(
+				Event::RFPBudgetReleased(rfp_owner.clone(), *rfp_id, escrow_id.clone(), reserved)
+			);
+		}
+
+		/// Builds the agreement paying a bid in a single payment that can be
+		/// claimed at once, out of the RFP's budget escrow if it has one, and
+		/// out of the owner's account otherwise
+		fn bid_payment(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			rfp_details: &RFPDetails<T>,
+			bid_details: &BidDetails<T>,
+		) -> Result<pallet_payments::PaymentDetails<T>, DispatchError> {
+			let payment_method = match &rfp_details.budget_escrow {
+				Some(escrow_id) => pallet_payments::PaymentMethod {
+					payment_source: pallet_payments::PaymentSource::EscrowAccount,
+					account_id: escrow_id.clone(),
+				},
+				None => pallet_payments::PaymentMethod {
+					payment_source: pallet_payments::PaymentSource::PersonalAccount,
+					account_id: rfp_owner.clone(),
+				},
+			};
+			Ok(pallet_payments::PaymentDetails {
+				payer: rfp_owner.clone(),
+				payee: bid_details.bid_owner.clone(),
+				payment_id: <pallet_payments::Pallet<T>>::allocate_payment_id(
+					rfp_owner,
+					&bid_details.bid_owner
+				)?,
+				rfp_reference_id: (*rfp_id).into(),
+				total_payment_amount: bid_details.bid_amount,
+				payment_schedule: bounded_vec![
+					pallet_payments::ScheduledPayment {
+						payment_date: <T as pallet_payments::Config>::TimeProvider::now().as_secs(),
+						amount_per_claim: bid_details.bid_amount,
+						released: true,
+						delinquent_since: None,
+						late_penalty: Zero::zero(),
+					}
+				],
+				payment_method,
+				administrator_id: rfp_owner.clone(),
+				asset_id: rfp_details.requirements.asset_id,
+				late_payment_terms: None,
+			})
+		}
+
+		/// Checks that a bidder holds every credential an RFP requires
+		fn ensure_credentials(rfp_details: &RFPDetails<T>, bidder: &T::AccountId) -> DispatchResult {
+			ensure!(
+				rfp_details.requirements.required_credentials.iter()
+					.all(|credential_id| T::Credentials::has_credential(bidder, credential_id)),
+				<Error<T>>::MissingRequiredCredential
+			);
+			Ok(())
+		}
+
+		/// Checks that a bidder on a private RFP was invited,
+		/// or holds the RFP's invite credential
+		fn ensure_invited(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			rfp_details: &RFPDetails<T>,
+			bidder: &T::AccountId,
+		) -> DispatchResult {
+			if rfp_details.visibility == RFPVisibility::Public {
+				return Ok(())
+			}
+			ensure!(
+				<RFPInvitees<T>>::contains_key((rfp_owner, rfp_id, bidder)) ||
+					rfp_details.invite_credential.as_ref().map_or(false, |credential_id| {
+						T::Credentials::has_credential(bidder, credential_id)
+					}),
+				<Error<T>>::BidderNotInvited
+			);
+			Ok(())
+		}
+
+		/// Checks that a lot of an RFP exists, and has been
+		/// neither awarded nor left unawarded yet
+		fn ensure_lot_undecided(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			rfp_details: &RFPDetails<T>,
+			lot: u32,
+		) -> DispatchResult {
+			ensure!(lot < rfp_details.lots, <Error<T>>::NonExistentLot);
+			ensure!(
+				!<RFPToAcceptedBid<T>>::contains_key((rfp_owner, rfp_id, lot)) &&
+					!<RFPUnawardedLots<T>>::contains_key((rfp_owner, rfp_id, lot)),
+				<Error<T>>::LotAlreadyDecided
+			);
+			Ok(())
+		}
+
+		/// Counts a lot of an RFP as decided, and updates the RFP's status.
+		/// Returns whether every lot of the RFP has now been decided
+		fn decide_lot(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			rfp_details: &mut RFPDetails<T>,
+			awarded: bool,
+		) -> bool {
+			let lots_decided = <RFPLotsDecided<T>>::mutate(rfp_owner, rfp_id, | lots_decided | {
+				*lots_decided = lots_decided.saturating_add(1);
+				*lots_decided
+			});
+			let any_awarded = awarded || rfp_details.rfp_status == RFPStatus::PartiallyAwarded;
+			if lots_decided >= rfp_details.lots {
+				rfp_details.rfp_status = if any_awarded {
+					RFPStatus::AcceptedBid
+				} else {
+					RFPStatus::Expired
+				};
+				true
+			} else {
+				if awarded {
+					rfp_details.rfp_status = RFPStatus::PartiallyAwarded;
+				}
+				false
+			}
+		}
+
+		/// Records that a bid takes the latest amendment of its RFP
+		/// into account, returning the number of that amendment
+		fn acknowledge_amendment(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			bid_id: &T::BidId,
+		) -> u32 {
+			let amendment = <RFPAmendmentCount<T>>::get(rfp_owner, rfp_id);
+			<BidAcknowledgedAmendment<T>>::insert(bid_id, amendment);
+			amendment
+		}
+
+		/// Checks that a bid was made, updated or acknowledged
+		/// since the latest amendment of its RFP
+		fn ensure_amendment_acknowledged(
+			rfp_owner: &T::AccountId,
+			rfp_id: &T::RFPId,
+			bid_id: &T::BidId,
+		) -> DispatchResult {
+			ensure!(
+				<BidAcknowledgedAmendment<T>>::get(bid_id) ==
+					<RFPAmendmentCount<T>>::get(rfp_owner, rfp_id),
+				<Error<T>>::AmendmentNotAcknowledged
+			);
+			Ok(())
+		}
+
+		/// Keeps the terms of the current version of a bid, so the
+		/// terms of the accepted bid can be proven later on
+		fn record_bid_terms(bid_id: &T::BidId, bid_details: &BidDetails<T>) {
+			<BidHistory<T>>::insert(
+				bid_id,
+				bid_details.version,
+				BidTerms {
+					ipfs_hash: bid_details.ipfs_hash.clone(),
+					bid_amount: bid_details.bid_amount,
+					submitted_at: <frame_system::Pallet<T>>::block_number(),
+				}
+			);
+		}
+
+		/// Removes a submitted or shortlisted bid from an undecided RFP, once
+		/// `authorize` approves of the bid and the RFP owner. The bid's storage
+		/// deposit is returned, and its bond too unless `forfeit_bond` is set.
+		/// Returns the RFP the bid was made on
+		fn close_bid(
+			bid_id: &T::BidId,
+			forfeit_bond: bool,
+			authorize: impl FnOnce(&BidDetails<T>, &T::AccountId) -> DispatchResult,
+		) -> Result<T::RFPId, DispatchError> {
+			let (rfp_owner, rfp_id) = <BidToRFP<T>>::get(bid_id)
+				.ok_or(<Error<T>>::NonExistentBid)?;
+			let bid_details = <AllBids<T>>::get(bid_id)
+				.ok_or(<Error<T>>::NonExistentBid)?;
+			authorize(&bid_details, &rfp_owner)?;
+			ensure!(
+				bid_details.bid_status == BidStatus::Submitted ||
+					bid_details.bid_status == BidStatus::Shortlisted,
+				<Error<T>>::BidNotActive
+			);
+			let rfp_details = <RFPs<T>>::get(&rfp_owner, &rfp_id)
+				.ok_or(<Error<T>>::NonExistentRFP)?;
+			ensure!(
+				rfp_details.rfp_status != RFPStatus::AcceptedBid &&
+					rfp_details.rfp_status != RFPStatus::Expired,
+				<Error<T>>::RFPAlreadyDecided
+			);
+			Self::settle_bid_bond(bid_id, forfeit_bond);
+			Self::remove_bid(&rfp_owner, &rfp_id, bid_id);
+			Ok(rfp_id)
+		}
+
+		/// Checks that an RFP is accepting bids, and that
+		/// the current block is within its bidding window
+		fn ensure_bidding_open(rfp_details: &RFPDetails<T>) -> DispatchResult {
+			ensure!(
+				rfp_details.rfp_status == RFPStatus::AcceptingBids,
+				<Error<T>>::RFPNotAcceptingBids
+			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				now >= rfp_details.bid_open,
+				<Error<T>>::BiddingNotOpen
+			);
+			ensure!(
+				now < rfp_details.bid_close,
+				<Error<T>>::BiddingClosed
+			);
+			Ok(())
+		}
+
+		/// Stops an RFP that is still accepting bids from accepting new bids,
+		/// if `now` is still its bid-close block
+		fn close_bidding(now: T::BlockNumber, rfp_owner: T::AccountId, rfp_id: T::RFPId) {
+			let closed = <RFPs<T>>::mutate(
+				&rfp_owner,
+				&rfp_id,
+				| maybe_rfp_details | {
+					match maybe_rfp_details {
+						Some(rfp_details) if rfp_details.bid_close == now &&
+							rfp_details.rfp_status == RFPStatus::AcceptingBids => {
+							rfp_details.rfp_status = RFPStatus::NotAcceptingNewBids;
+							true
+						},
+						_ => false,
+					}
+				}
+			);
+			if closed {
+				Self::deposit_event(Event::RFPBiddingClosed(rfp_owner, rfp_id));
+			}
+		}
+
+		/// Expires an RFP that has no accepted bid, if `now` is still its decision
+		/// deadline. A partially awarded RFP is completed instead, leaving the
+		/// lots that were not decided by the deadline unawarded.
+		/// Returns the weight consumed
+		fn expire_rfp(now: T::BlockNumber, rfp_owner: T::AccountId, rfp_id: T::RFPId) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			let expired = <RFPs<T>>::mutate(
+				&rfp_owner,
+				&rfp_id,
+				| maybe_rfp_details | {
+					match maybe_rfp_details {
+						Some(rfp_details) if rfp_details.decision_deadline == Some(now) &&
+							rfp_details.rfp_status != RFPStatus::AcceptedBid &&
+							rfp_details.rfp_status != RFPStatus::Expired => {
+							if rfp_details.rfp_status == RFPStatus::PartiallyAwarded {
+								rfp_details.rfp_status = RFPStatus::AcceptedBid;
+							} else {
+								rfp_details.rfp_status = RFPStatus::Expired;
+							}
+							if rfp_details.budget_escrow.is_some() {
+								// The budget's reservation and its escrow totals
+								weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 3));
+							}
+							Self::release_budget(&rfp_owner, &rfp_id, rfp_details);
+							weight = weight.saturating_add(Self::slash_unrevealed_bids(&rfp_owner, &rfp_id));
+							Some(rfp_details.rfp_status)
+						},
+						_ => None,
+					}
+				}
+			);
+			match expired {
+				Some(RFPStatus::Expired) =>
+					Self::deposit_event(Event::RFPExpired(rfp_owner, rfp_id)),
+				Some(_) =>
+					Self::deposit_event(Event::RFPAwardsCompleted(rfp_owner, rfp_id)),
+				None => (),
+			}
+			weight
+		}
+
+		/// Creates an RFP under the given id
+		pub fn do_create_rfp(
+			rfp_owner: T::AccountId,
+			rfp_id: T::RFPId,
+			mut rfp_details: RFPDetails<T>
+		) -> DispatchResult {
+			ensure!(
+				rfp_details.rfp_owner == rfp_owner,
+				Error::<T>::RFPOwnerMismatch
+			);
+			// Any other status would leave the RFP's budget locked for good
+			rfp_details.rfp_status = RFPStatus::AcceptingBids;
+
+			// Assert rfp doesn't already exist
+			let rfp_exists = <RFPs<T>>::get(
+				&rfp_owner,
+				&rfp_id,
+			);
+
+			ensure!(
+				rfp_exists.is_none(),
+				Error::<T>::RFPAlreadyExists
+			);
+			ensure!(
+				!<CancelledRFPs<T>>::contains_key(&rfp_owner, &rfp_id),
+				Error::<T>::RFPCleanupPending
+			);
+			Self::ensure_valid_lots(&rfp_details)?;
+			Self::ensure_valid_requirements(&rfp_details)?;
+			Self::ensure_valid_evaluation(&rfp_details)?;
+			Self::schedule_rfp_deadlines(&rfp_owner, &rfp_id, &rfp_details, None)?;
+			Self::lock_budget(&rfp_owner, &rfp_id, &rfp_details)?;
+
+			// Insert the RFP details into storage
+			<RFPs<T>>::insert(
+				&rfp_owner, 
+				&rfp_id,
+				rfp_details
+			);
+			let rfps_to_bids: BoundedVec<
+				T::BidId, ConstU32<{VEC_LIMIT}>
+			> = BoundedVec::<
+					T::BidId, ConstU32<{VEC_LIMIT}>
+				>::default();
+			<RFPToBids<T>>::insert(
+				&rfp_owner,
+				&rfp_id,
+				rfps_to_bids
+			);
+			Self::deposit_event(Event::CreateRFP(rfp_owner, rfp_id));
+			Ok(())
+		}
+
+		/// Bids on an RFP under the given bid id
+		pub fn do_bid_on_rfp(
+			bid_owner: T::AccountId,
+			rfp_owner: T::AccountId, 
+			rfp_id: T::RFPId, 
+			bid_id: T::BidId,
+			mut bid_details: BidDetails<T>
+		) -> DispatchResult {
+			// Credentials, invitations and bonds are checked against the bidder,
+			// so the bid must also be owned by, and paid out to, the bidder
+			ensure!(
+				bid_details.bid_owner == bid_owner,
+				<Error<T>>::BidOwnerMismatch
+			);
+			let rfp_details = <RFPs<T>>::get(
+				&rfp_owner,
+				&rfp_id
+			).ok_or(<Error<T>>::NonExistentRFP)?;
+			ensure!(
+				rfp_details.bidding_mode == BiddingMode::Open,
+				<Error<T>>::RFPRequiresSealedBids
+			);
+			Self::ensure_bidding_open(&rfp_details)?;
+			Self::ensure_within_budget(&rfp_details, bid_details.bid_amount)?;
+			Self::ensure_credentials(&rfp_details, &bid_owner)?;
+			Self::ensure_invited(&rfp_owner, &rfp_id, &rfp_details, &bid_owner)?;
+			ensure!(
+				!<AllBids<T>>::contains_key(&bid_id) &&
+					!<SealedBids<T>>::contains_key(&bid_id),
+				<Error<T>>::BidAlreadyExists
+			);
+			Self::reserve_bid_bond(&bid_owner, &rfp_owner, &rfp_id, &bid_id, rfp_details.bid_bond)?;
+			<BidToRFP<T>>::insert(&bid_id, (&rfp_owner, rfp_id));
+			Self::add_bid_to_rfp(&rfp_owner, &rfp_id, bid_id)?;
+			Self::acknowledge_amendment(&rfp_owner, &rfp_id, &bid_id);
+			bid_details.bid_status = BidStatus::Submitted;
+			bid_details.version = 0;
+			Self::record_bid_terms(&bid_id, &bid_details);
+			<AllBids<T>>::insert(
+				&bid_id,
+				bid_details
+			);
+
+			Self::deposit_event(Event::BidOnRFP(bid_owner, rfp_id, bid_id));
 			Ok(())
 		}
 	}
@@ -0,0 +1,638 @@
+// This file is part of Ventur, it implements an RFP process as a Substrate Pallet.
+
+// Copyright (C) 2022 Popular Coding LLC.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations for the RFP pallet.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::traits::{Bounded, Zero},
+	storage_alias,
+	traits::StorageVersion,
+};
+use sp_std::prelude::*;
+
+/// Migrates from the storage layout the pallet was first deployed with. RFPs get a
+/// bidding schedule, bids a status and their first version, and the bid lists of
+/// RFPs are keyed by the RFP owner and RFP id, the same key as `RFPs`, instead of
+/// by the RFP id alone.
+pub mod v1 {
+	use super::*;
+
+	/// The storage layout the pallet was first deployed with
+	pub mod v0 {
+		use super::*;
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct RFPDetails<T: Config>{
+			pub rfp_owner: T::AccountId,
+			pub ipfs_hash: T::Cid,
+			pub rfp_status: RFPStatus,
+		}
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct BidDetails<T: Config>{
+			pub bid_owner: T::AccountId,
+			pub ipfs_hash: T::Cid,
+			pub bid_amount: BalanceOf<T>,
+		}
+
+		#[storage_alias]
+		pub type RFPs<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			RFPDetails<T>,
+		>;
+
+		#[storage_alias]
+		pub type AllBids<T: Config> = StorageMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as Config>::BidId,
+			BidDetails<T>,
+		>;
+
+		#[storage_alias]
+		pub type RFPToBids<T: Config> = StorageMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			BoundedVec<<T as Config>::BidId, ConstU32<{VEC_LIMIT}>>,
+		>;
+
+		#[storage_alias]
+		pub type RFPToShortlistedBids<T: Config> = StorageMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			BoundedVec<<T as Config>::BidId, ConstU32<{VEC_LIMIT}>>,
+		>;
+
+		#[storage_alias]
+		pub type RFPToAcceptedBid<T: Config> = StorageMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			<T as Config>::BidId,
+		>;
+	}
+
+	/// RFPs that are accepting bids keep accepting them until their owner
+	/// amends their schedule, while the bidding of every other RFP closes at
+	/// the upgrade. Bids start out at their first version, shortlisted or
+	/// accepted if their RFP shortlisted or accepted them.
+	///
+	/// Bids did not record the RFP they were made on, so the bids listed under
+	/// an RFP id are moved under its owner. Bids listed under an RFP id that
+	/// several owners used can't be told apart, and are kept, but left out of
+	/// every RFP's bids
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 1 {
+			return T::DbWeight::get().reads(1)
+		}
+		let now = <frame_system::Pallet<T>>::block_number();
+		let mut reads: u64 = 1;
+		let mut writes: u64 = 1;
+
+		v2::v1::RFPs::<T>::translate::<v0::RFPDetails<T>, _>(|_, _, old| {
+			reads = reads.saturating_add(1);
+			writes = writes.saturating_add(1);
+			let bid_close = if old.rfp_status == RFPStatus::AcceptingBids {
+				T::BlockNumber::max_value()
+			} else {
+				now
+			};
+			Some(v2::v1::RFPDetails {
+				rfp_owner: old.rfp_owner,
+				ipfs_hash: old.ipfs_hash,
+				rfp_status: old.rfp_status,
+				bid_open: Zero::zero(),
+				bid_close,
+				decision_deadline: None,
+				bidding_mode: BiddingMode::Open,
+				bid_bond: Zero::zero(),
+			})
+		});
+
+		let old_bids: Vec<_> = v0::RFPToBids::<T>::drain().collect();
+		let old_shortlists: Vec<_> = v0::RFPToShortlistedBids::<T>::drain().collect();
+		let old_accepted_bids: Vec<_> = v0::RFPToAcceptedBid::<T>::drain().collect();
+		let drained = (old_bids.len() + old_shortlists.len() + old_accepted_bids.len()) as u64;
+		reads = reads.saturating_add(drained);
+		writes = writes.saturating_add(drained);
+
+		let rfp_keys: Vec<(T::AccountId, T::RFPId)> = <RFPs<T>>::iter_keys().collect();
+		reads = reads.saturating_add(rfp_keys.len() as u64);
+		let sole_owner_of = |rfp_id: &T::RFPId| -> Option<T::AccountId> {
+			let mut owners = rfp_keys.iter()
+				.filter(|(_, id)| id == rfp_id)
+				.map(|(rfp_owner, _)| rfp_owner.clone());
+			match (owners.next(), owners.next()) {
+				(Some(rfp_owner), None) => Some(rfp_owner),
+				_ => None,
+			}
+		};
+
+		for (rfp_id, bids) in old_bids {
+			if let Some(rfp_owner) = sole_owner_of(&rfp_id) {
+				for bid_id in bids.iter() {
+					<BidToRFP<T>>::insert(bid_id, (&rfp_owner, rfp_id));
+				}
+				writes = writes.saturating_add(bids.len() as u64).saturating_add(1);
+				<RFPToBids<T>>::insert(&rfp_owner, &rfp_id, bids);
+			}
+		}
+		let mut shortlisted: Vec<T::BidId> = Vec::new();
+		for (rfp_id, shortlisted_bids) in old_shortlists {
+			if let Some(rfp_owner) = sole_owner_of(&rfp_id) {
+				shortlisted.extend(shortlisted_bids.iter().copied());
+				<RFPToShortlistedBids<T>>::insert(&rfp_owner, &rfp_id, shortlisted_bids);
+				writes = writes.saturating_add(1);
+			}
+		}
+		let mut accepted: Vec<T::BidId> = Vec::new();
+		for (rfp_id, bid_id) in old_accepted_bids {
+			if let Some(rfp_owner) = sole_owner_of(&rfp_id) {
+				accepted.push(bid_id);
+				v2::v1::RFPToAcceptedBid::<T>::insert(&rfp_owner, &rfp_id, bid_id);
+				writes = writes.saturating_add(1);
+			}
+		}
+
+		<AllBids<T>>::translate::<v0::BidDetails<T>, _>(|bid_id, old| {
+			reads = reads.saturating_add(1);
+			writes = writes.saturating_add(2);
+			let bid_status = if accepted.contains(&bid_id) {
+				BidStatus::Accepted
+			} else if shortlisted.contains(&bid_id) {
+				BidStatus::Shortlisted
+			} else {
+				BidStatus::Submitted
+			};
+			<BidHistory<T>>::insert(
+				&bid_id,
+				0,
+				BidTerms {
+					ipfs_hash: old.ipfs_hash.clone(),
+					bid_amount: old.bid_amount,
+					submitted_at: now,
+				}
+			);
+			Some(BidDetails {
+				bid_owner: old.bid_owner,
+				ipfs_hash: old.ipfs_hash,
+				bid_amount: old.bid_amount,
+				bid_status,
+				version: 0,
+			})
+		});
+
+		// RFPs whose bids were left out get an empty bid list
+		for (rfp_owner, rfp_id) in rfp_keys.iter() {
+			reads = reads.saturating_add(1);
+			if !<RFPToBids<T>>::contains_key(rfp_owner, rfp_id) {
+				<RFPToBids<T>>::insert(rfp_owner, rfp_id, BoundedVec::default());
+				writes = writes.saturating_add(1);
+			}
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+/// Awards RFPs in lots. Every RFP gets a single lot, and
+/// an accepted bid is moved to the RFP's first lot.
+pub mod v2 {
+	use super::*;
+
+	/// The storage layout from before RFPs were awarded in lots
+	pub mod v1 {
+		use super::*;
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct RFPDetails<T: Config>{
+			pub rfp_owner: T::AccountId,
+			pub ipfs_hash: T::Cid,
+			pub rfp_status: RFPStatus,
+			pub bid_open: T::BlockNumber,
+			pub bid_close: T::BlockNumber,
+			pub decision_deadline: Option<T::BlockNumber>,
+			pub bidding_mode: BiddingMode,
+			pub bid_bond: BalanceOf<T>,
+		}
+
+		#[storage_alias]
+		pub type RFPs<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			RFPDetails<T>,
+		>;
+
+		#[storage_alias]
+		pub type RFPToAcceptedBid<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			<T as Config>::BidId,
+		>;
+	}
+
+	/// Gives every RFP a single lot, which its accepted bid, if any, is awarded
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 2 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut reads: u64 = 1;
+		let mut writes: u64 = 1;
+
+		v3::v2::RFPs::<T>::translate::<v1::RFPDetails<T>, _>(|_, _, old| {
+			reads = reads.saturating_add(1);
+			writes = writes.saturating_add(1);
+			Some(v3::v2::RFPDetails {
+				rfp_owner: old.rfp_owner,
+				ipfs_hash: old.ipfs_hash,
+				rfp_status: old.rfp_status,
+				bid_open: old.bid_open,
+				bid_close: old.bid_close,
+				decision_deadline: old.decision_deadline,
+				bidding_mode: old.bidding_mode,
+				bid_bond: old.bid_bond,
+				lots: 1,
+			})
+		});
+
+		let old_accepted_bids: Vec<_> = v1::RFPToAcceptedBid::<T>::drain().collect();
+		for (rfp_owner, rfp_id, bid_id) in old_accepted_bids {
+			<RFPToAcceptedBid<T>>::insert((&rfp_owner, &rfp_id, 0), bid_id);
+			<RFPLotsDecided<T>>::insert(&rfp_owner, &rfp_id, 1);
+			reads = reads.saturating_add(1);
+			writes = writes.saturating_add(3);
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+/// Keeps the budget range, payout asset and category tags of RFPs on-chain.
+pub mod v3 {
+	use super::*;
+
+	/// The storage layout from before RFPs kept their requirements on-chain
+	pub mod v2 {
+		use super::*;
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct RFPDetails<T: Config>{
+			pub rfp_owner: T::AccountId,
+			pub ipfs_hash: T::Cid,
+			pub rfp_status: RFPStatus,
+			pub bid_open: T::BlockNumber,
+			pub bid_close: T::BlockNumber,
+			pub decision_deadline: Option<T::BlockNumber>,
+			pub bidding_mode: BiddingMode,
+			pub bid_bond: BalanceOf<T>,
+			pub lots: u32,
+		}
+
+		#[storage_alias]
+		pub type RFPs<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			RFPDetails<T>,
+		>;
+	}
+
+	/// Gives every RFP empty requirements, which every bid meets
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 3 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut translated: u64 = 0;
+
+		v4::v3::RFPs::<T>::translate::<v2::RFPDetails<T>, _>(|_, _, old| {
+			translated = translated.saturating_add(1);
+			Some(v4::v3::RFPDetails {
+				rfp_owner: old.rfp_owner,
+				ipfs_hash: old.ipfs_hash,
+				rfp_status: old.rfp_status,
+				bid_open: old.bid_open,
+				bid_close: old.bid_close,
+				decision_deadline: old.decision_deadline,
+				bidding_mode: old.bidding_mode,
+				bid_bond: old.bid_bond,
+				lots: old.lots,
+				requirements: Default::default(),
+			})
+		});
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+}
+
+/// Lets RFPs require credentials of their bidders.
+pub mod v4 {
+	use super::*;
+
+	/// The storage layout from before RFPs could require credentials
+	pub mod v3 {
+		use super::*;
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo, DefaultNoBound)]
+		#[scale_info(skip_type_params(T))]
+		pub struct RFPRequirements<T: Config>{
+			pub budget_min: Option<BalanceOf<T>>,
+			pub budget_max: Option<BalanceOf<T>>,
+			pub asset_id: Option<pallet_payments::AssetIdOf<T>>,
+			pub category_tags: BoundedVec<CategoryTag<T>, T::MaxCategoryTags>,
+		}
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct RFPDetails<T: Config>{
+			pub rfp_owner: T::AccountId,
+			pub ipfs_hash: T::Cid,
+			pub rfp_status: RFPStatus,
+			pub bid_open: T::BlockNumber,
+			pub bid_close: T::BlockNumber,
+			pub decision_deadline: Option<T::BlockNumber>,
+			pub bidding_mode: BiddingMode,
+			pub bid_bond: BalanceOf<T>,
+			pub lots: u32,
+			pub requirements: RFPRequirements<T>,
+		}
+
+		#[storage_alias]
+		pub type RFPs<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			RFPDetails<T>,
+		>;
+	}
+
+	/// Keeps the requirements of every RFP, which requires no credentials
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 4 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut translated: u64 = 0;
+
+		v5::v4::RFPs::<T>::translate::<v3::RFPDetails<T>, _>(|_, _, old| {
+			translated = translated.saturating_add(1);
+			Some(v5::v4::RFPDetails {
+				rfp_owner: old.rfp_owner,
+				ipfs_hash: old.ipfs_hash,
+				rfp_status: old.rfp_status,
+				bid_open: old.bid_open,
+				bid_close: old.bid_close,
+				decision_deadline: old.decision_deadline,
+				bidding_mode: old.bidding_mode,
+				bid_bond: old.bid_bond,
+				lots: old.lots,
+				requirements: RFPRequirements {
+					budget_min: old.requirements.budget_min,
+					budget_max: old.requirements.budget_max,
+					asset_id: old.requirements.asset_id,
+					category_tags: old.requirements.category_tags,
+					required_credentials: Default::default(),
+				},
+			})
+		});
+
+		StorageVersion::new(4).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+}
+
+/// Lets RFPs lock their budget in an escrow.
+pub mod v5 {
+	use super::*;
+
+	/// The storage layout from before RFPs could lock their budget in an escrow
+	pub mod v4 {
+		use super::*;
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct RFPDetails<T: Config>{
+			pub rfp_owner: T::AccountId,
+			pub ipfs_hash: T::Cid,
+			pub rfp_status: RFPStatus,
+			pub bid_open: T::BlockNumber,
+			pub bid_close: T::BlockNumber,
+			pub decision_deadline: Option<T::BlockNumber>,
+			pub bidding_mode: BiddingMode,
+			pub bid_bond: BalanceOf<T>,
+			pub lots: u32,
+			pub requirements: RFPRequirements<T>,
+		}
+
+		#[storage_alias]
+		pub type RFPs<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			RFPDetails<T>,
+		>;
+	}
+
+	/// Leaves the budget of every RFP unlocked
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 5 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut translated: u64 = 0;
+
+		<v6::v5::RFPs<T>>::translate::<v4::RFPDetails<T>, _>(|_, _, old| {
+			translated = translated.saturating_add(1);
+			Some(v6::v5::RFPDetails {
+				rfp_owner: old.rfp_owner,
+				ipfs_hash: old.ipfs_hash,
+				rfp_status: old.rfp_status,
+				bid_open: old.bid_open,
+				bid_close: old.bid_close,
+				decision_deadline: old.decision_deadline,
+				bidding_mode: old.bidding_mode,
+				bid_bond: old.bid_bond,
+				lots: old.lots,
+				requirements: old.requirements,
+				budget_escrow: None,
+			})
+		});
+
+		StorageVersion::new(5).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+}
+
+/// Lets RFPs be private, taking bids from invited accounts only.
+pub mod v6 {
+	use super::*;
+
+	/// The storage layout from before RFPs could be private
+	pub mod v5 {
+		use super::*;
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct RFPDetails<T: Config>{
+			pub rfp_owner: T::AccountId,
+			pub ipfs_hash: T::Cid,
+			pub rfp_status: RFPStatus,
+			pub bid_open: T::BlockNumber,
+			pub bid_close: T::BlockNumber,
+			pub decision_deadline: Option<T::BlockNumber>,
+			pub bidding_mode: BiddingMode,
+			pub bid_bond: BalanceOf<T>,
+			pub lots: u32,
+			pub requirements: RFPRequirements<T>,
+			pub budget_escrow: Option<T::AccountId>,
+		}
+
+		#[storage_alias]
+		pub type RFPs<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			RFPDetails<T>,
+		>;
+	}
+
+	/// Makes every existing RFP public
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 6 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut translated: u64 = 0;
+
+		<v7::v6::RFPs<T>>::translate::<v5::RFPDetails<T>, _>(|_, _, old| {
+			translated = translated.saturating_add(1);
+			Some(v7::v6::RFPDetails {
+				rfp_owner: old.rfp_owner,
+				ipfs_hash: old.ipfs_hash,
+				rfp_status: old.rfp_status,
+				bid_open: old.bid_open,
+				bid_close: old.bid_close,
+				decision_deadline: old.decision_deadline,
+				bidding_mode: old.bidding_mode,
+				bid_bond: old.bid_bond,
+				lots: old.lots,
+				requirements: old.requirements,
+				budget_escrow: old.budget_escrow,
+				visibility: RFPVisibility::Public,
+				invite_credential: None,
+			})
+		});
+
+		StorageVersion::new(6).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+}
+
+/// Lets RFPs have their bids evaluated by a committee.
+pub mod v7 {
+	use super::*;
+
+	/// The storage layout from before RFPs had evaluation terms
+	pub mod v6 {
+		use super::*;
+
+		#[derive(Clone, Encode, Decode, PartialEq, TypeInfo)]
+		#[scale_info(skip_type_params(T))]
+		pub struct RFPDetails<T: Config>{
+			pub rfp_owner: T::AccountId,
+			pub ipfs_hash: T::Cid,
+			pub rfp_status: RFPStatus,
+			pub bid_open: T::BlockNumber,
+			pub bid_close: T::BlockNumber,
+			pub decision_deadline: Option<T::BlockNumber>,
+			pub bidding_mode: BiddingMode,
+			pub bid_bond: BalanceOf<T>,
+			pub lots: u32,
+			pub requirements: RFPRequirements<T>,
+			pub budget_escrow: Option<T::AccountId>,
+			pub visibility: RFPVisibility,
+			pub invite_credential: Option<T::CredentialId>,
+		}
+
+		#[storage_alias]
+		pub type RFPs<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			<T as Config>::RFPId,
+			RFPDetails<T>,
+		>;
+	}
+
+	/// Leaves the award of every existing RFP to its owner
+	pub fn migrate<T: Config>() -> Weight {
+		if StorageVersion::get::<Pallet<T>>() >= 7 {
+			return T::DbWeight::get().reads(1)
+		}
+		let mut translated: u64 = 0;
+
+		<RFPs<T>>::translate::<v6::RFPDetails<T>, _>(|_, _, old| {
+			translated = translated.saturating_add(1);
+			Some(RFPDetails {
+				rfp_owner: old.rfp_owner,
+				ipfs_hash: old.ipfs_hash,
+				rfp_status: old.rfp_status,
+				bid_open: old.bid_open,
+				bid_close: old.bid_close,
+				decision_deadline: old.decision_deadline,
+				bidding_mode: old.bidding_mode,
+				bid_bond: old.bid_bond,
+				lots: old.lots,
+				requirements: old.requirements,
+				budget_escrow: old.budget_escrow,
+				visibility: old.visibility,
+				invite_credential: old.invite_credential,
+				evaluation: Default::default(),
+			})
+		});
+
+		StorageVersion::new(7).put::<Pallet<T>>();
+		T::DbWeight::get().reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+	}
+}
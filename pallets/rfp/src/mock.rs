@@ -1,7 +1,8 @@
 use crate as pallet_rfp;
 use frame_support::{
 	parameter_types,
-	traits::{ConstU16, ConstU64},
+	traits::{ConstU8, ConstU16, ConstU32, ConstU64, ConstU128},
+	PalletId,
 };
 use frame_system as system;
 use sp_core::H256;
@@ -30,6 +31,8 @@ frame_support::construct_runtime!(
         PalletPayments: pallet_payments,
 		Balances: pallet_balances,
 		EscrowModule: pallet_escrow::{Pallet, Call, Storage, Event<T>},
+		NTNFTModule: pallet_ntnft::{Pallet, Call, Storage, Event<T>},
+		Assets: pallet_assets,
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 	}
 );
@@ -67,6 +70,24 @@ impl pallet_rfp::Config for Test {
 	type Currency = Balances;
 	type Cid = [u8; 59];
 	type BidId = u32;
+	type MaxRFPDeadlinesPerBlock = ConstU32<50>;
+	type BidDeposit = ConstU128<10>;
+	type MaxBidsCleanedPerCall = ConstU32<50>;
+	type MaxLotsPerRFP = ConstU32<10>;
+	type MaxCategoryTags = ConstU32<5>;
+	type MaxCategoryTagLength = ConstU32<32>;
+	type CredentialId = u128;
+	type Credentials = NTNFTModule;
+	type MaxRequiredCredentials = ConstU32<5>;
+	type MaxEvaluationCriteria = ConstU32<5>;
+	type MaxCommitteeMembers = ConstU32<5>;
+	type MaxCriterionScore = ConstU8<10>;
+	type RevealPeriod = ConstU64<5>;
+	type MaxBidsPerRFP = ConstU32<60>;
+	type MaxSealedBidsPerRFP = ConstU32<10>;
+	type MaxBidVersions = ConstU32<10>;
+	type MaxRFPAmendments = ConstU32<10>;
+	type MaxRFPInvitees = ConstU32<2>;
 }
 
 impl pallet_balances::Config for Test {
@@ -87,11 +108,47 @@ impl pallet_payments::Config for Test {
 	type RFPReferenceId = u32;
 	type PaymentCurrency = Balances;
 	type TimeProvider = pallet_timestamp::Pallet<Test>;
+	type Assets = Assets;
+	type Escrow = EscrowModule;
+	type PaymentProposalExpiry = ConstU64<86400>;
+	type MaxBatchSize = ConstU32<50>;
+	type CredentialCollectionId = u128;
+	type Credentials = NTNFTModule;
+}
+
+parameter_types! {
+	pub const EscrowPalletId: PalletId = PalletId(*b"py/escrw");
 }
 
 impl pallet_escrow::Config for Test {
 	type Event = Event;
 	type EscrowCurrency = Balances;
+	type AssetId = u32;
+	type EscrowAssets = Assets;
+	type PalletId = EscrowPalletId;
+}
+
+impl pallet_ntnft::Config for Test {
+	type Event = Event;
+	type CollectionId = u128;
+	type ItemId = u128;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<1>;
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type ApprovalDeposit = ConstU128<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
 }
 
 impl pallet_timestamp::Config for Test {
@@ -111,9 +168,14 @@ parameter_types! {
 }
 
 pub fn test_externalities() -> sp_io::TestExternalities {
-    let t = frame_system::GenesisConfig::default()
+    let mut t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
+    // Fund the RFP owner and bidder used in tests, so bids can reserve their deposits
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(24601, 1_000_000), (22222, 1_000_000)],
+    }.assimilate_storage(&mut t)
+    .unwrap();
     let mut externalities = sp_io::TestExternalities::new(t);
     externalities.execute_with(|| System::set_block_number(1));
     externalities
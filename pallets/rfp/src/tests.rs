@@ -1,7 +1,11 @@
 use crate::*;
 use frame_support::{
-    assert_ok, assert_noop, bounded_vec
+    assert_ok, assert_noop, bounded_vec,
+    storage::unhashed,
+    traits::{Currency, Get, Hooks, StorageVersion},
 };
+use sp_runtime::traits::Hash;
+use codec::Encode;
 use mock::*;
 
 const ACCOUNT_ID: u64 = 24601;
@@ -10,10 +14,12 @@ const RFP_ID: u32 = 1410;
 const OTHER_RFP_ID: u32 = 999;
 const BID_ID: u32 = 1111;
 const OTHER_BID_ID: u32 = 12345;
+const COPYING_BIDDER_ID: u64 = 33333;
+const COPIED_BID_ID: u32 = 2222;
 const BID_AMOUNT: u128 = 1999;
 const NEW_BID_AMOUNT: u128 = 1525;
-const PAYMENT_ID: u32 = 0001;
-const RFP_REFERENCE_ID: u32 = 1410;
+const PAYMENT_ID: u32 = 0;
+const LOT: u32 = 0;
 const RFP_CID: &str = "bafkreidgvpkjawlxz6sffxzwgooowe5yt7i6wsyg236mfoks77nywkptdq";
 const OTHER_CID: &str = "bafkreidgvpkjawlxz6sffxzwgooowe5yt7i6wsyg236mfoks77nywkptpg";
 const BID_CID: &str = "bafkreidgvpkjawlxz6sffxzwgooowe5yt7i6wsyg236mfoks77nywkpabc";
@@ -30,7 +36,18 @@ fn test_create_rfp() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -61,7 +78,18 @@ fn test_re_create_rfp_fails() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -79,6 +107,58 @@ fn test_re_create_rfp_fails() {
     })
 }
 
+#[test]
+fn test_create_rfp_is_owned_by_caller_and_accepts_bids() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptedBid,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_noop!(
+            RFPModule::create_rfp(
+                Origin::signed(BIDDER_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ),
+            Error::<Test>::RFPOwnerMismatch
+        );
+        assert_noop!(
+            RFPModule::create_rfp_with_auto_id(
+                Origin::signed(BIDDER_ID),
+                rfp_details.clone(),
+            ),
+            Error::<Test>::RFPOwnerMismatch
+        );
+
+        // An RFP can't be created already decided
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        assert_eq!(
+            RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap().rfp_status,
+            RFPStatus::AcceptingBids
+        );
+    })
+}
+
 #[test]
 fn test_update_rfp_succeeds() {
     let mut t = test_externalities();
@@ -90,7 +170,18 @@ fn test_update_rfp_succeeds() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -102,7 +193,18 @@ fn test_update_rfp_succeeds() {
         let new_rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash: new_ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::update_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -114,11 +216,19 @@ fn test_update_rfp_succeeds() {
                 crate::Event::UpdateRFP(
                     ACCOUNT_ID, 
                     RFP_ID,
+                    1,
                 )
         ));
         let stored_details = 
             RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap();
         assert_eq!(stored_details, new_rfp_details);
+
+        // The update is kept as an amendment, along with the previous CID
+        assert_eq!(RFPModule::rfp_amendment_count(ACCOUNT_ID, RFP_ID), 1);
+        assert_eq!(
+            RFPModule::rfp_amendments((ACCOUNT_ID, RFP_ID, 1u32)).unwrap().previous_ipfs_hash,
+            ipfs_hash
+        );
     })
 }
 
@@ -133,7 +243,18 @@ fn test_update_rfp_fails_if_rfp_doesnt_exist() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_noop!(
             RFPModule::update_rfp(
@@ -157,7 +278,18 @@ fn test_cancel_rfp() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -208,7 +340,18 @@ fn test_bid_on_rfp() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -221,6 +364,8 @@ fn test_bid_on_rfp() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -241,7 +386,7 @@ fn test_bid_on_rfp() {
             RFPModule::all_bids(BID_ID).unwrap();
         assert_eq!(stored_bid, bid_details);
         let bids_for_rfp = 
-            RFPModule::rfp_to_bids(RFP_ID).unwrap();
+            RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap();
         assert!(bids_for_rfp.contains(&BID_ID));
     })
 }
@@ -258,6 +403,8 @@ fn test_bid_on_rfp_fails_if_rfp_doesnt_exist() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
 
         assert_noop!(
@@ -284,7 +431,18 @@ fn test_bid_on_rfp_fails_with_existing_bid() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -297,6 +455,8 @@ fn test_bid_on_rfp_fails_with_existing_bid() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -330,19 +490,34 @@ fn test_bid_on_rfp_fails_if_rfp_not_accepting_bids() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::NotAcceptingNewBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
             RFP_ID,
             rfp_details.clone(),
         ));
+        System::set_block_number(100);
+        RFPModule::on_initialize(100);
         let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
         let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
         let bid_details = BidDetails::<Test> {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_noop!(
             RFPModule::bid_on_rfp(
@@ -368,7 +543,18 @@ fn test_shortlist_bid() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -381,6 +567,8 @@ fn test_shortlist_bid() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -403,7 +591,7 @@ fn test_shortlist_bid() {
                 )
         ));
         let shortlisted_bids = 
-            RFPModule::rfp_to_shortlisted_bids(RFP_ID).unwrap();
+            RFPModule::rfp_to_shortlisted_bids(ACCOUNT_ID, RFP_ID).unwrap();
         assert!(shortlisted_bids.contains(&BID_ID));
     })
 }
@@ -419,7 +607,18 @@ fn test_shortlist_multiple_bids() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -432,6 +631,8 @@ fn test_shortlist_multiple_bids() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -446,6 +647,8 @@ fn test_shortlist_multiple_bids() {
             bid_owner: BIDDER_ID,
             ipfs_hash: other_bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -466,7 +669,7 @@ fn test_shortlist_multiple_bids() {
             OTHER_BID_ID
         ));
         let shortlisted_bids = 
-            RFPModule::rfp_to_shortlisted_bids(RFP_ID).unwrap();
+            RFPModule::rfp_to_shortlisted_bids(ACCOUNT_ID, RFP_ID).unwrap();
         assert!(shortlisted_bids.contains(&BID_ID));
         assert!(shortlisted_bids.contains(&OTHER_BID_ID));
     })
@@ -500,7 +703,18 @@ fn test_shortlist_bid_fails_if_bid_non_existent() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -529,7 +743,18 @@ fn test_shortlist_bid_fails_if_rfp_has_no_such_bid() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -542,6 +767,8 @@ fn test_shortlist_bid_fails_if_rfp_has_no_such_bid() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -555,7 +782,18 @@ fn test_shortlist_bid_fails_if_rfp_has_no_such_bid() {
         let new_rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash: new_ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -568,6 +806,8 @@ fn test_shortlist_bid_fails_if_rfp_has_no_such_bid() {
             bid_owner: BIDDER_ID,
             ipfs_hash: other_bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -598,7 +838,18 @@ fn test_no_bids_for_rfp() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -611,6 +862,8 @@ fn test_no_bids_for_rfp() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -624,7 +877,18 @@ fn test_no_bids_for_rfp() {
         let new_rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash: new_ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -653,7 +917,18 @@ fn test_update_rfp_bid() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -666,6 +941,8 @@ fn test_update_rfp_bid() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -680,12 +957,15 @@ fn test_update_rfp_bid() {
             bid_owner: BIDDER_ID,
             ipfs_hash: other_bid_hash,
             bid_amount: NEW_BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 1,
         };
         assert_ok!(RFPModule::update_rfp_bid(
             Origin::signed(BIDDER_ID),
             RFP_ID,
             BID_ID,
-            updated_bid_details.clone(),
+            other_bid_hash,
+            NEW_BID_AMOUNT,
         ));
         let stored_bid = 
             RFPModule::all_bids(BID_ID).unwrap();
@@ -695,9 +975,114 @@ fn test_update_rfp_bid() {
                 crate::Event::UpdateRFPBid(
                     BIDDER_ID, 
                     RFP_ID,
-                    BID_ID
+                    BID_ID,
+                    1
                 )
         ));
+        // Both versions of the bid are kept
+        assert_eq!(RFPModule::bid_history(BID_ID, 0).unwrap().bid_amount, BID_AMOUNT);
+        assert_eq!(RFPModule::bid_history(BID_ID, 1).unwrap().bid_amount, NEW_BID_AMOUNT);
+
+        // A bid can only be updated through the RFP it was made on
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            OTHER_RFP_ID,
+            rfp_details,
+        ));
+        assert_noop!(
+            RFPModule::update_rfp_bid(
+                Origin::signed(BIDDER_ID),
+                OTHER_RFP_ID,
+                BID_ID,
+                bid_cid_hash,
+                BID_AMOUNT,
+            ),
+            Error::<Test>::NoSuchBidForRFP
+        );
+    })
+}
+
+#[test]
+fn test_bid_versions_and_rfp_amendments_are_bounded() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            BidDetails::<Test> {
+                bid_owner: BIDDER_ID,
+                ipfs_hash: bid_cid_hash,
+                bid_amount: BID_AMOUNT,
+                bid_status: BidStatus::Submitted,
+                version: 0,
+            }
+        ));
+
+        let max_bid_versions: u32 = <Test as Config>::MaxBidVersions::get();
+        for _ in 1..max_bid_versions {
+            assert_ok!(RFPModule::update_rfp_bid(
+                Origin::signed(BIDDER_ID),
+                RFP_ID,
+                BID_ID,
+                bid_cid_hash,
+                NEW_BID_AMOUNT,
+            ));
+        }
+        assert_noop!(
+            RFPModule::update_rfp_bid(
+                Origin::signed(BIDDER_ID),
+                RFP_ID,
+                BID_ID,
+                bid_cid_hash,
+                NEW_BID_AMOUNT,
+            ),
+            Error::<Test>::BidVersionOverflow
+        );
+
+        let max_rfp_amendments: u32 = <Test as Config>::MaxRFPAmendments::get();
+        for _ in 0..max_rfp_amendments {
+            assert_ok!(RFPModule::update_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ));
+        }
+        assert_noop!(
+            RFPModule::update_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details,
+            ),
+            Error::<Test>::RFPAmendmentOverflow
+        );
     })
 }
 
@@ -712,7 +1097,18 @@ fn test_update_fails_if_updater_not_owner() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -725,6 +1121,8 @@ fn test_update_fails_if_updater_not_owner() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -735,17 +1133,13 @@ fn test_update_fails_if_updater_not_owner() {
         ));
         let other_bid_cid: Vec<u8> = OTHER_BID_CID.as_bytes().to_vec();
         let other_bid_hash: [u8; 59] = other_bid_cid.try_into().unwrap();
-        let updated_bid_details = BidDetails::<Test> {
-            bid_owner: BIDDER_ID,
-            ipfs_hash: other_bid_hash,
-            bid_amount: NEW_BID_AMOUNT,
-        };
         assert_noop!(
             RFPModule::update_rfp_bid(
                 Origin::signed(ACCOUNT_ID),
                 RFP_ID,
                 BID_ID,
-                updated_bid_details.clone(),
+                other_bid_hash,
+                NEW_BID_AMOUNT,
             ),
             Error::<Test>::UnauthorizedUpdateOfBid
         );
@@ -763,7 +1157,18 @@ fn test_update_fails_if_bid_doesnt_exist() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -772,17 +1177,13 @@ fn test_update_fails_if_bid_doesnt_exist() {
         ));
         let other_bid_cid: Vec<u8> = OTHER_BID_CID.as_bytes().to_vec();
         let other_bid_hash: [u8; 59] = other_bid_cid.try_into().unwrap();
-        let updated_bid_details = BidDetails::<Test> {
-            bid_owner: BIDDER_ID,
-            ipfs_hash: other_bid_hash,
-            bid_amount: NEW_BID_AMOUNT,
-        };
         assert_noop!(
             RFPModule::update_rfp_bid(
                 Origin::signed(BIDDER_ID),
                 RFP_ID,
                 BID_ID,
-                updated_bid_details.clone(),
+                other_bid_hash,
+                NEW_BID_AMOUNT,
             ),
             Error::<Test>::UpdatingNonExistentBid
         );
@@ -795,27 +1196,12 @@ fn test_accept_rfp_bid_fails_if_no_rfp() {
     t.execute_with(||
     {
         assert!(System::events().is_empty());
-        let payment_schedule = bounded_vec![];
-        let payment_method = pallet_payments::PaymentMethod::<Test>{
-            payment_source: pallet_payments::PaymentSource::PersonalAccount,
-            account_id: ACCOUNT_ID,
-        };
-        let payment_details = pallet_payments::PaymentDetails::<Test> {
-            payer: ACCOUNT_ID,
-            payee: BIDDER_ID,
-            payment_id: PAYMENT_ID,
-            rfp_reference_id: RFP_REFERENCE_ID,
-            total_payment_amount: BID_AMOUNT.into(),
-            payment_schedule,
-            payment_method: payment_method.clone(),
-            administrator_id: ACCOUNT_ID,
-        };
         assert_noop!(
             RFPModule::accept_rfp_bid(
                 Origin::signed(ACCOUNT_ID),
                 RFP_ID,
                 BID_ID,
-                payment_details
+                LOT
             ),
             Error::<Test>::NonExistentRFP
         );
@@ -833,7 +1219,18 @@ fn test_accept_rfp_bid_fails_if_bid_not_shortlisted() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -846,6 +1243,8 @@ fn test_accept_rfp_bid_fails_if_bid_not_shortlisted() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -859,27 +1258,12 @@ fn test_accept_rfp_bid_fails_if_bid_not_shortlisted() {
             RFP_ID,
             BID_ID
         ));
-        let payment_schedule = bounded_vec![];
-        let payment_method = pallet_payments::PaymentMethod::<Test>{
-            payment_source: pallet_payments::PaymentSource::PersonalAccount,
-            account_id: ACCOUNT_ID,
-        };
-        let payment_details = pallet_payments::PaymentDetails::<Test> {
-            payer: ACCOUNT_ID,
-            payee: BIDDER_ID,
-            payment_id: PAYMENT_ID,
-            rfp_reference_id: RFP_REFERENCE_ID,
-            total_payment_amount: BID_AMOUNT.into(),
-            payment_schedule,
-            payment_method: payment_method.clone(),
-            administrator_id: ACCOUNT_ID,
-        };
         assert_noop!(
             RFPModule::accept_rfp_bid(
                 Origin::signed(ACCOUNT_ID),
                 RFP_ID,
                 OTHER_BID_ID,
-                payment_details.clone()
+                LOT
             ),
             Error::<Test>::AcceptedBidNotShortlisted
         );
@@ -897,7 +1281,18 @@ fn test_accept_rfp_bid_fails_if_bid_doesnt_exist() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -910,6 +1305,8 @@ fn test_accept_rfp_bid_fails_if_bid_doesnt_exist() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -918,27 +1315,12 @@ fn test_accept_rfp_bid_fails_if_bid_doesnt_exist() {
             BID_ID,
             bid_details.clone()
         ));
-        let payment_schedule = bounded_vec![];
-        let payment_method = pallet_payments::PaymentMethod::<Test>{
-            payment_source: pallet_payments::PaymentSource::PersonalAccount,
-            account_id: ACCOUNT_ID,
-        };
-        let payment_details = pallet_payments::PaymentDetails::<Test> {
-            payer: ACCOUNT_ID,
-            payee: BIDDER_ID,
-            payment_id: PAYMENT_ID,
-            rfp_reference_id: RFP_REFERENCE_ID,
-            total_payment_amount: BID_AMOUNT.into(),
-            payment_schedule,
-            payment_method: payment_method.clone(),
-            administrator_id: ACCOUNT_ID,
-        };
         assert_noop!(
             RFPModule::accept_rfp_bid(
                 Origin::signed(ACCOUNT_ID),
                 RFP_ID,
                 OTHER_BID_ID,
-                payment_details.clone()
+                LOT
             ),
             Error::<Test>::NoSuchBidForRFP
         );
@@ -956,7 +1338,18 @@ fn test_accept_rfp_bid() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -969,6 +1362,8 @@ fn test_accept_rfp_bid() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -982,47 +1377,67 @@ fn test_accept_rfp_bid() {
             RFP_ID,
             BID_ID
         ));
-        let payment_schedule = bounded_vec![];
-        let payment_method = pallet_payments::PaymentMethod::<Test>{
-            payment_source: pallet_payments::PaymentSource::PersonalAccount,
-            account_id: ACCOUNT_ID,
-        };
-        let payment_details = pallet_payments::PaymentDetails::<Test> {
-            payer: ACCOUNT_ID,
-            payee: BIDDER_ID,
-            payment_id: PAYMENT_ID,
-            rfp_reference_id: RFP_REFERENCE_ID,
-            total_payment_amount: BID_AMOUNT.into(),
-            payment_schedule,
-            payment_method: payment_method.clone(),
-            administrator_id: ACCOUNT_ID,
-        };
         assert_ok!(RFPModule::accept_rfp_bid(
             Origin::signed(ACCOUNT_ID),
             RFP_ID,
             BID_ID, 
-            payment_details
+            LOT
         ));
-        System::assert_last_event(
+        System::assert_has_event(
             mock::Event::RFPModule(
                 crate::Event::AcceptRFPBid(
                     ACCOUNT_ID, 
                     RFP_ID,
-                    BID_ID
+                    BID_ID,
+                    LOT
+                )
+        ));
+        // Awarding the only lot of the RFP completes it
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::RFPAwardsCompleted(
+                    ACCOUNT_ID, 
+                    RFP_ID,
                 )
         ));
+
+        // The winning bidder countersigns the proposed payment agreement
+        assert!(
+            PalletPayments::proposed_payment_agreements(
+                (ACCOUNT_ID, BIDDER_ID, PAYMENT_ID)
+            ).is_some()
+        );
+        assert!(
+            PalletPayments::payment_agreements(
+                (ACCOUNT_ID, BIDDER_ID, PAYMENT_ID)
+            ).is_none()
+        );
+        assert_ok!(PalletPayments::accept_payment(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            PAYMENT_ID
+        ));
         assert!(
             PalletPayments::payment_agreements(
                 (ACCOUNT_ID, BIDDER_ID, PAYMENT_ID)
             ).is_some()
         );
         let accepted_bid = 
-            RFPModule::rfp_to_accepted_bid(RFP_ID).unwrap();
+            RFPModule::rfp_to_accepted_bid((ACCOUNT_ID, RFP_ID, LOT)).unwrap();
         assert_eq!(accepted_bid, BID_ID);
         
         let stored_details = 
             RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap();
         assert_eq!(stored_details.rfp_status, RFPStatus::AcceptedBid);
+
+        // An RFP can't be cancelled once a bid has been accepted
+        assert_noop!(
+            RFPModule::cancel_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID
+            ),
+            Error::<Test>::BidAlreadyAccepted
+        );
     })
 }
 
@@ -1037,7 +1452,18 @@ fn test_accept_rfp_bid_again_fails() {
         let rfp_details = RFPDetails::<Test> {
             rfp_owner: ACCOUNT_ID,
             ipfs_hash,
-            rfp_status: RFPStatus::AcceptingBids
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
         };
         assert_ok!(RFPModule::create_rfp(
             Origin::signed(ACCOUNT_ID),
@@ -1050,6 +1476,8 @@ fn test_accept_rfp_bid_again_fails() {
             bid_owner: BIDDER_ID,
             ipfs_hash: bid_cid_hash,
             bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
         };
         assert_ok!(RFPModule::bid_on_rfp(
             Origin::signed(BIDDER_ID),
@@ -1063,35 +1491,2384 @@ fn test_accept_rfp_bid_again_fails() {
             RFP_ID,
             BID_ID
         ));
-        let payment_schedule = bounded_vec![];
-        let payment_method = pallet_payments::PaymentMethod::<Test> {
-            payment_source: pallet_payments::PaymentSource::PersonalAccount,
-            account_id: ACCOUNT_ID,
-        };
-        let payment_details = pallet_payments::PaymentDetails::<Test> {
-            payer: ACCOUNT_ID,
-            payee: BIDDER_ID,
-            payment_id: PAYMENT_ID,
-            rfp_reference_id: RFP_REFERENCE_ID,
-            total_payment_amount: BID_AMOUNT.into(),
-            payment_schedule,
-            payment_method: payment_method.clone(),
-            administrator_id: ACCOUNT_ID,
-        };
         assert_ok!(RFPModule::accept_rfp_bid(
             Origin::signed(ACCOUNT_ID),
             RFP_ID,
             BID_ID,
-            payment_details.clone()
+            LOT
         ));
         assert_noop!(
             RFPModule::accept_rfp_bid(
                 Origin::signed(ACCOUNT_ID),
                 RFP_ID,
                 BID_ID, 
-                payment_details.clone()
+                LOT
             ),
             Error::<Test>::BidAlreadyAccepted
         );
     })
+}
+
+#[test]
+fn test_create_rfp_and_bid_with_auto_id() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        // Ids chosen by the caller are skipped by the counter
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            0,
+            rfp_details.clone(),
+        ));
+        assert_ok!(RFPModule::create_rfp_with_auto_id(
+            Origin::signed(ACCOUNT_ID),
+            rfp_details.clone(),
+        ));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::CreateRFP(
+                    ACCOUNT_ID, 
+                    1,
+                )
+        ));
+        assert_eq!(RFPModule::get_rfps(ACCOUNT_ID, 1).unwrap(), rfp_details);
+
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            0,
+            0,
+            bid_details.clone()
+        ));
+        assert_ok!(RFPModule::bid_on_rfp_with_auto_id(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            1,
+            bid_details.clone()
+        ));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::BidOnRFP(
+                    BIDDER_ID, 
+                    1,
+                    1,
+                )
+        ));
+        assert_eq!(RFPModule::all_bids(1).unwrap(), bid_details);
+        assert!(RFPModule::rfp_to_bids(ACCOUNT_ID, 1).unwrap().contains(&1));
+        assert_eq!(RFPModule::bid_id_counter(), 2);
+    })
+}
+
+
+#[test]
+fn test_rfp_bidding_window_and_expiry() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 5,
+            bid_close: 5,
+            decision_deadline: Some(20),
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_noop!(
+            RFPModule::create_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ),
+            Error::<Test>::InvalidRFPSchedule
+        );
+        rfp_details.bid_close = 10;
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        assert!(RFPModule::rfps_closing_at(10).contains(&(ACCOUNT_ID, RFP_ID)));
+        assert!(RFPModule::rfps_expiring_at(20).contains(&(ACCOUNT_ID, RFP_ID)));
+
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                BID_ID,
+                bid_details.clone()
+            ),
+            Error::<Test>::BiddingNotOpen
+        );
+        System::set_block_number(5);
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            bid_details.clone()
+        ));
+
+        // Bidding closes in the hook of the bid-close block
+        System::set_block_number(10);
+        RFPModule::on_initialize(10);
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::RFPBiddingClosed(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                )
+        ));
+        assert_eq!(
+            RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap().rfp_status,
+            RFPStatus::NotAcceptingNewBids
+        );
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                OTHER_BID_ID,
+                bid_details
+            ),
+            Error::<Test>::RFPNotAcceptingBids
+        );
+
+        // Without an accepted bid, the RFP expires at its decision deadline
+        System::set_block_number(20);
+        RFPModule::on_initialize(20);
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::RFPExpired(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                )
+        ));
+        assert_eq!(
+            RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap().rfp_status,
+            RFPStatus::Expired
+        );
+        assert!(RFPModule::rfps_expiring_at(20).is_empty());
+    })
+}
+
+#[test]
+fn test_rescheduled_rfp_leaves_its_previous_deadlines() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 10,
+            decision_deadline: Some(20),
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            OTHER_RFP_ID,
+            rfp_details.clone(),
+        ));
+
+        rfp_details.bid_close = 15;
+        rfp_details.decision_deadline = Some(30);
+        assert_ok!(RFPModule::update_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        assert_eq!(RFPModule::rfps_closing_at(10).into_inner(), vec![(ACCOUNT_ID, OTHER_RFP_ID)]);
+        assert_eq!(RFPModule::rfps_expiring_at(20).into_inner(), vec![(ACCOUNT_ID, OTHER_RFP_ID)]);
+        assert_eq!(RFPModule::rfps_closing_at(15).into_inner(), vec![(ACCOUNT_ID, RFP_ID)]);
+        assert_eq!(RFPModule::rfps_expiring_at(30).into_inner(), vec![(ACCOUNT_ID, RFP_ID)]);
+
+        // Dropping the decision deadline unschedules the RFP's expiry
+        rfp_details.decision_deadline = None;
+        assert_ok!(RFPModule::update_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        assert!(RFPModule::rfps_expiring_at(30).is_empty());
+        assert_eq!(RFPModule::rfps_closing_at(15).into_inner(), vec![(ACCOUNT_ID, RFP_ID)]);
+    })
+}
+
+
+#[test]
+fn test_sealed_bids_are_committed_then_revealed() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 10,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Sealed,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        let salt = [7u8; 32];
+        let commitment = |bid_id: u32| <Test as frame_system::Config>::Hashing::hash_of(
+            &(BIDDER_ID, ACCOUNT_ID, RFP_ID, bid_id, BID_AMOUNT, bid_cid_hash, salt)
+        );
+
+        // Bids on a sealed RFP can't be made in the clear
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                BID_ID,
+                bid_details.clone()
+            ),
+            Error::<Test>::RFPRequiresSealedBids
+        );
+        assert_ok!(RFPModule::commit_sealed_bid(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            commitment(BID_ID)
+        ));
+        assert_ok!(RFPModule::commit_sealed_bid(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            OTHER_BID_ID,
+            commitment(OTHER_BID_ID)
+        ));
+        let _ = Balances::deposit_creating(&COPYING_BIDDER_ID, 1_000);
+        assert_ok!(RFPModule::commit_sealed_bid(
+            Origin::signed(COPYING_BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            COPIED_BID_ID,
+            commitment(BID_ID)
+        ));
+        assert!(RFPModule::all_bids(BID_ID).is_none());
+        assert_noop!(
+            RFPModule::reveal_bid(
+                Origin::signed(BIDDER_ID),
+                BID_ID,
+                bid_details.clone(),
+                salt
+            ),
+            Error::<Test>::NotRevealPeriod
+        );
+
+        System::set_block_number(10);
+        RFPModule::on_initialize(10);
+        assert_ok!(RFPModule::reveal_bid(
+            Origin::signed(BIDDER_ID),
+            BID_ID,
+            bid_details.clone(),
+            salt
+        ));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::BidRevealed(
+                    BIDDER_ID, 
+                    RFP_ID,
+                    BID_ID,
+                )
+        ));
+        assert_eq!(RFPModule::all_bids(BID_ID).unwrap(), bid_details);
+        assert!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().contains(&BID_ID));
+
+        // A commitment copied from another bidder can't be revealed with their bid
+        let mut copied_bid_details = bid_details.clone();
+        copied_bid_details.bid_owner = COPYING_BIDDER_ID;
+        assert_ok!(RFPModule::reveal_bid(
+            Origin::signed(COPYING_BIDDER_ID),
+            COPIED_BID_ID,
+            copied_bid_details,
+            salt
+        ));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::BidDisqualified(
+                    COPYING_BIDDER_ID, 
+                    RFP_ID,
+                    COPIED_BID_ID,
+                )
+        ));
+        assert!(RFPModule::all_bids(COPIED_BID_ID).is_none());
+
+        // A reveal that doesn't match the commitment disqualifies the bid
+        assert_ok!(RFPModule::reveal_bid(
+            Origin::signed(BIDDER_ID),
+            OTHER_BID_ID,
+            bid_details,
+            [0u8; 32]
+        ));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::BidDisqualified(
+                    BIDDER_ID, 
+                    RFP_ID,
+                    OTHER_BID_ID,
+                )
+        ));
+        assert!(RFPModule::sealed_bids(OTHER_BID_ID).is_none());
+
+        // Bids can only be shortlisted once the reveal period has ended
+        assert_noop!(
+            RFPModule::shortlist_bid(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                BID_ID
+            ),
+            Error::<Test>::RevealPeriodNotOver
+        );
+        System::set_block_number(15);
+        assert_noop!(
+            RFPModule::shortlist_bid(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                OTHER_BID_ID
+            ),
+            Error::<Test>::ShortlistingNonExistentBid
+        );
+        assert_ok!(RFPModule::shortlist_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            BID_ID
+        ));
+    })
+}
+
+#[test]
+fn test_sealed_bids_are_disqualified_outside_the_budget_or_reveal_period() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 10,
+            decision_deadline: Some(12),
+            bidding_mode: BiddingMode::Sealed,
+            bid_bond: 100,
+            lots: 1,
+            requirements: RFPRequirements::<Test> {
+                budget_min: None,
+                budget_max: Some(1000),
+                asset_id: None,
+                category_tags: bounded_vec![],
+                required_credentials: bounded_vec![],
+            },
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+
+        // A sealed RFP can't be decided before its reveal period has ended
+        assert_noop!(
+            RFPModule::create_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ),
+            Error::<Test>::InvalidRFPSchedule
+        );
+        rfp_details.decision_deadline = Some(20);
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        let salt = [7u8; 32];
+        let commitment = |bid_id: u32| <Test as frame_system::Config>::Hashing::hash_of(
+            &(BIDDER_ID, ACCOUNT_ID, RFP_ID, bid_id, BID_AMOUNT, bid_cid_hash, salt)
+        );
+        assert_ok!(RFPModule::commit_sealed_bid(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            commitment(BID_ID)
+        ));
+        assert_ok!(RFPModule::commit_sealed_bid(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            OTHER_BID_ID,
+            commitment(OTHER_BID_ID)
+        ));
+        let bid_deposit: u128 = <Test as Config>::BidDeposit::get();
+
+        // A bid revealed outside the budget is disqualified, and its bond slashed
+        System::set_block_number(10);
+        RFPModule::on_initialize(10);
+        assert_ok!(RFPModule::reveal_bid(
+            Origin::signed(BIDDER_ID),
+            BID_ID,
+            bid_details.clone(),
+            salt
+        ));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::BidDisqualified(
+                    BIDDER_ID, 
+                    RFP_ID,
+                    BID_ID,
+                )
+        ));
+        assert!(RFPModule::all_bids(BID_ID).is_none());
+        assert!(RFPModule::sealed_bids(BID_ID).is_none());
+        assert_eq!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().to_vec(), vec![OTHER_BID_ID]);
+        assert_eq!(Balances::reserved_balance(BIDDER_ID), 100 + bid_deposit);
+
+        // Bids can't be revealed once the reveal period has ended
+        System::set_block_number(15);
+        assert_noop!(
+            RFPModule::reveal_bid(
+                Origin::signed(BIDDER_ID),
+                OTHER_BID_ID,
+                bid_details,
+                salt
+            ),
+            Error::<Test>::NotRevealPeriod
+        );
+
+        // The bond of the bid that was never revealed is slashed once the RFP expires
+        System::set_block_number(20);
+        RFPModule::on_initialize(20);
+        System::assert_has_event(
+            mock::Event::RFPModule(
+                crate::Event::BidBondSlashed(
+                    BIDDER_ID, 
+                    RFP_ID,
+                    OTHER_BID_ID,
+                )
+        ));
+        assert!(RFPModule::sealed_bids(OTHER_BID_ID).is_none());
+        assert!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().is_empty());
+        assert!(RFPModule::rfp_unrevealed_bids(ACCOUNT_ID, RFP_ID).is_empty());
+        assert_eq!(Balances::reserved_balance(BIDDER_ID), 0);
+        assert_eq!(Balances::free_balance(BIDDER_ID), 1_000_000 - 200);
+    })
+}
+
+#[test]
+fn test_bid_bonds_are_released_or_slashed_once_rfp_is_decided() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 10,
+            decision_deadline: Some(20),
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 100,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        rfp_details.bidding_mode = BiddingMode::Sealed;
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            OTHER_RFP_ID,
+            rfp_details,
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            bid_details
+        ));
+        assert_ok!(RFPModule::commit_sealed_bid(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            OTHER_RFP_ID,
+            OTHER_BID_ID,
+            Default::default()
+        ));
+        let bid_deposit: u128 = <Test as Config>::BidDeposit::get();
+        assert_eq!(Balances::reserved_balance(BIDDER_ID), 2 * (100 + bid_deposit));
+        assert_noop!(
+            RFPModule::release_bid_bond(Origin::signed(BIDDER_ID), BID_ID),
+            Error::<Test>::RFPNotDecided
+        );
+
+        // The bond of a sealed bid that was never revealed
+        // is slashed as soon as the RFP is decided
+        System::set_block_number(20);
+        RFPModule::on_initialize(10);
+        RFPModule::on_initialize(20);
+        System::assert_has_event(
+            mock::Event::RFPModule(
+                crate::Event::BidBondSlashed(
+                    BIDDER_ID, 
+                    OTHER_RFP_ID,
+                    OTHER_BID_ID,
+                )
+        ));
+        assert!(RFPModule::sealed_bids(OTHER_BID_ID).is_none());
+        assert!(RFPModule::rfp_to_bids(ACCOUNT_ID, OTHER_RFP_ID).unwrap().is_empty());
+        assert_eq!(Balances::reserved_balance(BIDDER_ID), 100 + bid_deposit);
+        assert_noop!(
+            RFPModule::release_bid_bond(Origin::signed(BIDDER_ID), OTHER_BID_ID),
+            Error::<Test>::NonExistentBidBond
+        );
+
+        assert_noop!(
+            RFPModule::release_bid_bond(Origin::signed(ACCOUNT_ID), BID_ID),
+            Error::<Test>::UnauthorizedReleaseOfBidBond
+        );
+        assert_ok!(RFPModule::release_bid_bond(Origin::signed(BIDDER_ID), BID_ID));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::BidBondReleased(
+                    BIDDER_ID, 
+                    RFP_ID,
+                    BID_ID,
+                )
+        ));
+        assert!(RFPModule::all_bids(BID_ID).is_none());
+        assert_eq!(Balances::reserved_balance(BIDDER_ID), 0);
+        assert_eq!(Balances::free_balance(BIDDER_ID), 1_000_000 - 100);
+    })
+}
+
+#[test]
+fn test_bids_can_be_withdrawn_or_rejected() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 100,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            bid_details.clone()
+        ));
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            OTHER_BID_ID,
+            bid_details.clone()
+        ));
+
+        // A shortlisted bid can no longer be updated
+        assert_ok!(RFPModule::shortlist_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            BID_ID
+        ));
+        assert_eq!(RFPModule::all_bids(BID_ID).unwrap().bid_status, BidStatus::Shortlisted);
+        assert_noop!(
+            RFPModule::shortlist_bid(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                BID_ID
+            ),
+            Error::<Test>::BidAlreadyShortlisted
+        );
+        assert_noop!(
+            RFPModule::update_rfp_bid(
+                Origin::signed(BIDDER_ID),
+                RFP_ID,
+                BID_ID,
+                bid_cid_hash,
+                NEW_BID_AMOUNT
+            ),
+            Error::<Test>::BidNoLongerUpdatable
+        );
+
+        // Rejected bids get their bond back
+        assert_noop!(
+            RFPModule::reject_bid(Origin::signed(BIDDER_ID), OTHER_BID_ID),
+            Error::<Test>::UnauthorizedRejectionOfBid
+        );
+        assert_ok!(RFPModule::reject_bid(Origin::signed(ACCOUNT_ID), OTHER_BID_ID));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::BidRejected(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                    OTHER_BID_ID,
+                )
+        ));
+        // A closed bid's storage goes along with its storage deposit
+        assert!(RFPModule::all_bids(OTHER_BID_ID).is_none());
+        assert!(RFPModule::bid_to_rfp(OTHER_BID_ID).is_none());
+        assert!(RFPModule::bid_history(OTHER_BID_ID, 0).is_none());
+        assert!(!RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().contains(&OTHER_BID_ID));
+
+        // Withdrawn bids forfeit their bond
+        assert_noop!(
+            RFPModule::withdraw_bid(Origin::signed(ACCOUNT_ID), BID_ID),
+            Error::<Test>::UnauthorizedWithdrawalOfBid
+        );
+        assert_ok!(RFPModule::withdraw_bid(Origin::signed(BIDDER_ID), BID_ID));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::BidWithdrawn(
+                    BIDDER_ID, 
+                    RFP_ID,
+                    BID_ID,
+                )
+        ));
+        assert!(RFPModule::all_bids(BID_ID).is_none());
+        assert!(RFPModule::bid_to_rfp(BID_ID).is_none());
+        assert!(!BidAcknowledgedAmendment::<Test>::contains_key(BID_ID));
+        assert!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().is_empty());
+        assert!(!RFPModule::rfp_to_shortlisted_bids(ACCOUNT_ID, RFP_ID).unwrap().contains(&BID_ID));
+        assert_noop!(
+            RFPModule::withdraw_bid(Origin::signed(BIDDER_ID), BID_ID),
+            Error::<Test>::NonExistentBid
+        );
+        assert_noop!(
+            RFPModule::release_bid_bond(Origin::signed(BIDDER_ID), BID_ID),
+            Error::<Test>::NonExistentBidBond
+        );
+        assert_eq!(Balances::reserved_balance(BIDDER_ID), 0);
+        assert_eq!(Balances::free_balance(BIDDER_ID), 1_000_000 - 100);
+    })
+}
+
+#[test]
+fn test_cancel_rfp_cleans_up_bids_and_releases_bonds() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        System::set_block_number(1);
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 100,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        // One more bid than can be cleaned up in a single call
+        let max_cleaned: u32 = <Test as Config>::MaxBidsCleanedPerCall::get();
+        for bid_id in 0..=max_cleaned {
+            assert_ok!(RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                bid_id,
+                bid_details.clone()
+            ));
+        }
+        assert_ok!(RFPModule::shortlist_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            0
+        ));
+        let bid_deposit: u128 = <Test as Config>::BidDeposit::get();
+        assert_eq!(
+            Balances::reserved_balance(BIDDER_ID),
+            (max_cleaned as u128 + 1) * (100 + bid_deposit)
+        );
+
+        assert_noop!(
+            RFPModule::clean_up_cancelled_rfp(Origin::signed(BIDDER_ID), ACCOUNT_ID, RFP_ID),
+            Error::<Test>::NoRFPCleanupPending
+        );
+        assert_ok!(RFPModule::cancel_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID
+        ));
+        System::assert_has_event(
+            mock::Event::RFPModule(
+                crate::Event::BidCancelled(
+                    BIDDER_ID, 
+                    RFP_ID,
+                    max_cleaned,
+                )
+        ));
+        assert!(RFPModule::all_bids(max_cleaned).is_none());
+        assert!(RFPModule::bid_to_rfp(max_cleaned).is_none());
+        assert_eq!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().len(), 1);
+        assert_eq!(Balances::reserved_balance(BIDDER_ID), 100 + bid_deposit);
+
+        // The id can't be reused until every bid has been cleaned up
+        assert_noop!(
+            RFPModule::create_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ),
+            Error::<Test>::RFPCleanupPending
+        );
+        assert_ok!(RFPModule::clean_up_cancelled_rfp(Origin::signed(BIDDER_ID), ACCOUNT_ID, RFP_ID));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::RFPCleanupCompleted(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                )
+        ));
+        assert!(RFPModule::all_bids(0).is_none());
+        assert!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).is_none());
+        assert!(RFPModule::rfp_to_shortlisted_bids(ACCOUNT_ID, RFP_ID).is_none());
+        assert!(RFPModule::cancelled_rfps(ACCOUNT_ID, RFP_ID).is_none());
+        assert_eq!(Balances::reserved_balance(BIDDER_ID), 0);
+        assert_eq!(Balances::free_balance(BIDDER_ID), 1_000_000);
+
+        // A new RFP with the same id starts without any bids
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        assert!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().is_empty());
+    })
+}
+
+#[test]
+fn test_rfps_with_the_same_id_keep_separate_bids() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let mut bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            bid_details.clone()
+        ));
+
+        // Another owner creating an RFP with the same id
+        // doesn't reset the bids on the first RFP
+        rfp_details.rfp_owner = BIDDER_ID;
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(BIDDER_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        assert_eq!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().to_vec(), vec![BID_ID]);
+        assert!(RFPModule::rfp_to_bids(BIDDER_ID, RFP_ID).unwrap().is_empty());
+
+        bid_details.bid_owner = ACCOUNT_ID;
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(ACCOUNT_ID),
+            BIDDER_ID,
+            RFP_ID,
+            OTHER_BID_ID,
+            bid_details
+        ));
+        assert_eq!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().to_vec(), vec![BID_ID]);
+        assert_eq!(RFPModule::rfp_to_bids(BIDDER_ID, RFP_ID).unwrap().to_vec(), vec![OTHER_BID_ID]);
+
+        // Neither owner can shortlist a bid on the other's RFP
+        assert_noop!(
+            RFPModule::shortlist_bid(
+                Origin::signed(BIDDER_ID),
+                RFP_ID,
+                BID_ID
+            ),
+            Error::<Test>::NoSuchBidForRFP
+        );
+        assert_ok!(RFPModule::shortlist_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            BID_ID
+        ));
+        assert!(RFPModule::rfp_to_shortlisted_bids(BIDDER_ID, RFP_ID).is_none());
+
+        // Cancelling one RFP leaves the other one's bids alone
+        assert_ok!(RFPModule::cancel_rfp(
+            Origin::signed(BIDDER_ID),
+            RFP_ID
+        ));
+        assert!(RFPModule::all_bids(OTHER_BID_ID).is_none());
+        assert!(RFPModule::rfp_to_bids(BIDDER_ID, RFP_ID).is_none());
+        assert_eq!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().to_vec(), vec![BID_ID]);
+        assert_eq!(
+            RFPModule::rfp_to_shortlisted_bids(ACCOUNT_ID, RFP_ID).unwrap().to_vec(),
+            vec![BID_ID]
+        );
+    })
+}
+
+#[test]
+fn test_migrate_rfp_storage_from_baseline() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        System::set_block_number(5);
+
+        // Store RFPs and bids the way the first deployed version of the pallet
+        // encoded them, with three fields each and bid lists keyed by RFP id
+        let rfps = [
+            (ACCOUNT_ID, RFP_ID, RFPStatus::AcceptingBids),
+            (ACCOUNT_ID, OTHER_RFP_ID, RFPStatus::AcceptedBid),
+            (ACCOUNT_ID, 3, RFPStatus::AcceptingBids),
+            (BIDDER_ID, 3, RFPStatus::AcceptingBids),
+        ];
+        for (rfp_owner, rfp_id, rfp_status) in rfps {
+            unhashed::put_raw(
+                &migrations::v1::v0::RFPs::<Test>::hashed_key_for(rfp_owner, rfp_id),
+                &(rfp_owner, ipfs_hash, rfp_status).encode()
+            );
+        }
+        for bid_id in [BID_ID, OTHER_BID_ID, 7, 8] {
+            unhashed::put_raw(
+                &migrations::v1::v0::AllBids::<Test>::hashed_key_for(bid_id),
+                &(BIDDER_ID, bid_cid_hash, BID_AMOUNT).encode()
+            );
+        }
+        migrations::v1::v0::RFPToBids::<Test>::insert(RFP_ID, bounded_vec![BID_ID]);
+        migrations::v1::v0::RFPToShortlistedBids::<Test>::insert(RFP_ID, bounded_vec![BID_ID]);
+        migrations::v1::v0::RFPToBids::<Test>::insert(OTHER_RFP_ID, bounded_vec![OTHER_BID_ID, 7]);
+        migrations::v1::v0::RFPToAcceptedBid::<Test>::insert(OTHER_RFP_ID, OTHER_BID_ID);
+        // Two owners used the same RFP id, so this bid can't be attributed to either
+        migrations::v1::v0::RFPToBids::<Test>::insert(3, bounded_vec![8]);
+        StorageVersion::new(0).put::<RFPModule>();
+
+        RFPModule::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get::<RFPModule>(), StorageVersion::new(7));
+
+        // RFPs accepting bids keep accepting them, in a single lot
+        let rfp_details = RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap();
+        assert_eq!(rfp_details.rfp_status, RFPStatus::AcceptingBids);
+        assert_eq!(rfp_details.ipfs_hash, ipfs_hash);
+        assert_eq!(rfp_details.bid_close, u64::MAX);
+        assert_eq!(rfp_details.bidding_mode, BiddingMode::Open);
+        assert_eq!(rfp_details.lots, 1);
+        assert_eq!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().to_vec(), vec![BID_ID]);
+        assert_eq!(RFPModule::bid_to_rfp(BID_ID), Some((ACCOUNT_ID, RFP_ID)));
+        let bid_details = RFPModule::all_bids(BID_ID).unwrap();
+        assert_eq!(bid_details.bid_owner, BIDDER_ID);
+        assert_eq!(bid_details.bid_amount, BID_AMOUNT);
+        assert_eq!(bid_details.bid_status, BidStatus::Shortlisted);
+        assert_eq!(bid_details.version, 0);
+        assert_eq!(RFPModule::bid_history(BID_ID, 0).unwrap().submitted_at, 5);
+
+        // An accepted bid is awarded the RFP's only lot
+        let rfp_details = RFPModule::get_rfps(ACCOUNT_ID, OTHER_RFP_ID).unwrap();
+        assert_eq!(rfp_details.rfp_status, RFPStatus::AcceptedBid);
+        assert_eq!(rfp_details.bid_close, 5);
+        assert_eq!(RFPModule::rfp_to_accepted_bid((ACCOUNT_ID, OTHER_RFP_ID, 0)), Some(OTHER_BID_ID));
+        assert_eq!(RFPModule::rfp_lots_decided(ACCOUNT_ID, OTHER_RFP_ID), 1);
+        assert_eq!(RFPModule::all_bids(OTHER_BID_ID).unwrap().bid_status, BidStatus::Accepted);
+        assert_eq!(RFPModule::all_bids(7).unwrap().bid_status, BidStatus::Submitted);
+        assert_eq!(RFPModule::bid_to_rfp(7), Some((ACCOUNT_ID, OTHER_RFP_ID)));
+
+        // Bids under an RFP id several owners used are kept, but not listed under either RFP
+        assert!(RFPModule::rfp_to_bids(ACCOUNT_ID, 3).unwrap().is_empty());
+        assert!(RFPModule::rfp_to_bids(BIDDER_ID, 3).unwrap().is_empty());
+        assert_eq!(RFPModule::all_bids(8).unwrap().bid_amount, BID_AMOUNT);
+        assert_eq!(RFPModule::bid_to_rfp(8), None);
+
+        // Migrated RFPs take new bids
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            9,
+            bid_details
+        ));
+
+        // The migrations only run once
+        RFPModule::on_runtime_upgrade();
+        assert_eq!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().to_vec(), vec![BID_ID, 9]);
+    })
+}
+
+#[test]
+fn test_rfp_lots_are_awarded_separately() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 0,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_noop!(
+            RFPModule::create_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ),
+            Error::<Test>::InvalidRFPLots
+        );
+        rfp_details.lots = 3;
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            bid_details.clone()
+        ));
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            OTHER_BID_ID,
+            bid_details
+        ));
+
+        assert_noop!(
+            RFPModule::accept_rfp_bid(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                BID_ID,
+                3
+            ),
+            Error::<Test>::NonExistentLot
+        );
+        assert_ok!(RFPModule::accept_rfp_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            BID_ID,
+            0
+        ));
+        assert_eq!(
+            RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap().rfp_status,
+            RFPStatus::PartiallyAwarded
+        );
+
+        // Each lot is awarded once, and each bid wins a single lot
+        assert_noop!(
+            RFPModule::accept_rfp_bid(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                OTHER_BID_ID,
+                0
+            ),
+            Error::<Test>::LotAlreadyDecided
+        );
+        assert_noop!(
+            RFPModule::accept_rfp_bid(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                BID_ID,
+                1
+            ),
+            Error::<Test>::BidNotActive
+        );
+        assert_noop!(
+            RFPModule::cancel_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID
+            ),
+            Error::<Test>::BidAlreadyAccepted
+        );
+        assert_ok!(RFPModule::accept_rfp_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            OTHER_BID_ID,
+            1
+        ));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::AcceptRFPBid(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                    OTHER_BID_ID,
+                    1
+                )
+        ));
+        assert!(
+            PalletPayments::proposed_payment_agreements(
+                (ACCOUNT_ID, BIDDER_ID, PAYMENT_ID + 1)
+            ).is_some()
+        );
+
+        // The RFP is complete once its last lot is left unawarded
+        assert_noop!(
+            RFPModule::leave_lot_unawarded(Origin::signed(ACCOUNT_ID), RFP_ID, 1),
+            Error::<Test>::LotAlreadyDecided
+        );
+        assert_ok!(RFPModule::leave_lot_unawarded(Origin::signed(ACCOUNT_ID), RFP_ID, 2));
+        System::assert_has_event(
+            mock::Event::RFPModule(
+                crate::Event::LotLeftUnawarded(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                    2
+                )
+        ));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::RFPAwardsCompleted(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                )
+        ));
+        assert_eq!(
+            RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap().rfp_status,
+            RFPStatus::AcceptedBid
+        );
+        assert_eq!(RFPModule::rfp_to_accepted_bid((ACCOUNT_ID, RFP_ID, 0)), Some(BID_ID));
+        assert_eq!(RFPModule::rfp_to_accepted_bid((ACCOUNT_ID, RFP_ID, 1)), Some(OTHER_BID_ID));
+        assert!(RFPModule::rfp_unawarded_lots((ACCOUNT_ID, RFP_ID, 2)).is_some());
+        assert_eq!(RFPModule::rfp_lots_decided(ACCOUNT_ID, RFP_ID), 3);
+    })
+}
+
+#[test]
+fn test_rfp_with_every_lot_unawarded_closes_without_award() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            RFPDetails::<Test> {
+                rfp_owner: ACCOUNT_ID,
+                ipfs_hash,
+                rfp_status: RFPStatus::AcceptingBids,
+                bid_open: 0,
+                bid_close: 100,
+                decision_deadline: None,
+                bidding_mode: BiddingMode::Open,
+                bid_bond: 0,
+                lots: 2,
+                requirements: Default::default(),
+                budget_escrow: None,
+                visibility: RFPVisibility::Public,
+                invite_credential: None,
+                evaluation: Default::default(),
+            },
+        ));
+        assert_ok!(RFPModule::leave_lot_unawarded(Origin::signed(ACCOUNT_ID), RFP_ID, 0));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::LotLeftUnawarded(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                    0
+                )
+        ));
+        assert_ok!(RFPModule::leave_lot_unawarded(Origin::signed(ACCOUNT_ID), RFP_ID, 1));
+        System::assert_last_event(
+            mock::Event::RFPModule(
+                crate::Event::RFPClosedWithoutAward(
+                    ACCOUNT_ID, 
+                    RFP_ID,
+                )
+        ));
+        assert_eq!(
+            RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap().rfp_status,
+            RFPStatus::Expired
+        );
+    })
+}
+
+#[test]
+fn test_migrate_accepted_bids_to_lots() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        migrations::v2::v1::RFPs::<Test>::insert(
+            ACCOUNT_ID,
+            RFP_ID,
+            migrations::v2::v1::RFPDetails::<Test> {
+                rfp_owner: ACCOUNT_ID,
+                ipfs_hash,
+                rfp_status: RFPStatus::AcceptedBid,
+                bid_open: 0,
+                bid_close: 100,
+                decision_deadline: None,
+                bidding_mode: BiddingMode::Open,
+                bid_bond: 0,
+            }
+        );
+        migrations::v2::v1::RFPToAcceptedBid::<Test>::insert(ACCOUNT_ID, RFP_ID, BID_ID);
+        StorageVersion::new(1).put::<RFPModule>();
+
+        migrations::v2::migrate::<Test>();
+        assert_eq!(StorageVersion::get::<RFPModule>(), StorageVersion::new(2));
+        let rfp_details = migrations::v3::v2::RFPs::<Test>::get(ACCOUNT_ID, RFP_ID).unwrap();
+        assert_eq!(rfp_details.lots, 1);
+        assert_eq!(rfp_details.rfp_status, RFPStatus::AcceptedBid);
+        assert_eq!(RFPModule::rfp_to_accepted_bid((ACCOUNT_ID, RFP_ID, 0)), Some(BID_ID));
+        assert_eq!(RFPModule::rfp_lots_decided(ACCOUNT_ID, RFP_ID), 1);
+
+        // RFPs migrated to keep their requirements on-chain have none
+        migrations::v3::migrate::<Test>();
+        assert_eq!(StorageVersion::get::<RFPModule>(), StorageVersion::new(3));
+        let rfp_details = migrations::v4::v3::RFPs::<Test>::get(ACCOUNT_ID, RFP_ID).unwrap();
+        assert_eq!(rfp_details.lots, 1);
+        assert_eq!(rfp_details.requirements.budget_min, None);
+        assert!(rfp_details.requirements.category_tags.is_empty());
+
+        // and require no credentials
+        migrations::v4::migrate::<Test>();
+        assert_eq!(StorageVersion::get::<RFPModule>(), StorageVersion::new(4));
+        let rfp_details = migrations::v5::v4::RFPs::<Test>::get(ACCOUNT_ID, RFP_ID).unwrap();
+        assert_eq!(rfp_details.lots, 1);
+        assert_eq!(rfp_details.requirements, RFPRequirements::<Test>::default());
+
+        // and no budget escrow
+        migrations::v5::migrate::<Test>();
+        assert_eq!(StorageVersion::get::<RFPModule>(), StorageVersion::new(5));
+        let rfp_details = migrations::v6::v5::RFPs::<Test>::get(ACCOUNT_ID, RFP_ID).unwrap();
+        assert_eq!(rfp_details.lots, 1);
+        assert_eq!(rfp_details.budget_escrow, None);
+
+        // and are public
+        migrations::v6::migrate::<Test>();
+        assert_eq!(StorageVersion::get::<RFPModule>(), StorageVersion::new(6));
+        let rfp_details = migrations::v7::v6::RFPs::<Test>::get(ACCOUNT_ID, RFP_ID).unwrap();
+        assert_eq!(rfp_details.lots, 1);
+        assert_eq!(rfp_details.visibility, RFPVisibility::Public);
+        assert_eq!(rfp_details.invite_credential, None);
+
+        // and are awarded at their owner's discretion
+        migrations::v7::migrate::<Test>();
+        assert_eq!(StorageVersion::get::<RFPModule>(), StorageVersion::new(7));
+        let rfp_details = RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap();
+        assert_eq!(rfp_details.lots, 1);
+        assert_eq!(rfp_details.evaluation, EvaluationTerms::<Test>::default());
+    })
+}
+
+#[test]
+fn test_bids_must_meet_rfp_requirements() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: RFPRequirements::<Test> {
+                budget_min: Some(2000),
+                budget_max: Some(1000),
+                asset_id: None,
+                category_tags: bounded_vec![bounded_vec![b'i', b't']],
+                required_credentials: bounded_vec![],
+            },
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_noop!(
+            RFPModule::create_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ),
+            Error::<Test>::InvalidBudgetRange
+        );
+        rfp_details.requirements.budget_min = Some(1000);
+        rfp_details.requirements.budget_max = Some(2000);
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        assert_eq!(RFPModule::get_rfps(ACCOUNT_ID, RFP_ID).unwrap(), rfp_details);
+
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let mut bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: 2500,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                BID_ID,
+                bid_details.clone()
+            ),
+            Error::<Test>::BidOutsideBudget
+        );
+        bid_details.bid_amount = BID_AMOUNT;
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            bid_details
+        ));
+        assert_noop!(
+            RFPModule::update_rfp_bid(
+                Origin::signed(BIDDER_ID),
+                RFP_ID,
+                BID_ID,
+                bid_cid_hash,
+                500
+            ),
+            Error::<Test>::BidOutsideBudget
+        );
+
+        // Awarded bids are paid in the asset the RFP names
+        assert_ok!(RFPModule::accept_rfp_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            BID_ID,
+            LOT
+        ));
+        let payment_details = PalletPayments::proposed_payment_agreements(
+            (ACCOUNT_ID, BIDDER_ID, 0)
+        ).unwrap().payment_details;
+        assert_eq!(payment_details.asset_id, rfp_details.requirements.asset_id);
+    })
+}
+
+#[test]
+fn test_bidders_must_hold_required_credentials() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let credential_id: u128 = 7;
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: RFPRequirements::<Test> {
+                required_credentials: bounded_vec![credential_id],
+                ..Default::default()
+            },
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                BID_ID,
+                bid_details.clone()
+            ),
+            Error::<Test>::MissingRequiredCredential
+        );
+
+        // The bidder accepts a badge from the required collection
+        assert_ok!(NTNFTModule::create_collection(
+            Origin::signed(ACCOUNT_ID),
+            credential_id,
+            bounded_vec![b'i'],
+            bounded_vec![b'm'],
+        ));
+        assert_ok!(NTNFTModule::mint_ntnft(Origin::signed(ACCOUNT_ID), credential_id, 1));
+        assert_ok!(NTNFTModule::assign_ntnft(Origin::signed(ACCOUNT_ID), credential_id, 1, BIDDER_ID));
+        assert_ok!(NTNFTModule::accept_assignment(Origin::signed(BIDDER_ID), credential_id, 1));
+
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            bid_details.clone()
+        ));
+
+        // The credentialed bidder can't place a bid owned by,
+        // and paid out to, an account without the credential
+        let mut uncredentialed_bid_details = bid_details;
+        uncredentialed_bid_details.bid_owner = ACCOUNT_ID;
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                OTHER_BID_ID,
+                uncredentialed_bid_details
+            ),
+            Error::<Test>::BidOwnerMismatch
+        );
+    })
+}
+
+#[test]
+fn test_escrow_backed_rfp_pays_accepted_bid_from_budget() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: Some(ACCOUNT_ID),
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_noop!(
+            RFPModule::create_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ),
+            Error::<Test>::BudgetEscrowWithoutBudget
+        );
+
+        // The owner's escrow is created, and the budget locked in it
+        rfp_details.requirements.budget_max = Some(2000);
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        System::assert_has_event(mock::Event::RFPModule(
+            crate::Event::RFPBudgetLocked(ACCOUNT_ID, RFP_ID, ACCOUNT_ID, 2000)
+        ));
+        assert!(EscrowModule::escrow(ACCOUNT_ID).is_some());
+        assert_eq!(EscrowModule::total_reserved_funds(ACCOUNT_ID, None::<u32>), 2000);
+        assert_eq!(RFPModule::rfp_budget_reserved(ACCOUNT_ID, RFP_ID), 2000);
+
+        rfp_details.requirements.budget_max = Some(3000);
+        assert_noop!(
+            RFPModule::update_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details,
+            ),
+            Error::<Test>::BudgetEscrowLocked
+        );
+
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            BidDetails::<Test> {
+                bid_owner: BIDDER_ID,
+                ipfs_hash: bid_cid_hash,
+                bid_amount: BID_AMOUNT,
+                bid_status: BidStatus::Submitted,
+                version: 0,
+            }
+        ));
+
+        // The agreement is built from the bid and paid out of the escrow,
+        // and the rest of the budget is released once the RFP is decided
+        assert_ok!(RFPModule::accept_rfp_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            BID_ID,
+            LOT
+        ));
+        System::assert_has_event(mock::Event::RFPModule(
+            crate::Event::RFPBudgetReleased(ACCOUNT_ID, RFP_ID, ACCOUNT_ID, 2000 - BID_AMOUNT)
+        ));
+        assert_eq!(EscrowModule::total_reserved_funds(ACCOUNT_ID, None::<u32>), BID_AMOUNT);
+        assert_eq!(RFPModule::rfp_budget_reserved(ACCOUNT_ID, RFP_ID), 0);
+        let payment_id: u32 = 0;
+        let payment_details = PalletPayments::proposed_payment_agreements(
+            (ACCOUNT_ID, BIDDER_ID, payment_id)
+        ).unwrap().payment_details;
+        assert_eq!(payment_details.total_payment_amount, BID_AMOUNT);
+        assert_eq!(payment_details.rfp_reference_id, RFP_ID);
+        assert_eq!(
+            payment_details.payment_method,
+            pallet_payments::PaymentMethod::<Test>{
+                payment_source: pallet_payments::PaymentSource::EscrowAccount,
+                account_id: ACCOUNT_ID,
+            }
+        );
+
+        let bidder_balance = Balances::free_balance(BIDDER_ID);
+        assert_ok!(PalletPayments::accept_payment(Origin::signed(BIDDER_ID), ACCOUNT_ID, payment_id));
+        assert_ok!(PalletPayments::claim(Origin::signed(BIDDER_ID), ACCOUNT_ID, payment_id));
+        assert_eq!(Balances::free_balance(BIDDER_ID), bidder_balance + BID_AMOUNT);
+        assert_eq!(EscrowModule::total_reserved_funds(ACCOUNT_ID, None::<u32>), 0);
+    })
+}
+
+#[test]
+fn test_accept_builds_payment_from_bid_without_budget_escrow() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            RFPDetails::<Test> {
+                rfp_owner: ACCOUNT_ID,
+                ipfs_hash,
+                rfp_status: RFPStatus::AcceptingBids,
+                bid_open: 0,
+                bid_close: 100,
+                decision_deadline: None,
+                bidding_mode: BiddingMode::Open,
+                bid_bond: 0,
+                lots: 1,
+                requirements: Default::default(),
+                budget_escrow: None,
+                visibility: RFPVisibility::Public,
+                invite_credential: None,
+                evaluation: Default::default(),
+            },
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            BidDetails::<Test> {
+                bid_owner: BIDDER_ID,
+                ipfs_hash: bid_cid_hash,
+                bid_amount: BID_AMOUNT,
+                bid_status: BidStatus::Submitted,
+                version: 0,
+            }
+        ));
+        // Without a budget escrow the agreement is paid out of the owner's account
+        assert_ok!(RFPModule::accept_rfp_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            BID_ID,
+            LOT
+        ));
+        let payment_id: u32 = 0;
+        let payment_details = PalletPayments::proposed_payment_agreements(
+            (ACCOUNT_ID, BIDDER_ID, payment_id)
+        ).unwrap().payment_details;
+        assert_eq!(payment_details.payee, BIDDER_ID);
+        assert_eq!(payment_details.total_payment_amount, BID_AMOUNT);
+        assert_eq!(payment_details.rfp_reference_id, RFP_ID);
+        assert_eq!(
+            payment_details.payment_method,
+            pallet_payments::PaymentMethod::<Test>{
+                payment_source: pallet_payments::PaymentSource::PersonalAccount,
+                account_id: ACCOUNT_ID,
+            }
+        );
+
+        let bidder_balance = Balances::free_balance(BIDDER_ID);
+        assert_ok!(PalletPayments::accept_payment(Origin::signed(BIDDER_ID), ACCOUNT_ID, payment_id));
+        assert_ok!(PalletPayments::claim(Origin::signed(BIDDER_ID), ACCOUNT_ID, payment_id));
+        assert_eq!(Balances::free_balance(BIDDER_ID), bidder_balance + BID_AMOUNT);
+    })
+}
+
+#[test]
+fn test_update_rfp_cannot_change_owner_or_status() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+
+        let mut new_rfp_details = rfp_details.clone();
+        new_rfp_details.rfp_owner = BIDDER_ID;
+        assert_noop!(
+            RFPModule::update_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                new_rfp_details,
+            ),
+            Error::<Test>::RFPOwnerChanged
+        );
+
+        let mut new_rfp_details = rfp_details;
+        new_rfp_details.rfp_status = RFPStatus::AcceptedBid;
+        assert_noop!(
+            RFPModule::update_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                new_rfp_details,
+            ),
+            Error::<Test>::RFPStatusChanged
+        );
+        assert_eq!(RFPModule::rfp_amendment_count(ACCOUNT_ID, RFP_ID), 0);
+    })
+}
+
+#[test]
+fn test_update_rfp_cannot_change_bidding_terms_once_bid_on() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+
+        // The bidding terms can change until the first bid is placed
+        rfp_details.bid_bond = 5;
+        assert_ok!(RFPModule::update_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            BidDetails::<Test> {
+                bid_owner: BIDDER_ID,
+                ipfs_hash: bid_cid_hash,
+                bid_amount: BID_AMOUNT,
+                bid_status: BidStatus::Submitted,
+                version: 0,
+            }
+        ));
+
+        let mut new_rfp_details = rfp_details.clone();
+        new_rfp_details.bidding_mode = BiddingMode::Sealed;
+        assert_noop!(
+            RFPModule::update_rfp(Origin::signed(ACCOUNT_ID), RFP_ID, new_rfp_details),
+            Error::<Test>::BiddingTermsLocked
+        );
+        let mut new_rfp_details = rfp_details.clone();
+        new_rfp_details.bid_bond = 0;
+        assert_noop!(
+            RFPModule::update_rfp(Origin::signed(ACCOUNT_ID), RFP_ID, new_rfp_details),
+            Error::<Test>::BiddingTermsLocked
+        );
+        let mut new_rfp_details = rfp_details.clone();
+        new_rfp_details.visibility = RFPVisibility::Private;
+        assert_noop!(
+            RFPModule::update_rfp(Origin::signed(ACCOUNT_ID), RFP_ID, new_rfp_details),
+            Error::<Test>::BiddingTermsLocked
+        );
+        let mut new_rfp_details = rfp_details.clone();
+        new_rfp_details.invite_credential = Some(1);
+        assert_noop!(
+            RFPModule::update_rfp(Origin::signed(ACCOUNT_ID), RFP_ID, new_rfp_details),
+            Error::<Test>::BiddingTermsLocked
+        );
+
+        // Other terms can still be amended
+        rfp_details.ipfs_hash = OTHER_CID.as_bytes().to_vec().try_into().unwrap();
+        assert_ok!(RFPModule::update_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        assert_eq!(RFPModule::rfp_amendment_count(ACCOUNT_ID, RFP_ID), 2);
+    })
+}
+
+#[test]
+fn test_bidders_must_acknowledge_rfp_amendments() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        for bid_id in [BID_ID, OTHER_BID_ID] {
+            assert_ok!(RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                bid_id,
+                BidDetails::<Test> {
+                    bid_owner: BIDDER_ID,
+                    ipfs_hash: bid_cid_hash,
+                    bid_amount: BID_AMOUNT,
+                    bid_status: BidStatus::Submitted,
+                    version: 0,
+                }
+            ));
+        }
+
+        let new_cid: Vec<u8> = OTHER_CID.as_bytes().to_vec();
+        let mut new_rfp_details = rfp_details;
+        new_rfp_details.ipfs_hash = new_cid.try_into().unwrap();
+        assert_ok!(RFPModule::update_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            new_rfp_details,
+        ));
+        assert_noop!(
+            RFPModule::shortlist_bid(Origin::signed(ACCOUNT_ID), RFP_ID, BID_ID),
+            Error::<Test>::AmendmentNotAcknowledged
+        );
+
+        // Bidders acknowledge the amendment themselves
+        assert_noop!(
+            RFPModule::acknowledge_rfp_amendment(Origin::signed(ACCOUNT_ID), BID_ID),
+            Error::<Test>::UnauthorizedAcknowledgementOfAmendment
+        );
+        assert_ok!(RFPModule::acknowledge_rfp_amendment(Origin::signed(BIDDER_ID), BID_ID));
+        System::assert_last_event(mock::Event::RFPModule(
+            crate::Event::RFPAmendmentAcknowledged(BIDDER_ID, RFP_ID, BID_ID, 1)
+        ));
+        assert_ok!(RFPModule::shortlist_bid(Origin::signed(ACCOUNT_ID), RFP_ID, BID_ID));
+
+        // Updating a bid takes the amendment into account as well
+        assert_ok!(RFPModule::update_rfp_bid(
+            Origin::signed(BIDDER_ID),
+            RFP_ID,
+            OTHER_BID_ID,
+            bid_cid_hash,
+            NEW_BID_AMOUNT
+        ));
+        assert_eq!(RFPModule::bid_acknowledged_amendment(OTHER_BID_ID), 1);
+        assert_ok!(RFPModule::shortlist_bid(Origin::signed(ACCOUNT_ID), RFP_ID, OTHER_BID_ID));
+    })
+}
+
+#[test]
+fn test_private_rfps_only_take_bids_from_invitees() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let credential_id: u128 = 7;
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            RFPDetails::<Test> {
+                rfp_owner: ACCOUNT_ID,
+                ipfs_hash,
+                rfp_status: RFPStatus::AcceptingBids,
+                bid_open: 0,
+                bid_close: 100,
+                decision_deadline: None,
+                bidding_mode: BiddingMode::Open,
+                bid_bond: 0,
+                lots: 1,
+                requirements: Default::default(),
+                budget_escrow: None,
+                visibility: RFPVisibility::Private,
+                invite_credential: Some(credential_id),
+                evaluation: Default::default(),
+            },
+        ));
+
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                BID_ID,
+                bid_details.clone()
+            ),
+            Error::<Test>::BidderNotInvited
+        );
+
+        assert_ok!(RFPModule::add_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, BIDDER_ID));
+        System::assert_last_event(mock::Event::RFPModule(
+            crate::Event::BidderInvited(ACCOUNT_ID, RFP_ID, BIDDER_ID)
+        ));
+        assert_noop!(
+            RFPModule::add_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, BIDDER_ID),
+            Error::<Test>::AlreadyInvited
+        );
+
+        // The invitee can't place a bid on behalf of an account that isn't invited
+        let mut uninvited_bid_details = bid_details.clone();
+        uninvited_bid_details.bid_owner = 3;
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                BID_ID,
+                uninvited_bid_details
+            ),
+            Error::<Test>::BidOwnerMismatch
+        );
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            BID_ID,
+            bid_details.clone()
+        ));
+        assert_eq!(RFPModule::bid_bonds(BID_ID).unwrap().bidder, BIDDER_ID);
+        assert_eq!(RFPModule::all_bids(BID_ID).unwrap().bid_owner, BIDDER_ID);
+
+        // Withdrawing the invitation keeps the bid, but stops new ones
+        assert_ok!(RFPModule::remove_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, BIDDER_ID));
+        System::assert_last_event(mock::Event::RFPModule(
+            crate::Event::BidderUninvited(ACCOUNT_ID, RFP_ID, BIDDER_ID)
+        ));
+        assert_noop!(
+            RFPModule::remove_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, BIDDER_ID),
+            Error::<Test>::NotAnInvitee
+        );
+        assert!(RFPModule::all_bids(BID_ID).is_some());
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                OTHER_BID_ID,
+                bid_details.clone()
+            ),
+            Error::<Test>::BidderNotInvited
+        );
+
+        // Holders of the invite credential can bid without an invitation
+        assert_ok!(NTNFTModule::create_collection(
+            Origin::signed(ACCOUNT_ID),
+            credential_id,
+            bounded_vec![b'i'],
+            bounded_vec![b'm'],
+        ));
+        assert_ok!(NTNFTModule::mint_ntnft(Origin::signed(ACCOUNT_ID), credential_id, 1));
+        assert_ok!(NTNFTModule::assign_ntnft(Origin::signed(ACCOUNT_ID), credential_id, 1, BIDDER_ID));
+        assert_ok!(NTNFTModule::accept_assignment(Origin::signed(BIDDER_ID), credential_id, 1));
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            OTHER_BID_ID,
+            bid_details
+        ));
+
+        // An RFP only takes so many invitees
+        assert_ok!(RFPModule::add_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, 1));
+        assert_ok!(RFPModule::add_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, 2));
+        assert_noop!(
+            RFPModule::add_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, 3),
+            Error::<Test>::TooManyInvitees
+        );
+        assert_ok!(RFPModule::remove_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, 1));
+        assert_ok!(RFPModule::add_rfp_invitee(Origin::signed(ACCOUNT_ID), RFP_ID, 3));
+        assert_eq!(RFPModule::rfp_invitee_count(ACCOUNT_ID, RFP_ID), 2);
+    })
+}
+
+#[test]
+fn test_top_scoring_bid_is_accepted() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let mut rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: EvaluationTerms::<Test> {
+                criteria_weights: bounded_vec![],
+                award_rule: AwardRule::TopScore,
+            },
+        };
+        assert_noop!(
+            RFPModule::create_rfp(
+                Origin::signed(ACCOUNT_ID),
+                RFP_ID,
+                rfp_details.clone(),
+            ),
+            Error::<Test>::InvalidEvaluationCriteria
+        );
+        rfp_details.evaluation.criteria_weights = bounded_vec![3, 1];
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details.clone(),
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        for bid_id in [BID_ID, OTHER_BID_ID] {
+            assert_ok!(RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                bid_id,
+                BidDetails::<Test> {
+                    bid_owner: BIDDER_ID,
+                    ipfs_hash: bid_cid_hash,
+                    bid_amount: BID_AMOUNT,
+                    bid_status: BidStatus::Submitted,
+                    version: 0,
+                }
+            ));
+        }
+        assert_noop!(
+            RFPModule::accept_rfp_bid(Origin::signed(ACCOUNT_ID), RFP_ID, BID_ID, LOT),
+            Error::<Test>::NoEvaluationCommittee
+        );
+        // A member can't be counted more than once
+        assert_noop!(
+            RFPModule::set_evaluation_committee(Origin::signed(ACCOUNT_ID), RFP_ID, bounded_vec![3, 4, 3]),
+            Error::<Test>::DuplicateCommitteeMember
+        );
+        assert_ok!(RFPModule::set_evaluation_committee(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            bounded_vec![3, 4]
+        ));
+        System::assert_last_event(mock::Event::RFPModule(
+            crate::Event::EvaluationCommitteeSet(ACCOUNT_ID, RFP_ID)
+        ));
+
+        // Only committee members score bids, once per criterion
+        assert_noop!(
+            RFPModule::score_bid(Origin::signed(BIDDER_ID), BID_ID, bounded_vec![5, 5]),
+            Error::<Test>::NotCommitteeMember
+        );
+        assert_noop!(
+            RFPModule::score_bid(Origin::signed(3), BID_ID, bounded_vec![5]),
+            Error::<Test>::ScoresDontMatchCriteria
+        );
+        assert_noop!(
+            RFPModule::score_bid(Origin::signed(3), BID_ID, bounded_vec![11, 5]),
+            Error::<Test>::ScoreTooHigh
+        );
+        assert_ok!(RFPModule::score_bid(Origin::signed(3), BID_ID, bounded_vec![5, 5]));
+        System::assert_last_event(mock::Event::RFPModule(
+            crate::Event::BidScored(3, RFP_ID, BID_ID, 20)
+        ));
+        assert_ok!(RFPModule::score_bid(Origin::signed(3), OTHER_BID_ID, bounded_vec![10, 0]));
+        assert_ok!(RFPModule::score_bid(Origin::signed(4), BID_ID, bounded_vec![10, 10]));
+        assert_eq!(
+            RFPModule::bid_evaluations(BID_ID),
+            BidEvaluation { weighted_total: 60, scorers: 2, approvals: 0 }
+        );
+
+        // The committee and its criteria are locked once scoring starts
+        assert_noop!(
+            RFPModule::set_evaluation_committee(Origin::signed(ACCOUNT_ID), RFP_ID, bounded_vec![3]),
+            Error::<Test>::EvaluationLocked
+        );
+        rfp_details.evaluation.criteria_weights = bounded_vec![1, 3];
+        assert_noop!(
+            RFPModule::update_rfp(Origin::signed(ACCOUNT_ID), RFP_ID, rfp_details),
+            Error::<Test>::EvaluationLocked
+        );
+
+        // Scoring a bid again replaces the member's previous scores
+        assert_ok!(RFPModule::score_bid(Origin::signed(3), BID_ID, bounded_vec![0, 0]));
+        assert_eq!(
+            RFPModule::bid_evaluations(BID_ID),
+            BidEvaluation { weighted_total: 40, scorers: 2, approvals: 0 }
+        );
+        assert_noop!(
+            RFPModule::accept_rfp_bid(Origin::signed(ACCOUNT_ID), RFP_ID, BID_ID, LOT),
+            Error::<Test>::NotTopScoringBid
+        );
+        assert_ok!(RFPModule::accept_rfp_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            OTHER_BID_ID,
+            LOT
+        ));
+    })
+}
+
+#[test]
+fn test_rfp_takes_at_most_max_bids() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 100,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: Default::default(),
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        let bid_details = BidDetails::<Test> {
+            bid_owner: BIDDER_ID,
+            ipfs_hash: bid_cid_hash,
+            bid_amount: BID_AMOUNT,
+            bid_status: BidStatus::Submitted,
+            version: 0,
+        };
+        // The award rule checks every bid on the RFP, so their number is capped
+        let max_bids: u32 = <Test as Config>::MaxBidsPerRFP::get();
+        for bid_id in 0..max_bids {
+            assert_ok!(RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                bid_id,
+                bid_details.clone()
+            ));
+        }
+        assert_noop!(
+            RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                max_bids,
+                bid_details.clone()
+            ),
+            Error::<Test>::TooManyBids
+        );
+
+        // Withdrawing a bid makes room for another
+        assert_ok!(RFPModule::withdraw_bid(Origin::signed(BIDDER_ID), 0));
+        assert_ok!(RFPModule::bid_on_rfp(
+            Origin::signed(BIDDER_ID),
+            ACCOUNT_ID,
+            RFP_ID,
+            max_bids,
+            bid_details
+        ));
+        assert_eq!(RFPModule::rfp_to_bids(ACCOUNT_ID, RFP_ID).unwrap().len() as u32, max_bids);
+    })
+}
+
+#[test]
+fn test_committee_approval_is_required_to_accept_bid() {
+    let mut t = test_externalities();
+    t.execute_with(||
+    {
+        let cid: Vec<u8> = RFP_CID.as_bytes().to_vec();
+        let ipfs_hash: [u8; 59] = cid.try_into().unwrap();
+        let rfp_details = RFPDetails::<Test> {
+            rfp_owner: ACCOUNT_ID,
+            ipfs_hash,
+            rfp_status: RFPStatus::AcceptingBids,
+            bid_open: 0,
+            bid_close: 100,
+            decision_deadline: None,
+            bidding_mode: BiddingMode::Open,
+            bid_bond: 0,
+            lots: 1,
+            requirements: Default::default(),
+            budget_escrow: None,
+            visibility: RFPVisibility::Public,
+            invite_credential: None,
+            evaluation: EvaluationTerms::<Test> {
+                criteria_weights: bounded_vec![],
+                award_rule: AwardRule::CommitteeApproval,
+            },
+        };
+        assert_ok!(RFPModule::create_rfp(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            rfp_details,
+        ));
+        let bid_cid: Vec<u8> = BID_CID.as_bytes().to_vec();
+        let bid_cid_hash: [u8; 59] = bid_cid.try_into().unwrap();
+        for bid_id in [BID_ID, OTHER_BID_ID] {
+            assert_ok!(RFPModule::bid_on_rfp(
+                Origin::signed(BIDDER_ID),
+                ACCOUNT_ID,
+                RFP_ID,
+                bid_id,
+                BidDetails::<Test> {
+                    bid_owner: BIDDER_ID,
+                    ipfs_hash: bid_cid_hash,
+                    bid_amount: BID_AMOUNT,
+                    bid_status: BidStatus::Submitted,
+                    version: 0,
+                }
+            ));
+        }
+        assert_ok!(RFPModule::set_evaluation_committee(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            bounded_vec![3, 4, 5]
+        ));
+
+        assert_ok!(RFPModule::approve_bid(Origin::signed(3), BID_ID));
+        System::assert_last_event(mock::Event::RFPModule(
+            crate::Event::BidApproved(3, RFP_ID, BID_ID)
+        ));
+        assert_noop!(
+            RFPModule::approve_bid(Origin::signed(3), BID_ID),
+            Error::<Test>::BidAlreadyApproved
+        );
+        assert_noop!(
+            RFPModule::accept_rfp_bid(Origin::signed(ACCOUNT_ID), RFP_ID, BID_ID, LOT),
+            Error::<Test>::CommitteeApprovalRequired
+        );
+
+        // A majority of the committee approves the bid
+        assert_ok!(RFPModule::approve_bid(Origin::signed(4), BID_ID));
+        assert_eq!(RFPModule::bid_evaluations(BID_ID).approvals, 2);
+        assert_ok!(RFPModule::accept_rfp_bid(
+            Origin::signed(ACCOUNT_ID),
+            RFP_ID,
+            BID_ID,
+            LOT
+        ));
+    })
 }
\ No newline at end of file
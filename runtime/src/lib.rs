@@ -25,7 +25,7 @@ use sp_version::RuntimeVersion;
 
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
-	construct_runtime, parameter_types,
+	construct_runtime, parameter_types, PalletId,
 	traits::{
 		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
 	},
@@ -110,10 +110,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -274,12 +274,39 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type AssetAccountDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type MetadataDepositBase = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type MetadataDepositPerByte = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type ApprovalDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const EscrowPalletId: PalletId = PalletId(*b"py/escrw");
+}
+
 /// Configure the escrow pallet in pallets/escrow.
 impl pallet_escrow::Config for Runtime {
 	type Event = Event;
 	type EscrowCurrency = Balances;
+	type AssetId = u32;
+	type EscrowAssets = Assets;
+	type PalletId = EscrowPalletId;
 }
 
+/// The receipt of a completed payment agreement, as returned by the payments RPC
+pub type PaymentReceipt = pallet_payments::PaymentReceiptOf<Runtime>;
+
 // Configure the payments contract pallet in pallets/payments
 impl pallet_payments::Config for Runtime {
 	type Event = Event;
@@ -287,6 +314,13 @@ impl pallet_payments::Config for Runtime {
 	type RFPReferenceId = u32;
 	type PaymentCurrency = Balances;
 	type TimeProvider = pallet_timestamp::Pallet<Runtime>;
+	type Assets = Assets;
+	type Escrow = Escrow;
+	// Payees have a week to accept a proposed payment agreement
+	type PaymentProposalExpiry = ConstU64<{ 7 * 24 * 60 * 60 }>;
+	type MaxBatchSize = ConstU32<100>;
+	type CredentialCollectionId = u128;
+	type Credentials = NTNFT;
 } 
 
 // Configure the rfp pallet in pallets/rfp
@@ -296,6 +330,24 @@ impl pallet_rfp::Config for Runtime {
 	type Currency = Balances;
 	type Cid = [u8; 59];
 	type BidId = u32;
+	type MaxRFPDeadlinesPerBlock = ConstU32<100>;
+	type BidDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type MaxBidsCleanedPerCall = ConstU32<100>;
+	type MaxLotsPerRFP = ConstU32<20>;
+	type MaxCategoryTags = ConstU32<10>;
+	type MaxCategoryTagLength = ConstU32<32>;
+	type CredentialId = u128;
+	type Credentials = NTNFT;
+	type MaxRequiredCredentials = ConstU32<10>;
+	type MaxEvaluationCriteria = ConstU32<10>;
+	type MaxCommitteeMembers = ConstU32<10>;
+	type MaxCriterionScore = ConstU8<10>;
+	type RevealPeriod = ConstU32<{ 2 * DAYS }>;
+	type MaxBidsPerRFP = ConstU32<500>;
+	type MaxSealedBidsPerRFP = ConstU32<100>;
+	type MaxBidVersions = ConstU32<50>;
+	type MaxRFPAmendments = ConstU32<50>;
+	type MaxRFPInvitees = ConstU32<100>;
 } 
 
 // Configure the NT-NFT pallet in pallets/nt-nft
@@ -326,6 +378,7 @@ construct_runtime!(
 		Payments: pallet_payments,
 		RFP: pallet_rfp,
 		NTNFT: pallet_ntnft,
+		Assets: pallet_assets,
 	}
 );
 
@@ -369,6 +422,7 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_assets, Assets]
 		[pallet_timestamp, Timestamp]
 		[pallet_escrow, Escrow]
 		[pallet_payments, Payments]
@@ -508,6 +562,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_payments_runtime_api::PaymentsApi<Block, AccountId, PaymentReceipt> for Runtime {
+		fn payment_receipts(payee: AccountId) -> Vec<PaymentReceipt> {
+			Payments::receipts_of(&payee)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, Call>
 		for Runtime
 	{